@@ -38,7 +38,7 @@ use figment::{
 	Figment,
 	providers::{Env, Format as _, Serialized, Toml},
 };
-use patchify::client::{Config as UpdaterConfig, Updater};
+use patchify::client::{Config as UpdaterConfig, DrainPolicy, HttpSettings, ProgressPolicy, RestartStrategy, RetryPolicy, UpdatePolicy, Updater};
 use rubedo::crypto::VerifyingKey;
 use semver::Version;
 use serde::{Deserialize, Serialize};
@@ -113,9 +113,27 @@ async fn main() {
 	let _updater = Updater::new(UpdaterConfig {
 		version:          app_version.clone(),
 		api:              config.updater_api_server.parse().expect("Invalid updater API server URL"),
+		mirrors:          vec![],
+		source:           None,
+		http:             HttpSettings::default(),
+		token:            None,
 		key:              config.updater_api_key,
 		check_on_startup: config.update_on_startup,
 		check_interval:   config.update_interval.map(Duration::from_secs),
+		check_schedule:   None,
+		check_jitter:     Duration::ZERO,
+		push_updates:     false,
+		bundle_dir:       None,
+		update_policy:    UpdatePolicy::Automatic,
+		maintenance:      None,
+		retry_policy:     RetryPolicy::default(),
+		failure_cooldown: Duration::from_secs(60),
+		shutdown_timeout: Duration::from_secs(30),
+		drain_policy:     DrainPolicy::default(),
+		progress_policy:  ProgressPolicy::default(),
+		restart_strategy: RestartStrategy::default(),
+		systemd_notify:   false,
+		consent:          None,
 	}).unwrap();
 	info!("Application started");
 	info!("{} v{app_version}", config.appname);
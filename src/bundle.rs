@@ -0,0 +1,130 @@
+//! This module provides portable update bundles, for offline installation.
+//! 
+//! Some applications run in places that cannot reach the update API, such as
+//! air-gapped sites. For these, a release can be exported as a single bundle
+//! file, carried to the site, and installed using the
+//! [`Updater`](crate::client::Updater), which verifies it in the same way as
+//! an update obtained from the API.
+//! 
+//! # Format
+//! 
+//! A bundle is a single file, made up of three header lines followed by the
+//! release file itself:
+//! 
+//!   1. The format identifier, `PATCHIFY-BUNDLE 1`.
+//!   2. The signed metadata, which is the same JSON payload as returned by the
+//!      `hashes/{version}` endpoint, containing the version and the SHA256
+//!      hash of the release file.
+//!   3. The signature of the metadata, in hexadecimal, generated using the
+//!      server's private key.
+//! 
+//! Each header line ends with a single newline character, and the rest of the
+//! file is the binary release file, unaltered. As the metadata is signed, and
+//! contains the hash of the release file, the whole bundle can be verified
+//! using only the server's public key.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "tests/bundle.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use crate::responses::VersionHashResponse;
+use ed25519_dalek::Signer as _;
+use rubedo::crypto::{Sha256Hash, SigningKey};
+use semver::Version;
+use std::{
+	fs::File,
+	io::{BufWriter, Error as IoError, Write as _, copy},
+	path::{Path, PathBuf},
+};
+use thiserror::Error as ThisError;
+
+
+
+//		Constants																										
+
+/// The identifier at the start of a bundle, which specifies the format.
+pub(crate) const BUNDLE_FORMAT: &str = "PATCHIFY-BUNDLE 1";
+
+/// The maximum length of a bundle header line, in bytes. This stops a file that
+/// is not a bundle from being read into memory in its entirety.
+pub(crate) const MAX_HEADER_LINE: u64 = 1_024;
+
+
+
+//		Enums																											
+
+//		BundleError																
+/// Errors that can occur when creating bundles.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[non_exhaustive]
+pub enum BundleError {
+	/// The release file for the bundle could not be read.
+	#[error("Unable to read release file {0:?}: {1}")]
+	UnableToReadRelease(PathBuf, String),
+	
+	/// The bundle file could not be written.
+	#[error("Unable to write bundle file {0:?}: {1}")]
+	UnableToWriteBundle(PathBuf, String),
+	
+	/// The requested version is not one of the available versions.
+	#[error("Version {0} not found")]
+	UnknownVersion(Version),
+}
+
+
+
+//		Functions																										
+
+//		write_bundle															
+/// Writes a bundle for a release.
+/// 
+/// This function creates a bundle file containing the specified release file,
+/// along with its metadata, signed using the specified private key. See the
+/// [module documentation](self) for details of the format.
+/// 
+/// The release file is copied into the bundle as it is, and is not checked
+/// against the hash. The hash is checked when the bundle is installed.
+/// 
+/// # Parameters
+/// 
+/// * `key`     - The server's private key.
+/// * `version` - The version of the release.
+/// * `hash`    - The SHA256 hash of the release file.
+/// * `release` - The path to the release file.
+/// * `bundle`  - The path to write the bundle to.
+/// 
+/// # Errors
+/// 
+/// * [`BundleError::UnableToReadRelease`]
+/// * [`BundleError::UnableToWriteBundle`]
+/// 
+#[expect(clippy::missing_panics_doc,  reason = "Infallible")]
+#[expect(clippy::unwrap_in_result,    reason = "Infallible")]
+#[expect(clippy::unwrap_used,         reason = "Infallible")]
+pub fn write_bundle(
+	key:     &SigningKey,
+	version: &Version,
+	hash:    Sha256Hash,
+	release: &Path,
+	bundle:  &Path,
+) -> Result<(), BundleError> {
+	let write_error = |err: IoError| BundleError::UnableToWriteBundle(bundle.to_owned(), err.to_string());
+	let payload     = serde_json::to_string(&VersionHashResponse { version: version.clone(), hash, url: None }).unwrap();
+	let signature   = key.sign(payload.as_bytes()).to_string();
+	let mut input   = File::open(release).map_err(|err|
+		BundleError::UnableToReadRelease(release.to_owned(), err.to_string())
+	)?;
+	let mut output  = BufWriter::new(File::create(bundle).map_err(write_error)?);
+	writeln!(output, "{BUNDLE_FORMAT}\n{payload}\n{signature}").map_err(write_error)?;
+	let _written    = copy(&mut input, &mut output).map_err(write_error)?;
+	output.flush().map_err(write_error)
+}
@@ -6,31 +6,46 @@
 //! and installed, and the application will be restarted. This will only occur
 //! once any critical actions that have been registered are complete.
 //! 
+//! How far the updater proceeds by itself, when and how it checks for updates,
+//! where updates come from, and how it talks to the API server are all set in
+//! the [`Config`]. See the [`consent`], [`maintenance`], [`schedule`], and
+//! [`source`] modules for the larger features.
+//! 
 //! # Critical actions
 //! 
 //! Critical actions are registered using the [`Updater::register_action()`]
-//! method, and deregistered using the [`Updater::deregister_action()`] method.
-//! When a critical action is in progress, the updater will not restart the
-//! application, and will wait until all critical actions are complete before
-//! doing so.
+//! method, and deregistered using the [`Updater::deregister_action()`] method,
+//! or more safely using [`Updater::guard_action()`]. When a critical action is
+//! in progress, the updater will not restart the application, and will wait
+//! until all critical actions are complete before doing so.
 //! 
 //! # Status
 //! 
 //! The [`Status`] enum is used to represent the possible statuses that the
 //! updater can have. The current status can be obtained at any time, using the
 //! [`Updater::status()`] method, and the status change events can be
-//! subscribed to using the [`Updater::subscribe()`] method.
+//! subscribed to using the [`Updater::subscribe()`],
+//! [`Updater::subscribe_events()`], or [`Updater::watch()`] methods.
 //! 
 //! # Failure
 //! 
 //! If an error occurs when trying to update, it will be logged, and the
-//! updater will stop checking for updates.
+//! status will be set to [`Status::Failed`]. After the cool-down period
+//! specified in the [`Config`], the status will return to [`Status::Idle`], so
+//! that the next check can go ahead.
 //! 
 
 
 
 //		Modules																											
 
+pub mod consent;
+pub mod listeners;
+pub mod maintenance;
+pub mod schedule;
+pub mod source;
+pub mod systemd;
+
 #[cfg(test)]
 #[path = "tests/client.rs"]
 mod tests;
@@ -39,21 +54,34 @@ mod tests;
 
 //		Packages																										
 
-use crate::responses::{LatestVersionResponse, VersionHashResponse};
+use crate::{
+	bundle::{BUNDLE_FORMAT, MAX_HEADER_LINE},
+	responses::{LatestVersionResponse, SignedEvent, VersionHashResponse},
+};
+use self::consent::{Consent, ConsentDecision, ConsentStep};
+use self::maintenance::MaintenanceSchedule;
+use self::schedule::CheckSchedule;
+use self::source::{HttpSource, SignedPayload, UpdateSource};
+use command_fds::FdMapping;
 use core::{
-	fmt::{Display, self},
-	str::FromStr,
-	sync::atomic::{AtomicUsize, Ordering},
+	fmt::{Debug, Display, self},
+	sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
 };
 use ed25519_dalek::Signature;
-use flume::{Sender, self};
-use futures_util::StreamExt as _;
+use flume::{Receiver, Sender, r#async::RecvStream, self};
+use futures_util::{StreamExt as _, future::BoxFuture};
 use hex;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
+use rand::{Rng as _, thread_rng};
 use reqwest::{
+	Certificate,
+	ClientBuilder,
+	NoProxy,
+	Proxy,
 	StatusCode,
 	Url,
-	header::{AsHeaderName, CONTENT_LENGTH, CONTENT_TYPE},
+	header::{HeaderMap, HeaderName, HeaderValue},
+	redirect::Policy as RedirectPolicy,
 };
 use rubedo::{
 	crypto::{Sha256Hash, VerifyingKey},
@@ -63,27 +91,37 @@ use semver::Version;
 use serde::de::DeserializeOwned;
 use sha2::{Sha256, Digest as _};
 use std::{
-	env::args,
-	io::Error as IoError,
-	os::unix::fs::PermissionsExt as _,
-	path::PathBuf,
-	sync::Arc,
+	collections::{BTreeMap, HashSet},
+	env::{args, var},
+	io::{Error as IoError, Result as IoResult},
+	os::{
+		fd::{AsFd, OwnedFd},
+		unix::fs::PermissionsExt as _,
+	},
+	path::{Path, PathBuf},
+	process::id as process_id,
+	sync::{Arc, Weak},
+	time::{SystemTime, UNIX_EPOCH},
 };
 use tempfile::{tempdir, TempDir};
 use thiserror::Error as ThisError;
 use tokio::{
 	fs::{File as AsyncFile, self},
-	io::AsyncWriteExt as _,
+	io::{AsyncBufRead, AsyncBufReadExt as _, AsyncReadExt as _, AsyncWriteExt as _, BufReader},
 	select,
 	spawn,
-	sync::broadcast::{Receiver as Listener, Sender as Broadcaster, self},
-	time::{Duration, interval},
+	sync::{
+		broadcast::{Receiver as Listener, Sender as Broadcaster, self},
+		watch::{Receiver as Watcher, Sender as WatchSender},
+	},
+	time::{Duration, Instant, sleep, timeout},
 };
 use tracing::{debug, error, info, warn};
 
 #[cfg(not(test))]
 use ::{
-	reqwest::{Client, Response},
+	command_fds::CommandFdExt as _,
+	reqwest::Client,
 	std::{
 		env::current_exe,
 		os::unix::process::CommandExt as _,
@@ -93,15 +131,227 @@ use ::{
 #[cfg(test)]
 use crate::mocks::std_env::mock_current_exe as current_exe;
 #[cfg(test)]
+use crate::mocks::std_process::{FakeCommand as Command, mock_exit as exit};
+#[cfg(test)]
 use sham::{
-	reqwest::{MockClient as Client, MockResponse as Response},
-	std_process::{FakeCommand as Command, MockStdio as Stdio, mock_exit as exit}
+	reqwest::MockClient as Client,
+	std_process::MockStdio as Stdio,
 };
 
 
 
+//		Constants																										
+
+/// How often to look for new update bundles in the drop directory.
+const BUNDLE_SCAN_INTERVAL: Duration = Duration::from_secs(10);
+
+/// The maximum size of a single Server-Sent Event, in bytes. A connection that
+/// sends a larger event is dropped, so that the buffer cannot grow unbounded.
+const MAX_EVENT_SIZE: usize = 64 * 1_024;
+
+/// The default maximum number of redirects to follow for each request.
+const MAX_REDIRECTS: usize = 10;
+
+
+
 //		Enums																											
 
+//		CheckOutcome															
+/// The possible outcomes of an update check.
+/// 
+/// This is returned by [`Updater::check_for_updates()`], to describe what
+/// happened during the check.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum CheckOutcome {
+	/// The current version of the application is the latest available. The
+	/// version reported by the server is included.
+	UpToDate(Version),
+	
+	/// A newer version of the application is available, but it has not been
+	/// downloaded.
+	UpdateAvailable(Version),
+	
+	/// A newer version of the application has been downloaded and verified,
+	/// but it has not been installed.
+	Downloaded(Version),
+	
+	/// A newer version of the application has been downloaded and installed,
+	/// and the application is waiting to be restarted in order to apply it.
+	/// This may be because critical actions are in progress, or because the
+	/// update policy leaves restarting to the application.
+	PendingRestart(Version),
+	
+	/// A newer version of the application is available, but the configured
+	/// consent has deferred the next step. The updater will ask again once the
+	/// included duration has passed.
+	Deferred(Version, Duration),
+	
+	/// A newer version of the application is available, but the next step is
+	/// held back until a maintenance window opens. The updater will resume at
+	/// the included time.
+	AwaitingWindow(Version, SystemTime),
+	
+	/// A newer version of the application is available, but the configured
+	/// consent has declined it. It will not be offered again.
+	Declined(Version),
+	
+	/// The check could not be completed due to an error.
+	Failed(UpdaterError),
+}
+
+//		Display																	
+impl Display for CheckOutcome {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::UpToDate(ref version)          => write!(f, "Up to date: {version}"),
+			Self::UpdateAvailable(ref version)   => write!(f, "Update available: {version}"),
+			Self::Downloaded(ref version)        => write!(f, "Downloaded: {version}"),
+			Self::PendingRestart(ref version)    => write!(f, "Pending restart: {version}"),
+			Self::Deferred(ref version, delay)   => write!(f, "Deferred: {version} (for {}s)", delay.as_secs()),
+			Self::AwaitingWindow(ref version, _) => write!(f, "Awaiting maintenance window: {version}"),
+			Self::Declined(ref version)          => write!(f, "Declined: {version}"),
+			Self::Failed(ref err)                => write!(f, "Failed: {err}"),
+		}
+	}
+}
+
+//		DrainAction																
+/// What to do when critical actions have not finished by the drain deadline.
+/// 
+/// See [`DrainPolicy`] for details.
+/// 
+#[derive(Clone, Default)]
+#[non_exhaustive]
+pub enum DrainAction {
+	/// Restart the application anyway, interrupting the critical actions that
+	/// are still in progress.
+	#[default]
+	ForceRestart,
+	
+	/// Cancel the upgrade, and put the previous version of the executable back
+	/// in place. The status will be set to [`Status::RollingBack`] while this
+	/// is done, and then to [`Status::Failed`], and the update will be tried
+	/// again at the next check after the failure cool-down.
+	Rollback,
+	
+	/// Call the specified function, and keep waiting for the critical actions
+	/// to finish. The function is given the version waiting to be applied, and
+	/// the tracked critical actions still in progress, so that the situation
+	/// can be escalated, for instance by alerting an operator.
+	#[expect(clippy::type_complexity, reason = "Clearer than an alias here")]
+	Escalate(Arc<dyn Fn(&Version, &[CriticalAction]) + Send + Sync>),
+}
+
+//		Debug																	
+impl Debug for DrainAction {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::ForceRestart => write!(f, "ForceRestart"),
+			Self::Rollback     => write!(f, "Rollback"),
+			Self::Escalate(_)  => f.debug_tuple("Escalate").finish_non_exhaustive(),
+		}
+	}
+}
+
+//		ProgressPolicy															
+/// How often to report progress.
+/// 
+/// Progress is reported by changing the status, for example to
+/// [`Status::Downloading`], which is broadcast to all subscribers. Reporting
+/// every small change would flood subscribers and logs, so this policy decides
+/// when a change is worth reporting. The first and last progress reports for a
+/// step are always sent.
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ProgressPolicy {
+	/// Report progress whenever the percentage completed changes. If the total
+	/// is not known, progress is not reported until the step is complete.
+	#[default]
+	OnPercentChange,
+	
+	/// Report progress when at least the specified amount of time has passed
+	/// since the last report.
+	Interval(Duration),
+	
+	/// Report progress when at least the specified number of bytes have been
+	/// processed since the last report.
+	Bytes(usize),
+}
+
+//󰭅		ProgressPolicy															
+impl ProgressPolicy {
+	//		is_due																
+	/// Checks whether progress should be reported.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `last`    - The progress that was last reported.
+	/// * `elapsed` - The time that has passed since the last report.
+	/// * `next`    - The progress that could now be reported.
+	/// 
+	fn is_due(self, last: &Progress, elapsed: Duration, next: &Progress) -> bool {
+		if next.total > 0 && next.done >= next.total {
+			return true;
+		}
+		match self {
+			Self::OnPercentChange => next.percent() != last.percent(),
+			Self::Interval(every) => elapsed >= every,
+			Self::Bytes(every)    => next.done.saturating_sub(last.done) >= every,
+		}
+	}
+}
+
+//		RestartStrategy															
+/// How the application should be restarted to apply an update.
+/// 
+/// By default, the new version replaces the running process in place, using
+/// the same arguments. When the application is run by a supervisor, such as
+/// systemd or Kubernetes, it is usually better to exit cleanly and let the
+/// supervisor start the new version instead.
+/// 
+#[derive(Clone, Default)]
+#[expect(variant_size_differences, reason = "Doesn't matter here")]
+#[non_exhaustive]
+pub enum RestartStrategy {
+	/// Replace the running process with the new version, keeping the same
+	/// process ID and arguments. If this fails, the application exits with a
+	/// code of `1`.
+	#[default]
+	Exec,
+	
+	/// Start the new version as a separate process, with the same arguments,
+	/// and then exit with a code of `0`. If the new version cannot be started,
+	/// the application exits with a code of `1`.
+	SpawnAndExit,
+	
+	/// Exit with the specified code, leaving it to the supervisor to start the
+	/// new version.
+	Exit(i32),
+	
+	/// Call the specified function, which is responsible for restarting the
+	/// application. It is given the path to the executable, which is now the
+	/// new version. The updater does nothing further after it returns.
+	Callback(Arc<dyn Fn(&Path) + Send + Sync>),
+}
+
+//		Debug																	
+impl Debug for RestartStrategy {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Exec         => write!(f, "Exec"),
+			Self::SpawnAndExit => write!(f, "SpawnAndExit"),
+			Self::Exit(code)   => f.debug_tuple("Exit").field(&code).finish(),
+			Self::Callback(_)  => f.debug_tuple("Callback").finish_non_exhaustive(),
+		}
+	}
+}
+
 //		Status																	
 /// The possible statuses that an [`Updater`] can have.
 #[derive(Clone, Debug, Eq, Hash, PartialEq)]
@@ -115,23 +365,65 @@ pub enum Status {
 	/// the application available.
 	Checking,
 	
+	/// A newer version of the application is available, but the update policy
+	/// means that it will not be downloaded until [`Updater::download()`] is
+	/// called.
+	UpdateAvailable(Version),
+	
 	/// A newer version of the application is available, and the updater is
-	/// currently downloading the release file.
-	Downloading(Version, u8),
+	/// currently downloading the release file. The progress of the download is
+	/// included.
+	Downloading(Version, Progress),
+	
+	/// A newer version of the application has been downloaded, and the updater
+	/// is currently verifying it against the hash provided by the server.
+	Verifying(Version),
+	
+	/// A newer version of the application has been downloaded and verified,
+	/// and is ready to be installed.
+	Downloaded(Version),
 	
 	/// A newer version of the application is available, and the updater is
-	/// currently installing it.
-	Installing(Version),
+	/// currently installing it. The progress of the installation is included.
+	Installing(Version, Progress),
+	
+	/// A newer version of the application has been installed, but the update
+	/// policy means that the application will not be restarted until
+	/// [`Updater::restart()`] is called.
+	Installed(Version),
+	
+	/// A newer version of the application is available, but the step included
+	/// is held back until a maintenance window opens, according to the
+	/// [`MaintenanceSchedule`]. The time at which the next window opens is
+	/// included, and the update process will resume by itself at that point.
+	/// The status before the step is held back is either
+	/// [`Status::Downloaded`] or [`Status::Installed`].
+	AwaitingWindow(Version, ConsentStep, SystemTime),
 	
 	/// A newer version of the application is available, and the updater is
 	/// currently waiting to start the upgrade process, but is blocked from
-	/// doing so due to one or more critical actions being in progress.
-	PendingRestart(Version),
+	/// doing so due to one or more critical actions being in progress. The
+	/// number of critical actions still in progress is included, and is updated
+	/// each time one is deregistered. If a drain deadline has been configured
+	/// using the [`DrainPolicy`], the time remaining until it is reached is
+	/// also included, and is updated periodically as it approaches.
+	PendingRestart(Version, usize, Option<Duration>),
 	
 	/// A newer version of the application is available, and the updater is
 	/// currently in the process of restarting the application to apply the
 	/// upgrade. No new critical actions are allowed to start.
 	Restarting(Version),
+	
+	/// The drain deadline passed while a restart was pending, and the updater
+	/// is currently restoring the previous version of the application, as set
+	/// by [`DrainAction::Rollback`]. The status will be set to
+	/// [`Status::Failed`] once this has finished.
+	RollingBack(Version),
+	
+	/// The update to a newer version of the application failed. The error that
+	/// occurred is included. The status will return to [`Status::Idle`] once the
+	/// configured cool-down period has passed.
+	Failed(Version, Box<UpdaterError>),
 }
 
 //󰭅		Display																	
@@ -141,19 +433,122 @@ impl Display for Status {
 		write!(f, "{}", match *self {
 			Self::Idle                                  => s!(     "Idle"),
 			Self::Checking                              => s!(     "Checking"),
-			Self::Installing(ref version)               => format!("Installing: {version}"),
-			Self::Downloading(ref version, ref percent) => format!("Downloading: {version} ({percent}%)"),
-			Self::PendingRestart(ref version)           => format!("Pending restart: {version}"),
+			Self::UpdateAvailable(ref version)          => format!("Update available: {version}"),
+			Self::Downloading(ref version, progress)    => format!("Downloading: {version} ({progress})"),
+			Self::Verifying(ref version)                => format!("Verifying: {version}"),
+			Self::Downloaded(ref version)               => format!("Downloaded: {version}"),
+			Self::Installing(ref version, progress)     => format!("Installing: {version} ({progress})"),
+			Self::Installed(ref version)                => format!("Installed: {version}"),
+			Self::AwaitingWindow(ref version, step, _)  => format!("Awaiting maintenance window: {version} ({step})"),
+			Self::PendingRestart(ref version, actions, None)            => format!("Pending restart: {version} ({actions} critical actions in progress)"),
+			Self::PendingRestart(ref version, actions, Some(remaining)) => format!("Pending restart: {version} ({actions} critical actions in progress, {}s until deadline)", remaining.as_secs()),
 			Self::Restarting(ref version)               => format!("Restarting: {version}"),
+			Self::RollingBack(ref version)              => format!("Rolling back: {version}"),
+			Self::Failed(ref version, ref err)          => format!("Failed: {version}: {err}"),
 		})
 	}
 }
 
+//		TokenSource																
+/// Where to obtain the API token to send to the API server.
+/// 
+/// The token is obtained again for each request, so that a token held in an
+/// environment variable or a file can be changed without restarting the
+/// application. It is sent as a bearer token in the `Authorization` header.
+/// 
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum TokenSource {
+	/// Use the specified token.
+	Static(String),
+	
+	/// Read the token from the specified environment variable.
+	Env(String),
+	
+	/// Read the token from the specified file. Any whitespace around the token
+	/// is ignored.
+	File(PathBuf),
+}
+
+//󰭅		TokenSource																
+impl TokenSource {
+	//		Public methods														
+	
+	//		token																
+	/// Obtains the API token.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidToken`]
+	/// 
+	pub async fn token(&self) -> Result<String, UpdaterError> {
+		let token = match *self {
+			Self::Static(ref token) => token.clone(),
+			Self::Env(ref name)     => var(name).map_err(|err|
+				UpdaterError::InvalidToken(format!("Environment variable {name}: {err}"))
+			)?,
+			Self::File(ref path)    => fs::read_to_string(path).await.map_err(|err|
+				UpdaterError::InvalidToken(format!("File {}: {err}", path.display()))
+			)?.trim().to_owned(),
+		};
+		if token.is_empty() {
+			return Err(UpdaterError::InvalidToken(s!("The token is empty")));
+		}
+		Ok(token)
+	}
+}
+
+//		Debug																	
+impl Debug for TokenSource {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Static(_)      => f.debug_tuple("Static").finish_non_exhaustive(),
+			Self::Env(ref name)  => f.debug_tuple("Env").field(name).finish(),
+			Self::File(ref path) => f.debug_tuple("File").field(path).finish(),
+		}
+	}
+}
+
+//		UpdatePolicy															
+/// The policy to follow when a newer version of the application is found.
+/// 
+/// This determines how far the updater will proceed by itself. Whenever it
+/// stops short of restarting, the application can advance to the next step
+/// when it is ready, using the [`Updater::download()`],
+/// [`Updater::install()`], and [`Updater::restart()`] methods.
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum UpdatePolicy {
+	/// Report that an update is available, but do not download it. The status
+	/// will be set to [`Status::UpdateAvailable`].
+	NotifyOnly,
+	
+	/// Download and verify the update, but do not install it. The status will
+	/// be set to [`Status::Downloaded`].
+	DownloadOnly,
+	
+	/// Download, verify, and install the update, but do not restart the
+	/// application. The status will be set to [`Status::Installed`].
+	InstallWithoutRestart,
+	
+	/// Download, verify, and install the update, and then restart the
+	/// application as soon as no critical actions are in progress.
+	#[default]
+	Automatic,
+}
+
 //		UpdaterError															
 /// Errors that can occur when trying to update.
-#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ThisError)]
 #[non_exhaustive]
 pub enum UpdaterError {
+	/// The critical actions in progress did not finish before the drain
+	/// deadline, and so the upgrade was cancelled.
+	#[error("Timed out waiting for {1} critical actions to finish before restarting to apply version {0}")]
+	DrainTimedOut(Version, usize),
+	
 	/// Verification of the SHA256 hash of the downloaded file against the
 	/// server's hash data failed.
 	#[error("Failed hash verification for downloaded version {0}")]
@@ -164,6 +559,11 @@ pub enum UpdaterError {
 	#[error("Failed signature verification for response from {0}")]
 	FailedSignatureVerification(Url),
 	
+	/// The API server responded with a `403 Forbidden` status, meaning that
+	/// the API token is not entitled to what was requested.
+	#[error("The API token is not entitled to {0}")]
+	Forbidden(Url),
+	
 	/// An HTTP error occurred, i.e. the status code returned is not `200`. No
 	/// other codes are expected, as this library only performs `GET` requests.
 	#[error("HTTP status code {1} received when calling {0}")]
@@ -179,6 +579,22 @@ pub enum UpdaterError {
 	#[error("Invalid HTTP body received from {0}")]
 	InvalidBody(Url),
 	
+	/// The update bundle is not in the expected format. See the
+	/// [`bundle`](crate::bundle) module for details.
+	#[error("The update bundle {0:?} is not in the expected format")]
+	InvalidBundle(PathBuf),
+	
+	/// The [`HttpSettings`] could not be used to set up the HTTP client, for
+	/// instance due to an invalid header or certificate.
+	#[error("Invalid HTTP settings: {0}")]
+	InvalidHttpSettings(String),
+	
+	/// The name given for a listening socket is not valid. Names must not be
+	/// empty, must be no longer than 255 characters, and must not contain
+	/// colons or control characters.
+	#[error(r#"Invalid listener name "{0}""#)]
+	InvalidListenerName(String),
+	
 	/// The response from the API server could not be parsed. This could be due
 	/// to invalid JSON, or the JSON not matching the expected structure.
 	#[error("Invalid payload received from {0}")]
@@ -188,6 +604,17 @@ pub enum UpdaterError {
 	#[error(r#"Invalid signature header "{1}" received from {0}"#)]
 	InvalidSignature(Url, String),
 	
+	/// The requested step of the update process cannot be performed while the
+	/// updater has its current status. The status at the time of the request
+	/// is included.
+	#[error("Unable to proceed with the update while the status is: {0}")]
+	InvalidStatus(Status),
+	
+	/// The API token could not be obtained from the [`TokenSource`], or is not
+	/// valid for use in an HTTP header.
+	#[error("Unable to use the API token: {0}")]
+	InvalidToken(String),
+	
 	/// The URL specified to use to make an HTTP request is invalid. The API URL
 	/// should be okay due to type validation, so something must have happened
 	/// when adding a particular endpoint to it, as the outcome is invalid.
@@ -198,6 +625,11 @@ pub enum UpdaterError {
 	#[error("HTTP response body from {0} is shorter than expected: {1} < {2}")]
 	MissingData(Url, usize, usize),
 	
+	/// The downloaded release file for the update is no longer available, and
+	/// so it cannot be installed. The update will need to be downloaded again.
+	#[error("The downloaded file for version {0} is not available")]
+	MissingDownload(Version),
+	
 	/// The HTTP response from the API server does not contain a signature
 	/// header.
 	#[error("HTTP response from {0} does not contain a signature header")]
@@ -230,11 +662,30 @@ pub enum UpdaterError {
 	#[error("Unable to obtain current executable path: {0}")]
 	UnableToObtainCurrentExePath(String),
 	
+	/// A problem was encountered when trying to read an update bundle.
+	#[error("Unable to read update bundle {0:?}: {1}")]
+	UnableToReadBundle(PathBuf, String),
+	
+	/// A problem was encountered when trying to read a file from an update
+	/// source.
+	#[error("Unable to read file {0:?}: {1}")]
+	UnableToReadFile(PathBuf, String),
+	
+	/// A problem was encountered when trying to keep a listening socket open
+	/// for passing on to the new version.
+	#[error(r#"Unable to register listener "{0}": {1}"#)]
+	UnableToRegisterListener(String, String),
+	
 	/// A problem was encountered when trying to rename the current running
 	/// application.
 	#[error("Unable to rename the current executable {0:?}: {1}")]
 	UnableToRenameCurrentExe(PathBuf, String),
 	
+	/// A problem was encountered when trying to put the previous version of the
+	/// application back in place.
+	#[error("Unable to restore the previous executable {0:?}: {1}")]
+	UnableToRestoreBackup(PathBuf, String),
+	
 	/// A problem was encountered when trying to set the new executable's file
 	/// permissions.
 	#[error(r#"Unable to set file permissions for the new executable "{0:?}": {1}"#)]
@@ -244,15 +695,142 @@ pub enum UpdaterError {
 	#[error(r#"Unable to write to download file "{0:?}": {1}"#)]
 	UnableToWriteToDownload(PathBuf, String),
 	
+	/// The API server responded with a `401 Unauthorized` status, meaning that
+	/// an API token is required, and the one given, if any, is not known.
+	#[error("A valid API token is required to call {0}")]
+	Unauthorized(Url),
+	
 	/// The content type of the response is not as expected.
 	#[error(r#"HTTP response from {0} had unexpected content type: "{1}", expected: "{2}""#)]
 	UnexpectedContentType(Url, String, String),
+	
+	/// An update check was requested while the updater was already busy. The
+	/// status at the time of the request is included.
+	#[error("An update is already underway: {0}")]
+	UpdateAlreadyUnderway(Status),
+}
+
+//󰭅		UpdaterError															
+impl UpdaterError {
+	//		is_retryable														
+	/// Whether the error is likely to be temporary.
+	/// 
+	/// Errors that are caused by network problems, server-side failures, or
+	/// local resources being temporarily unavailable are considered retryable,
+	/// as trying again later may well succeed. Errors that indicate a problem
+	/// with the data received from the server, such as a failed signature or
+	/// hash verification, or with the application's own setup, are considered
+	/// permanent, as trying again would give the same result — or worse, in the
+	/// case of tampered data, would give an attacker another attempt.
+	/// 
+	#[must_use]
+	pub fn is_retryable(&self) -> bool {
+		match *self {
+			Self::HttpError(_, status)             => {
+				status.is_server_error()
+				|| status == StatusCode::REQUEST_TIMEOUT
+				|| status == StatusCode::TOO_MANY_REQUESTS
+			},
+			Self::HttpRequestFailed(_, _)          |
+			Self::InvalidBody(_)                   |
+			Self::MissingData(_, _, _)             |
+			Self::UnableToCreateDownload(_, _)     |
+			Self::UnableToCreateTempDir(_)         |
+			Self::UnableToWriteToDownload(_, _)    => true,
+			Self::DrainTimedOut(_, _)              |
+			Self::FailedHashVerification(_)        |
+			Self::FailedSignatureVerification(_)   |
+			Self::Forbidden(_)                     |
+			Self::InvalidBundle(_)                 |
+			Self::InvalidHttpSettings(_)           |
+			Self::InvalidListenerName(_)           |
+			Self::InvalidPayload(_)                |
+			Self::InvalidSignature(_, _)           |
+			Self::InvalidStatus(_)                 |
+			Self::InvalidToken(_)                  |
+			Self::InvalidUrl(_, _)                 |
+			Self::MissingDownload(_)               |
+			Self::MissingSignature(_)              |
+			Self::TooMuchData(_, _, _)             |
+			Self::UnableToGetFileMetadata(_, _)    |
+			Self::UnableToMoveNewExe(_, _)         |
+			Self::UnableToObtainCurrentExePath(_)  |
+			Self::UnableToReadBundle(_, _)         |
+			Self::UnableToReadFile(_, _)           |
+			Self::UnableToRegisterListener(_, _)   |
+			Self::UnableToRenameCurrentExe(_, _)   |
+			Self::UnableToRestoreBackup(_, _)      |
+			Self::UnableToSetFilePermissions(_, _) |
+			Self::Unauthorized(_)                  |
+			Self::UnexpectedContentType(_, _, _)   |
+			Self::UpdateAlreadyUnderway(_)         => false,
+		}
+	}
+	
+	//		warrants_failover													
+	/// Whether the error means that a mirror should be tried.
+	/// 
+	/// This is the case when the API server could not be reached, or
+	/// responded with a server error.
+	/// 
+	fn warrants_failover(&self) -> bool {
+		matches!(*self, Self::HttpRequestFailed(_, _))
+		|| matches!(*self, Self::HttpError(_, status) if status.is_server_error())
+	}
 }
 
 
 
 //		Structs																											
 
+//		ActionGuard																
+/// A guard for a critical action.
+/// 
+/// This is obtained from [`Updater::guard_action()`], and keeps a critical
+/// action registered for as long as it exists. When it is dropped, the action
+/// is deregistered automatically, which means that an early return or a panic
+/// cannot leave the action registered and prevent the restart from happening.
+/// 
+#[derive(Debug)]
+#[must_use = "The action is deregistered as soon as the guard is dropped"]
+pub struct ActionGuard {
+	//		Private properties													
+	/// The identifier of the action, used to find it in the list of tracked
+	/// actions.
+	id:      u64,
+	
+	/// The updater that the action is registered with. A weak reference is used
+	/// so that the guard does not keep the updater alive.
+	updater: Weak<Updater>,
+}
+
+//󰭅		ActionGuard																
+impl ActionGuard {
+	//		Public methods														
+	
+	//		id																	
+	/// Gets the identifier of the action.
+	/// 
+	/// This matches the [`CriticalAction::id`] of the action, as listed by
+	/// [`Updater::actions()`].
+	/// 
+	#[must_use]
+	pub const fn id(&self) -> u64 {
+		self.id
+	}
+}
+
+//		Drop																	
+impl Drop for ActionGuard {
+	//		drop																
+	fn drop(&mut self) {
+		if let Some(updater) = self.updater.upgrade() {
+			drop(updater.tracked.lock().remove(&self.id));
+			let _count = updater.deregister_action();
+		}
+	}
+}
+
 //		Config																	
 /// The configuration options for the client.
 #[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
@@ -269,6 +847,35 @@ pub struct Config {
 	/// path (e.g. `/api`). For example, `https://api.example.com/api/v2`.
 	pub api:              Url,
 	
+	/// Additional URLs of the API to fall back to when the primary
+	/// [`api`](Self::api) URL cannot be reached, in order of preference. They
+	/// should provide the same endpoints, but as all responses are verified
+	/// using the [`key`](Self::key), they do not need to be trusted. The one
+	/// that works is used for subsequent requests, and the primary is tried
+	/// again according to the [`retry_policy`](Self::retry_policy). This can be
+	/// left empty.
+	pub mirrors:          Vec<Url>,
+	
+	/// An alternative source to obtain updates from, instead of the API server
+	/// given by the [`api`](Self::api) and [`mirrors`](Self::mirrors) settings,
+	/// which are then ignored. This is optional. See the [`source`] module for
+	/// details.
+	pub source:           Option<Arc<dyn UpdateSource>>,
+	
+	/// The settings for the HTTP client used to communicate with the API
+	/// server, such as timeouts, proxies, and extra headers. These are not used
+	/// when a [`source`](Self::source) is given, so a source with a
+	/// preconfigured client can be supplied instead, using
+	/// [`HttpSource::with_client()`].
+	pub http:             HttpSettings,
+	
+	/// Where to obtain the API token to send to the API server, when it
+	/// requires authentication. It is sent as a bearer token, and only to the
+	/// API server and its mirrors. This is optional, and is not used when a
+	/// [`source`](Self::source) is given, in which case the token can be set
+	/// using [`HttpSource::with_token()`].
+	pub token:            Option<TokenSource>,
+	
 	/// The public key for the server. This is used to verify the HTTP responses
 	/// from the server, to ensure that they have not been tampered with. The
 	/// format used is Ed25519, which is a modern and secure algorithm.
@@ -277,94 +884,664 @@ pub struct Config {
 	/// Whether to check for updates on startup.
 	pub check_on_startup: bool,
 	
-	/// How often to check for updates. This is optional.
+	/// How often to check for updates. This is optional, and is ignored if a
+	/// [`check_schedule`](Self::check_schedule) is specified.
 	pub check_interval:   Option<Duration>,
+	
+	/// When to check for updates, as an alternative to checking at a fixed
+	/// interval. This is optional. See the [`schedule`] module for details.
+	pub check_schedule:   Option<CheckSchedule>,
+	
+	/// The maximum amount of random delay to add before each check, including
+	/// the check on startup. This spreads the load on the server when many
+	/// instances of the application start or check at the same time.
+	pub check_jitter:     Duration,
+	
+	/// Whether to listen for new releases being announced by the server, and
+	/// check for updates as soon as they are. Each announcement must be signed
+	/// using the [`key`](Self::key). Scheduled checks are skipped while the
+	/// updater is listening, and resume if the connection drops, until it can
+	/// be reestablished according to the [`retry_policy`](Self::retry_policy).
+	pub push_updates:     bool,
+	
+	/// A directory to watch for update bundles. Any file with a `.bundle`
+	/// extension that appears in it is installed, if it contains a newer
+	/// version. Files should be copied into the directory under a different
+	/// name and then renamed, so that they are not read before they are
+	/// complete. This is optional. See the [`bundle`](crate::bundle) module
+	/// for details.
+	pub bundle_dir:       Option<PathBuf>,
+	
+	/// How far the updater should proceed by itself when a newer version is
+	/// found. The default is to update fully automatically.
+	pub update_policy:    UpdatePolicy,
+	
+	/// When the updater is allowed to install updates and restart the
+	/// application by itself. This is optional, and if not specified, it can
+	/// do so at any time.
+	pub maintenance:      Option<MaintenanceSchedule>,
+	
+	/// How to retry requests to the API server that fail for reasons that are
+	/// likely to be temporary, and how often to check for updates after a
+	/// check has failed.
+	pub retry_policy:     RetryPolicy,
+	
+	/// How long to wait after an update has failed before returning to
+	/// [`Status::Idle`], so that the next check can go ahead.
+	pub failure_cooldown: Duration,
+	
+	/// How long to wait for the shutdown hook registered using
+	/// [`Updater::on_shutdown()`] to finish before restarting. If the hook has
+	/// not finished by then, the restart will go ahead anyway.
+	pub shutdown_timeout: Duration,
+	
+	/// How long to wait for critical actions to finish before restarting, and
+	/// what to do if they have not finished by then.
+	pub drain_policy:     DrainPolicy,
+	
+	/// How often to report progress while downloading and installing.
+	pub progress_policy:  ProgressPolicy,
+	
+	/// How to restart the application once an update has been installed.
+	pub restart_strategy: RestartStrategy,
+	
+	/// Whether to report to systemd, via the notification socket, that the
+	/// application is reloading or stopping when it restarts. This has no
+	/// effect if the application is not run by systemd. See the [`systemd`]
+	/// module for details.
+	pub systemd_notify:   bool,
+	
+	/// A means of asking for consent before each step of the update process
+	/// that the updater carries out by itself. This is optional, and if not
+	/// specified, no consent will be sought.
+	pub consent:          Option<Arc<dyn Consent>>,
 }
 
-//		Updater																	
-/// A service to check for updates at intervals, and upgrade the application.
+//		CriticalAction															
+/// Information about a critical action that is in progress.
 /// 
-/// This struct provides a service that will query the API server at defined
-/// intervals, to check for updates. If an update is found, it will be
-/// downloaded and installed, and the application will be restarted.
+/// This is provided by [`Updater::actions()`], for actions registered using
+/// [`Updater::guard_action()`], so that it is possible to see what is holding
+/// up a pending restart.
 /// 
-#[derive(Debug)]
-pub struct Updater {
-	//		Private properties													
-	/// A counter of critical actions that are currently active. This is used to
-	/// prevent the updater from stopping the application while a critical
-	/// action is in progress.
-	actions:     AtomicUsize,
-	
-	/// The status broadcast channel that status changes are added to. This is
-	/// the sender side only. Each interested party can subscribe to this
-	/// channel to receive status changes on a real-time basis.
-	broadcast:   Broadcaster<Status>,
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct CriticalAction {
+	//		Public properties													
+	/// The identifier of the action. This is unique for the lifetime of the
+	/// updater.
+	pub id:      u64,
 	
-	/// The configuration for the updater service.
-	config:      Config,
+	/// The name of the action, if one was given.
+	pub name:    Option<String>,
 	
-	/// The path to the current running executable. This is used to replace the
-	/// executable with the new version when upgrading. It is checked at startup
-	/// and stored here as a reliable reference so that the updater can use it
-	/// later on.
-	exe_path:    PathBuf,
+	/// When the action was started.
+	pub started: Instant,
+}
+
+//󰭅		CriticalAction															
+impl CriticalAction {
+	//		Public methods														
 	
-	/// The HTTP client instance that is used for communicating with the API
-	/// server.
-	http_client: Client,
+	//		elapsed																
+	/// Gets how long the action has been in progress.
+	#[must_use]
+	pub fn elapsed(&self) -> Duration {
+		self.started.elapsed()
+	}
+}
+
+//		Display																	
+impl Display for CriticalAction {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{} (#{}, running for {}s)", self.name.as_deref().unwrap_or("Unnamed action"), self.id, self.elapsed().as_secs())
+	}
+}
+
+//		DrainPolicy																
+/// The policy for waiting for critical actions to finish before restarting.
+/// 
+/// By default, the updater will wait indefinitely for critical actions to
+/// finish before restarting. If a timeout is set, then once it has passed, the
+/// configured [`DrainAction`] is taken. While waiting, the status is updated at
+/// the specified interval, so that subscribers can see the deadline
+/// approaching.
+/// 
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct DrainPolicy {
+	//		Public properties													
+	/// The maximum time to wait for critical actions to finish. If this is
+	/// [`None`], the updater will wait indefinitely.
+	pub timeout:         Option<Duration>,
 	
-	/// The updater queue that is used for communicating with the interval
-	/// timer. This is the sender side only. A queue is used so that the timer
-	/// can run in a separate thread, but be stopped when required.
-	queue:       Sender<()>,
+	/// What to do when the timeout has passed.
+	pub action:          DrainAction,
 	
-	/// The current status of the updater.
-	status:      RwLock<Status>,
+	/// How often to report the time remaining until the deadline.
+	pub report_interval: Duration,
 }
 
-//󰭅		Updater																	
-impl Updater {
+//󰭅		DrainPolicy																
+impl DrainPolicy {
 	//		Constructors														
 	
 	//		new																	
-	/// Creates a new updater service instance.
+	/// Creates a new drain policy.
 	/// 
-	/// This function creates a new updater service instance, with the specified
-	/// configuration. As soon as the service is created, it will start checking
-	/// for updates.
+	/// # Parameters
 	/// 
-	/// In order to shut down nicely, [`Drop`] is implemented, and will send a
-	/// message to the internal queue to stop the timer.
+	/// * `timeout` - The maximum time to wait for critical actions to finish.
+	/// * `action`  - What to do when the timeout has passed.
 	/// 
-	/// # Parameters
+	#[must_use]
+	pub fn new(timeout: Duration, action: DrainAction) -> Self {
+		Self {
+			timeout: Some(timeout),
+			action,
+			..Self::default()
+		}
+	}
+}
+
+//		Default																	
+impl Default for DrainPolicy {
+	//		default																
+	fn default() -> Self {
+		Self {
+			timeout:         None,
+			action:          DrainAction::default(),
+			report_interval: Duration::from_secs(10),
+		}
+	}
+}
+
+//		HttpSettings															
+/// The settings for the HTTP client used to communicate with the API server.
+/// 
+/// By default, the client is set up in the same way as [`reqwest::Client::new()`],
+/// which includes using any proxy given by the usual environment variables,
+/// such as `HTTPS_PROXY`. Each setting here is optional, and only changes that
+/// one aspect of the client.
+/// 
+/// Redirects are followed, so that the API server can send the client to
+/// somewhere else for the release files, such as a CDN. They are followed
+/// safely: there is a limit on how many are followed, redirects from HTTPS to
+/// plain HTTP are refused, and the `Authorization` header, which holds any API
+/// token, is dropped if the redirect goes to a different host. The release
+/// files are checked in the same way wherever they come from.
+/// 
+#[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct HttpSettings {
+	//		Public properties													
+	/// The maximum time to wait for a connection to the server to be
+	/// established. If this is [`None`], there is no limit.
+	pub connect_timeout:   Option<Duration>,
+	
+	/// The maximum time to wait for each read from the server, which applies
+	/// to the response headers and to each part of the response body, rather
+	/// than to the whole response, so that large downloads are not cut off. If
+	/// this is [`None`], there is no limit.
+	pub read_timeout:      Option<Duration>,
+	
+	/// The URL of a proxy server to send all requests through, such as
+	/// `http://proxy.example.com:3128`. Credentials can be included in the URL.
+	/// This overrides any proxy given by environment variables.
+	pub proxy:             Option<Url>,
+	
+	/// The hosts that should not be sent through the [`proxy`](Self::proxy),
+	/// as a comma-separated list in the same format as the `NO_PROXY`
+	/// environment variable, such as `localhost,.internal.example.com`.
+	pub no_proxy:          Option<String>,
+	
+	/// Extra root certificates to trust, in addition to the system ones, such
+	/// as that of an internal certificate authority. Each one should be in PEM
+	/// format.
+	pub root_certificates: Vec<Vec<u8>>,
+	
+	/// The `User-Agent` header to send with each request. If this is [`None`],
+	/// no `User-Agent` header is sent, unless one is given in the
+	/// [`headers`](Self::headers).
+	pub user_agent:        Option<String>,
+	
+	/// Extra headers to send with each request, as pairs of names and values.
+	/// If a name appears more than once, the last value is used.
+	pub headers:           Vec<(String, String)>,
+	
+	/// The maximum number of redirects to follow for each request. If this is
+	/// [`None`], up to 10 are followed. Setting it to `0` stops redirects from
+	/// being followed at all.
+	pub max_redirects:     Option<usize>,
+}
+
+//󰭅		HttpSettings															
+impl HttpSettings {
+	//		Public methods														
+	
+	//		client_builder														
+	/// Creates an HTTP client builder that uses these settings.
 	/// 
-	/// * `config` - The configuration for the updater service.
+	/// This can be used to make further changes to the client before building
+	/// it, and passing it to [`HttpSource::with_client()`].
 	/// 
 	/// # Errors
 	/// 
-	/// * [`UpdaterError::UnableToObtainCurrentExePath`]
+	/// * [`UpdaterError::InvalidHttpSettings`]
 	/// 
 	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
-	pub fn new(config: Config) -> Result<Arc<Self>, UpdaterError> {
-		//		Set up updater instance											
-		let http_client        = Client::new();
-		let (sender, receiver) = flume::unbounded();
-		let (tx, mut rx)       = broadcast::channel(1);
-		let updater            = Arc::new(Self {
+	pub fn client_builder(&self) -> Result<ClientBuilder, UpdaterError> {
+		let invalid     = UpdaterError::InvalidHttpSettings;
+		let mut builder = ClientBuilder::new();
+		if let Some(timeout) = self.connect_timeout {
+			builder = builder.connect_timeout(timeout);
+		}
+		if let Some(timeout) = self.read_timeout {
+			builder = builder.read_timeout(timeout);
+		}
+		if let Some(ref url) = self.proxy {
+			let proxy = Proxy::all(url.clone()).map_err(|err| invalid(format!("Proxy {url}: {err}")))?;
+			builder   = builder.proxy(proxy.no_proxy(self.no_proxy.as_deref().and_then(NoProxy::from_string)));
+		}
+		for pem in &self.root_certificates {
+			let certificate = Certificate::from_pem(pem).map_err(|err| invalid(format!("Root certificate: {err}")))?;
+			builder         = builder.add_root_certificate(certificate);
+		}
+		let mut headers = HeaderMap::new();
+		#[expect(clippy::pattern_type_mismatch, reason = "Not resolvable")]
+		for (name, value) in &self.headers {
+			let header_name  = HeaderName::from_bytes(name.as_bytes()).map_err(|_err| invalid(format!(r#"Header name "{name}""#)))?;
+			let header_value = HeaderValue::from_str(value).map_err(|_err| invalid(format!(r#"Header value for "{name}""#)))?;
+			let _previous    = headers.insert(header_name, header_value);
+		}
+		builder = builder.default_headers(headers);
+		if let Some(ref user_agent) = self.user_agent {
+			let header_value = HeaderValue::from_str(user_agent).map_err(|_err| invalid(s!("User agent")))?;
+			builder          = builder.user_agent(header_value);
+		}
+		let max_redirects = self.max_redirects.unwrap_or(MAX_REDIRECTS);
+		builder = builder.redirect(RedirectPolicy::custom(move |attempt| {
+			match check_redirect(attempt.url(), attempt.previous(), max_redirects) {
+				Ok(())   => attempt.follow(),
+				Err(err) => attempt.error(err),
+			}
+		}));
+		Ok(builder)
+	}
+	
+	//		build_client														
+	/// Creates an HTTP client that uses these settings.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidHttpSettings`]
+	/// 
+	#[cfg(not(test))]
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	pub fn build_client(&self) -> Result<Client, UpdaterError> {
+		self.client_builder()?.build().map_err(|err| UpdaterError::InvalidHttpSettings(err.to_string()))
+	}
+	
+	//		build_client														
+	/// Creates a mock HTTP client, after checking the settings.
+	/// 
+	/// The settings are checked in the same way as for real, but as the mock
+	/// client cannot be configured, a default one is returned.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidHttpSettings`]
+	/// 
+	#[cfg(test)]
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	pub fn build_client(&self) -> Result<Client, UpdaterError> {
+		let _builder = self.client_builder()?;
+		Ok(Client::new())
+	}
+}
+
+//		Progress																
+/// Progress information for a step of the update process.
+/// 
+/// This is included in the [`Status::Downloading`] and [`Status::Installing`]
+/// statuses, so that subscribers can show how far along the step is.
+/// 
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct Progress {
+	//		Public properties													
+	/// The number of bytes processed so far.
+	pub done:  usize,
+	
+	/// The total number of bytes to process. This will be zero if the total is
+	/// not known, in which case there is no percentage or estimate.
+	pub total: usize,
+	
+	/// The estimated time remaining until the step is complete. This will be
+	/// [`None`] until there is enough information to make an estimate.
+	pub eta:   Option<Duration>,
+}
+
+//󰭅		Progress																
+impl Progress {
+	//		new																	
+	/// Creates a new progress instance.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `done`  - The number of bytes processed so far.
+	/// * `total` - The total number of bytes to process.
+	/// * `eta`   - The estimated time remaining.
+	/// 
+	#[must_use]
+	pub const fn new(done: usize, total: usize, eta: Option<Duration>) -> Self {
+		Self { done, total, eta }
+	}
+	
+	//		measure																
+	/// Measures progress.
+	/// 
+	/// This creates a new progress instance, estimating the time remaining by
+	/// assuming that the rest of the step will proceed at the same rate as it
+	/// has so far.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `done`    - The number of bytes processed so far.
+	/// * `total`   - The total number of bytes to process.
+	/// * `elapsed` - The time taken so far.
+	/// 
+	#[expect(clippy::cast_precision_loss, reason = "Loss of precision is not important here")]
+	#[must_use]
+	pub fn measure(done: usize, total: usize, elapsed: Duration) -> Self {
+		let eta = (done > 0 && total > 0).then(||
+			elapsed.mul_f64(total.saturating_sub(done) as f64 / done as f64)
+		);
+		Self::new(done, total, eta)
+	}
+	
+	//		percent																
+	/// The percentage of the step that has been completed.
+	#[expect(clippy::cast_possible_truncation, reason = "Loss of precision is not important here")]
+	#[expect(clippy::cast_precision_loss,      reason = "Loss of precision is not important here")]
+	#[expect(clippy::cast_sign_loss,           reason = "Loss of sign is not important here")]
+	#[must_use]
+	pub fn percent(&self) -> u8 {
+		if self.total == 0 {
+			return 0;
+		}
+		(self.done as f64 / self.total as f64 * 100.0).min(100.0) as u8
+	}
+}
+
+//		Display																	
+impl Display for Progress {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		if self.total == 0 {
+			write!(f, "{} bytes", self.done)?;
+		} else {
+			write!(f, "{}%, {} of {} bytes", self.percent(), self.done, self.total)?;
+		}
+		if let Some(eta) = self.eta {
+			write!(f, ", {}s remaining", eta.as_secs())?;
+		}
+		Ok(())
+	}
+}
+
+//		RetryPolicy																
+/// The policy to follow when requests to the API server fail.
+/// 
+/// Failed requests are retried if the error is considered to be temporary,
+/// according to [`UpdaterError::is_retryable()`]. The delay before each retry
+/// doubles each time, starting from the base delay, up to the maximum delay,
+/// with a random amount of jitter added to avoid many clients retrying at the
+/// same moment.
+/// 
+#[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct RetryPolicy {
+	//		Public properties													
+	/// The number of times to retry a failed request before giving up. Setting
+	/// this to zero disables retries.
+	pub max_retries:       u32,
+	
+	/// The delay before the first retry.
+	pub base_delay:        Duration,
+	
+	/// The longest delay allowed between retries, before jitter is added.
+	pub max_delay:         Duration,
+	
+	/// The maximum amount of random jitter to add to each delay.
+	pub jitter:            Duration,
+	
+	/// The interval to check for updates at after a check has failed with a
+	/// retryable error, even after retrying. This should normally be longer than
+	/// the usual check interval, so that the server is not overloaded while it
+	/// is having problems. The usual interval is restored once a check
+	/// succeeds. This is optional, and if not specified, the usual interval will
+	/// continue to be used.
+	pub fallback_interval: Option<Duration>,
+	
+	/// How long to keep using a mirror after the primary API URL has failed,
+	/// before trying the primary again. See [`Config::mirrors`].
+	pub primary_retry:     Duration,
+}
+
+//󰭅		RetryPolicy																
+impl RetryPolicy {
+	//		delay																
+	/// Calculates the delay before a retry.
+	/// 
+	/// The delay is the base delay doubled for each previous retry, capped at
+	/// the maximum delay, plus a random amount of jitter.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `attempt` - The number of retries that have already been made.
+	/// 
+	#[must_use]
+	pub fn delay(&self, attempt: u32) -> Duration {
+		let factor = 2_u32.checked_pow(attempt).unwrap_or(u32::MAX);
+		let delay  = self.base_delay.saturating_mul(factor).min(self.max_delay);
+		delay.saturating_add(self.jitter.mul_f64(thread_rng().gen_range(0.0..=1.0)))
+	}
+}
+
+//		Default																	
+impl Default for RetryPolicy {
+	//		default																
+	fn default() -> Self {
+		Self {
+			max_retries:       3,
+			base_delay:        Duration::from_secs(1),
+			max_delay:         Duration::from_secs(60),
+			jitter:            Duration::from_millis(500),
+			fallback_interval: None,
+			primary_retry:     Duration::from_secs(5 * 60),
+		}
+	}
+}
+
+//		ShutdownHook															
+/// A shutdown hook registered using [`Updater::on_shutdown()`].
+struct ShutdownHook(Box<dyn FnOnce() -> BoxFuture<'static, ()> + Send>);
+
+//		Debug																	
+impl Debug for ShutdownHook {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ShutdownHook").finish_non_exhaustive()
+	}
+}
+
+//		Updater																	
+/// A service to check for updates at intervals, and upgrade the application.
+/// 
+/// This struct provides a service that will query the API server at defined
+/// intervals, to check for updates. If an update is found, it will be
+/// downloaded and installed, and the application will be restarted.
+/// 
+#[derive(Debug)]
+pub struct Updater {
+	//		Private properties													
+	/// A counter of critical actions that are currently active. This is used to
+	/// prevent the updater from stopping the application while a critical
+	/// action is in progress.
+	actions:     AtomicUsize,
+	
+	/// The status broadcast channel that status changes are added to. This is
+	/// the sender side only. Each interested party can subscribe to this
+	/// channel to receive status changes on a real-time basis.
+	broadcast:   Broadcaster<Status>,
+	
+	/// The update bundles found in the drop directory that have already been
+	/// dealt with, along with their modification times, so that they are not
+	/// installed again.
+	bundles:     Mutex<HashSet<(PathBuf, SystemTime)>>,
+	
+	/// The configuration for the updater service.
+	config:      Config,
+	
+	/// The versions that have been declined via the configured consent. These
+	/// will not be offered again.
+	declined:    RwLock<HashSet<Version>>,
+	
+	/// When the current wait for critical actions to finish must end, if a
+	/// drain timeout has been configured and a restart is pending.
+	deadline:    Mutex<Option<Instant>>,
+	
+	/// The senders for the lossless status change event subscriptions. Unlike
+	/// the broadcast channel, these are unbounded, so that no events are lost.
+	/// Senders whose receivers have been dropped are removed when the next
+	/// event is sent.
+	events:      Mutex<Vec<Sender<Status>>>,
+	
+	/// The path to the current running executable. This is used to replace the
+	/// executable with the new version when upgrading. It is checked at startup
+	/// and stored here as a reliable reference so that the updater can use it
+	/// later on.
+	exe_path:    PathBuf,
+	
+	/// The listening sockets to pass on to the new version when restarting,
+	/// along with their names. These are duplicates of the application's own
+	/// sockets, so that they stay open regardless of what the application does.
+	listeners:   Mutex<Vec<(String, OwnedFd)>>,
+	
+	/// The identifier to give to the next tracked critical action.
+	next_action: AtomicU64,
+	
+	/// The updater queue that is used for communicating with the interval
+	/// timer. This is the sender side only. A queue is used so that the timer
+	/// can run in a separate thread, but be stopped when required.
+	queue:       Sender<()>,
+	
+	/// The update that has been downloaded and verified, but not yet installed.
+	/// This holds the temporary directory, to keep it alive, along with the
+	/// path to the downloaded file.
+	staged:      Mutex<Option<(TempDir, PathBuf)>>,
+	
+	/// The hook to run before the application is restarted, if one has been
+	/// registered. It is taken when run, so that it only runs once.
+	shutdown:    Mutex<Option<ShutdownHook>>,
+	
+	/// The source that updates are obtained from. This is the configured
+	/// [`source`](Config::source) if there is one, and otherwise the API server.
+	source:      Arc<dyn UpdateSource>,
+	
+	/// The current status of the updater.
+	status:      RwLock<Status>,
+	
+	/// Whether the updater is currently listening for new releases being
+	/// announced by the server. While it is, scheduled checks are skipped.
+	subscribed:  AtomicBool,
+	
+	/// The critical actions that have been registered using guards, by
+	/// identifier. These are tracked so that it is possible to see what is
+	/// holding up a pending restart. Actions registered using
+	/// [`Updater::register_action()`] are counted, but not tracked.
+	tracked:     Mutex<BTreeMap<u64, CriticalAction>>,
+	
+	/// The watch channel that always holds the latest status. This is the
+	/// sender side only. Each interested party can obtain a receiver to see the
+	/// current status at any time, and to wait for it to change.
+	watch:       WatchSender<Status>,
+	
+	/// A weak reference to the updater itself. This is used to resume the
+	/// update process after a deferral, without keeping the updater alive.
+	this:        Weak<Self>,
+}
+
+//󰭅		Updater																	
+impl Updater {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new updater service instance.
+	/// 
+	/// This function creates a new updater service instance, with the specified
+	/// configuration. As soon as the service is created, it will start checking
+	/// for updates.
+	/// 
+	/// In order to shut down nicely, [`Drop`] is implemented, and will send a
+	/// message to the internal queue to stop the timer.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `config` - The configuration for the updater service.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidHttpSettings`]
+	/// * [`UpdaterError::UnableToObtainCurrentExePath`]
+	/// 
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	pub fn new(config: Config) -> Result<Arc<Self>, UpdaterError> {
+		//		Set up updater instance											
+		let exe_path           = current_exe().map_err(|err| UpdaterError::UnableToObtainCurrentExePath(err.to_string()))?;
+		let source: Arc<dyn UpdateSource> = if let Some(ref source) = config.source {
+			Arc::clone(source)
+		} else {
+			let mut source = HttpSource::with_client(
+				config.http.build_client()?,
+				config.api.clone(),
+				config.mirrors.clone(),
+				config.retry_policy.primary_retry,
+			);
+			if let Some(ref token) = config.token {
+				source = source.with_token(token.clone());
+			}
+			Arc::new(source)
+		};
+		let (sender, receiver) = flume::unbounded();
+		let (tx, mut rx)       = broadcast::channel(1);
+		let updater            = Arc::new_cyclic(|this| Self {
 			actions:     AtomicUsize::new(0),
 			broadcast:   tx,
+			bundles:     Mutex::new(HashSet::new()),
 			config,
-			exe_path:    current_exe().map_err(|err| UpdaterError::UnableToObtainCurrentExePath(err.to_string()))?,
-			http_client,
+			declined:    RwLock::new(HashSet::new()),
+			deadline:    Mutex::new(None),
+			events:      Mutex::new(vec![]),
+			exe_path,
+			listeners:   Mutex::new(vec![]),
+			next_action: AtomicU64::new(1),
 			queue:       sender,
+			staged:      Mutex::new(None),
+			shutdown:    Mutex::new(None),
+			source,
 			status:      RwLock::new(Status::Idle),
+			subscribed:  AtomicBool::new(false),
+			tracked:     Mutex::new(BTreeMap::new()),
+			watch:       WatchSender::new(Status::Idle),
+			this:        Weak::clone(this),
 		});
 		//		Listen for status change events									
 		//	It's useful to listen for status changes, so that they can be logged.
 		//	However, a persistent subscriber is also necessary to keep the broadcast
 		//	channel open, as it will be closed when the last subscriber is dropped.
-		#[expect(clippy::pattern_type_mismatch, reason = "Cannot dereference here")]
 		drop(spawn(async move { loop { select! {
 			//	Wait for data from the broadcast channel
 			Ok(status) = rx.recv() => {
@@ -376,119 +1553,577 @@ impl Updater {
 		if updater.config.check_on_startup {
 			let startup_updater = Arc::clone(&updater);
 			drop(spawn(async move {
-				startup_updater.check_for_updates().await;
+				sleep(startup_updater.jitter()).await;
+				let _outcome = startup_updater.check_for_updates().await;
 			}));
 		}
-		//		Check for updates at intervals									
-		if let Some(check_interval) = updater.config.check_interval {
-			let mut timer      = interval(check_interval);
-			let mut first_tick = true;
-			let timer_updater  = Arc::clone(&updater);
+		//		Listen for new releases											
+		if updater.config.push_updates {
+			drop(spawn(Self::listen_for_releases(Weak::clone(&updater.this))));
+		}
+		//		Watch for update bundles										
+		if updater.config.bundle_dir.is_some() {
+			drop(spawn(Self::watch_bundles(Weak::clone(&updater.this))));
+		}
+		//		Check for updates at intervals or on a schedule					
+		if updater.config.check_interval.is_some() || updater.config.check_schedule.is_some() {
+			let mut falling_back = false;
+			let timer_updater    = Arc::clone(&updater);
 			//	Event-handling loop
-			drop(spawn(async move { loop { select!{
-				//	Wait for timer to tick
-				_ = timer.tick() => {
-					if first_tick {
-						first_tick = false;
-						continue;
-					}
-					timer_updater.check_for_updates().await;
-				}
-				//	Wait for message from queue - this is a blocking call
-				_ = receiver.recv_async() => {
-					info!("Stopping updater");
+			drop(spawn(async move { loop {
+				let Some(delay) = timer_updater.time_until_check(falling_back, SystemTime::now()) else {
+					warn!("No further update checks are scheduled");
 					break;
+				};
+				select!{
+					//	Wait until the next check is due
+					() = sleep(delay) => {
+						//	New releases are announced while listening
+						if timer_updater.subscribed.load(Ordering::SeqCst) {
+							continue;
+						}
+						let outcome  = timer_updater.check_for_updates().await;
+						let fallback = timer_updater.falls_back_after(&outcome, falling_back);
+						if fallback != falling_back {
+							if fallback {
+								info!("Checking for updates at the fallback interval until a check succeeds");
+							} else {
+								info!("Resuming usual update checks");
+							}
+							falling_back = fallback;
+						}
+					}
+					//	Wait for message from queue - this is a blocking call
+					_ = receiver.recv_async() => {
+						info!("Stopping updater");
+						break;
+					}
 				}
-			}}}));
+			}}));
 		}
 		Ok(updater)
 	}
 	
 	//		Public methods														
 	
-	//		register_action														
-	/// Registers a critical action.
+	//		check_for_updates													
+	/// Checks for updates.
 	/// 
-	/// This function increments the critical actions counter, preventing the
-	/// application from being updated while the critical action is in progress.
+	/// This function checks for updates by querying the API server. If an
+	/// update is found, it will be downloaded and installed, and the
+	/// application will be restarted — although the configured
+	/// [`UpdatePolicy`] may stop this process at an earlier step.
 	/// 
-	/// It returns the *likely new value* of the counter, or [`None`] if the
-	/// counter overflows or if starting a new action is not permitted due to a
-	/// pending update. The new value is likely rather than guaranteed due to
-	/// the effect of concurrent updates, and therefore is the value known and
-	/// set at the time it was incremented, and may not be the value by the time
-	/// the function returns.
+	/// If a [`Consent`] implementation has been configured, it will be asked
+	/// before each step. If a step is deferred, the outcome is returned
+	/// straight away, and the process resumes by itself after the deferral.
 	/// 
-	pub fn register_action(&self) -> Option<usize> {
-		match self.status() {
-			Status::Idle              |
-			Status::Checking          |
-			Status::Downloading(_, _) |
-			Status::Installing(_)     => {},
-			Status::PendingRestart(_) |
-			Status::Restarting(_)     => return None,
-		}
-		let value = self.actions
-			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |value| { value.checked_add(1) })
-			.ok()?
-		;
-		Some(value.saturating_add(1))
-	}
-	
-	//		deregister_action													
-	/// Deregisters a critical action.
+	/// It is called automatically on startup and at intervals, according to
+	/// the configuration, but can also be called at any time to check on
+	/// demand. Only one check can be underway at a time, and so if the updater
+	/// is not idle when this function is called, it will return immediately.
+	/// The exception is when an update is being held at a step of the update
+	/// process, because the [`UpdatePolicy`] goes no further or because consent
+	/// has been deferred. A newer version then replaces the held update, and
+	/// otherwise the held update is left as it is. An update that has already
+	/// been installed is never replaced, as the backup of the original
+	/// executable would be lost, and so the newer version is only picked up
+	/// after the restart.
 	/// 
-	/// This function decrements the critical actions counter, allowing the
-	/// application to be updated once the count reaches zero.
+	/// The outcome of the check is returned, and any error encountered is also
+	/// logged. Note that if the application is restarted, this function will
+	/// not return.
 	/// 
-	/// It returns the *likely new value* of the counter, or [`None`] if the
-	/// counter underflows. The new value is likely rather than guaranteed due
-	/// to the effect of concurrent updates, and therefore is the value known
-	/// and set at the time it was incremented, and may not be the value by the
-	/// time the function returns.
+	/// # Errors
 	/// 
-	/// If a restart is pending, then when the critical actions counter reaches
-	/// zero, the restart will be triggered.
+	/// Errors are reported using [`CheckOutcome::Failed`] rather than via a
+	/// [`Result`], as a failed check is one of the expected outcomes. Any of
+	/// the errors that can occur when communicating with the API server or
+	/// installing the update may be reported, plus:
 	/// 
-	pub fn deregister_action(&self) -> Option<usize> {
-		let mut value = self.actions
-			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |value| { value.checked_sub(1) })
-			.ok()?
-		;
-		value = value.saturating_sub(1);
-		if let Status::PendingRestart(version) = self.status() {
-			if value > 0 {
-				info!("Pending restart: {} critical actions in progress", self.actions.load(Ordering::SeqCst));
-			} else {
-				self.set_status(Status::Restarting(version));
-				info!("Restarting");
-				self.restart();
+	/// * [`UpdaterError::UpdateAlreadyUnderway`]
+	/// 
+	pub async fn check_for_updates(&self) -> CheckOutcome {
+		//		Ensure no updates are already underway							
+		//	An update that is being held at a step of the update process does not
+		//	stop a check, so that a newer version can take its place.
+		let mut held = None;
+		if let Err(status) = self.set_status_if(|status| {
+			held = Self::held_update(status).map(|(version, outcome)| (status.clone(), version, outcome));
+			*status == Status::Idle || held.is_some()
+		}, Status::Checking) {
+			return CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(status));
+		}
+		let previous = held.as_ref().map_or(Status::Idle, |update| update.0.clone());
+		//		Get latest version												
+		info!("Checking for updates");
+		let payload = match self.request(|source| source.latest()).await {
+			Ok(data) => data,
+			Err(err) => {
+				self.set_status(previous);
+				error!("Error checking for updates: {err}");
+				return CheckOutcome::Failed(err);
+			},
+		};
+		let version = match self.decode_and_verify::<LatestVersionResponse>(payload) {
+			Ok(json) => json.version,
+			Err(err) => {
+				self.set_status(previous);
+				error!("Error checking for updates: {err}");
+				return CheckOutcome::Failed(err);
+			},
+		};
+		//		Compare to current version										
+		if version <= self.config.version {
+			self.set_status(previous);
+			info!("The current version {} is the latest available", self.config.version);
+			return CheckOutcome::UpToDate(version);
+		}
+		if let Some((status, held_version, outcome)) = held {
+			if version <= held_version {
+				self.set_status(status);
+				info!("Version {held_version} is still the latest available");
+				return outcome;
+			}
+			//	An installed update cannot be replaced, as installing another would
+			//	overwrite the backup of the original executable
+			if let Status::Installed(_) = status {
+				self.set_status(status);
+				info!("New version {version} available, but version {held_version} has already been installed");
+				return outcome;
 			}
 		}
-		Some(value)
+		if self.declined.read().contains(&version) {
+			self.set_status(previous);
+			info!("New version {version} available, but it has been declined");
+			return CheckOutcome::Declined(version);
+		}
+		if previous != Status::Idle {
+			info!("New version {version} replaces the held update");
+			drop(self.staged.lock().take());
+		}
+		info!("New version {} available", version);
+		self.set_status(Status::UpdateAvailable(version.clone()));
+		self.advance(version).await
 	}
 	
-	//		is_safe_to_update													
-	/// Checks if it is safe to update.
+	//		install_bundle														
+	/// Installs an update from a bundle.
 	/// 
-	/// This function checks the critical actions counter, to see if it is safe
-	/// to update the application — i.e. if the counter is zero.
+	/// This function reads an update bundle from the specified path, and checks
+	/// its signature against the public key. If it contains a newer version,
+	/// the release file is extracted and checked against the signed hash, and
+	/// the update then proceeds as if it had been downloaded, according to the
+	/// configured [`UpdatePolicy`] and any maintenance windows or consent. This
+	/// allows applications to be updated without being able to reach the API
+	/// server. See the [`bundle`](crate::bundle) module for details.
 	/// 
-	/// Note that at present this is a naive implementation that does not lock
-	/// the counter, and so it is possible that the counter could change between
-	/// the time of checking and the time of updating.
+	/// As with [`check_for_updates()`](Self::check_for_updates()), the
+	/// updater must be idle, and the outcome is returned. Note that if the
+	/// application is restarted, this function will not return.
 	/// 
-	pub fn is_safe_to_update(&self) -> bool {
-		self.actions.load(Ordering::SeqCst) == 0
-	}
-	
-	//		status																
-	/// Gets the current status of the updater.
+	/// # Parameters
 	/// 
-	/// This function returns the current status of the updater, correct at the
-	/// time of calling.
+	/// * `path` - The path to the update bundle.
 	/// 
-	/// Note that the status may change between the time of calling and the time
+	/// # Errors
+	/// 
+	/// Errors are reported using [`CheckOutcome::Failed`] rather than via a
+	/// [`Result`]. Any of the errors that can occur when reading and verifying
+	/// the bundle, or installing the update, may be reported, plus:
+	/// 
+	/// * [`UpdaterError::UpdateAlreadyUnderway`]
+	/// 
+	pub async fn install_bundle(&self, path: &Path) -> CheckOutcome {
+		//		Ensure no updates are already underway							
+		if let Err(status) = self.set_status_if(|status| *status == Status::Idle, Status::Checking) {
+			return CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(status));
+		}
+		//		Read and verify metadata										
+		info!("Reading update bundle {path:?}");
+		let (release, reader) = match self.open_bundle(path).await {
+			Ok(opened) => opened,
+			Err(err)   => {
+				self.set_status(Status::Idle);
+				error!("Error reading update bundle: {err}");
+				return CheckOutcome::Failed(err);
+			},
+		};
+		let version = release.version;
+		//		Compare to current version										
+		if version <= self.config.version {
+			self.set_status(Status::Idle);
+			info!("The current version {} is newer than or the same as the bundle", self.config.version);
+			return CheckOutcome::UpToDate(version);
+		}
+		if self.declined.read().contains(&version) {
+			self.set_status(Status::Idle);
+			info!("Update bundle contains version {version}, but it has been declined");
+			return CheckOutcome::Declined(version);
+		}
+		//		Extract and verify release file									
+		info!("Verifying update {version} from bundle");
+		self.set_status(Status::Verifying(version.clone()));
+		match self.unpack_bundle(path, &version, release.hash, reader).await {
+			Ok(staged) => *self.staged.lock() = Some(staged),
+			Err(err)   => {
+				error!("Error verifying update bundle: {err}");
+				return self.fail(version, err);
+			},
+		}
+		info!("Update file verified");
+		self.set_status(Status::Downloaded(version.clone()));
+		self.advance(version).await
+	}
+	
+	//		download															
+	/// Downloads an available update.
+	/// 
+	/// This function downloads and verifies the update that was found by the
+	/// last check, when the [`UpdatePolicy`] has stopped the process at that
+	/// point. The status must be [`Status::UpdateAvailable`], and will be set
+	/// to [`Status::Downloaded`] once the update is ready to install.
+	/// 
+	/// If the download fails, the status will be set back to
+	/// [`Status::UpdateAvailable`], so that it can be tried again.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidStatus`]
+	/// 
+	/// Any of the errors that can occur when downloading and verifying the
+	/// update may also be returned.
+	/// 
+	pub async fn download(&self) -> Result<(), UpdaterError> {
+		let status = self.status();
+		let Status::UpdateAvailable(ref version) = status else {
+			return Err(UpdaterError::InvalidStatus(status));
+		};
+		self.set_status_if(|current| *current == status, Status::Downloading(version.clone(), Progress::default()))
+			.map_err(UpdaterError::InvalidStatus)?
+		;
+		match self.fetch_update(version).await {
+			Ok(staged) => {
+				*self.staged.lock() = Some(staged);
+				self.set_status(Status::Downloaded(version.clone()));
+				Ok(())
+			},
+			Err(err) => {
+				self.set_status(status.clone());
+				Err(err)
+			},
+		}
+	}
+	
+	//		install																
+	/// Installs a downloaded update.
+	/// 
+	/// This function installs the update that has been downloaded, when the
+	/// [`UpdatePolicy`] has stopped the process at that point. The status must
+	/// be [`Status::Downloaded`], and will be set to [`Status::Installed`] once
+	/// the update has been installed. The application will not be restarted.
+	/// 
+	/// It can also be called while installation is being held back until a
+	/// maintenance window opens, in which case the update is installed straight
+	/// away, without waiting for the window.
+	/// 
+	/// If the installation fails, the status will be set back to
+	/// [`Status::UpdateAvailable`], as the downloaded file will need to be
+	/// obtained again.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidStatus`]
+	/// * [`UpdaterError::MissingDownload`]
+	/// 
+	/// Any of the errors that can occur when installing the update may also be
+	/// returned.
+	/// 
+	pub async fn install(&self) -> Result<(), UpdaterError> {
+		let status = self.status();
+		let (Status::Downloaded(ref version) | Status::AwaitingWindow(ref version, ConsentStep::Install, _)) = status else {
+			return Err(UpdaterError::InvalidStatus(status));
+		};
+		self.set_status_if(|current| *current == status, Status::Installing(version.clone(), Progress::default()))
+			.map_err(UpdaterError::InvalidStatus)?
+		;
+		let Some((download_dir, update_path)) = self.staged.lock().take() else {
+			self.set_status(Status::UpdateAvailable(version.clone()));
+			return Err(UpdaterError::MissingDownload(version.clone()));
+		};
+		if let Err(err) = self.install_update(version, &update_path).await {
+			self.set_status(Status::UpdateAvailable(version.clone()));
+			return Err(err);
+		}
+		drop(download_dir);
+		self.set_status(Status::Installed(version.clone()));
+		Ok(())
+	}
+	
+	//		restart																
+	/// Restarts the application to apply an installed update.
+	/// 
+	/// This function restarts the application once an update has been
+	/// installed, when the [`UpdatePolicy`] has stopped the process at that
+	/// point. The status must be [`Status::Installed`]. It can also be called
+	/// while restarting is being held back until a maintenance window opens, in
+	/// which case the restart goes ahead without waiting for the window.
+	/// 
+	/// If any critical actions are in progress, the restart will not happen
+	/// straight away. Instead, the status will be set to
+	/// [`Status::PendingRestart`], and the application will be restarted once
+	/// the last critical action has been deregistered. Otherwise, the
+	/// application will be restarted immediately, and this function will not
+	/// return — unless a shutdown hook has been registered using
+	/// [`Updater::on_shutdown()`], in which case the hook is run in the
+	/// background first, and the restart happens once it has finished.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidStatus`]
+	/// 
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	pub fn restart(&self) -> Result<(), UpdaterError> {
+		let status = self.status();
+		let (Status::Installed(ref version) | Status::AwaitingWindow(ref version, ConsentStep::Restart, _)) = status else {
+			return Err(UpdaterError::InvalidStatus(status));
+		};
+		let actions = self.actions.load(Ordering::SeqCst);
+		if actions > 0 {
+			let timeout  = self.config.drain_policy.timeout;
+			let deadline = timeout.and_then(|limit| Instant::now().checked_add(limit));
+			self.set_status_if(|current| *current == status, Status::PendingRestart(version.clone(), actions, timeout))
+				.map_err(UpdaterError::InvalidStatus)?
+			;
+			//	The deadline is only set once the restart is known to be pending
+			*self.deadline.lock() = deadline;
+			info!("Pending restart: {actions} critical actions in progress");
+			for action in self.actions() {
+				info!("Waiting for critical action: {action}");
+			}
+			if let (Some(_), Some(updater)) = (timeout, self.this.upgrade()) {
+				let pending = version.clone();
+				drop(spawn(async move {
+					updater.watch_drain(pending).await;
+				}));
+			}
+			return Ok(());
+		}
+		self.set_status_if(|current| *current == status, Status::Restarting(version.clone()))
+			.map_err(UpdaterError::InvalidStatus)?
+		;
+		info!("Restarting");
+		self.shut_down();
+		Ok(())
+	}
+	
+	//		register_action														
+	/// Registers a critical action.
+	/// 
+	/// This function increments the critical actions counter, preventing the
+	/// application from being updated while the critical action is in progress.
+	/// 
+	/// It returns the *likely new value* of the counter, or [`None`] if the
+	/// counter overflows or if starting a new action is not permitted due to a
+	/// pending update. The new value is likely rather than guaranteed due to
+	/// the effect of concurrent updates, and therefore is the value known and
+	/// set at the time it was incremented, and may not be the value by the time
+	/// the function returns.
+	/// 
+	pub fn register_action(&self) -> Option<usize> {
+		match self.status() {
+			Status::Idle                 |
+			Status::Checking             |
+			Status::UpdateAvailable(_)   |
+			Status::Downloading(_, _)    |
+			Status::Verifying(_)         |
+			Status::Downloaded(_)        |
+			Status::Installing(_, _)     |
+			Status::Installed(_)         |
+			Status::AwaitingWindow(_, _, _) |
+			Status::RollingBack(_)       |
+			Status::Failed(_, _)         => {},
+			Status::PendingRestart(_, _, _) |
+			Status::Restarting(_)           => return None,
+		}
+		let value = self.actions
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |value| { value.checked_add(1) })
+			.ok()?
+		;
+		Some(value.saturating_add(1))
+	}
+	
+	//		guard_action														
+	/// Registers a critical action, returning a guard.
+	/// 
+	/// This works in the same way as [`Updater::register_action()`], except
+	/// that the action is deregistered automatically when the returned guard
+	/// is dropped. It is also tracked, along with its name and start time, so
+	/// that it is included in the list given by [`Updater::actions()`].
+	/// 
+	/// It returns [`None`] if starting a new action is not permitted due to a
+	/// pending update.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - An optional name for the action, to help identify it.
+	/// 
+	pub fn guard_action(self: &Arc<Self>, name: Option<&str>) -> Option<ActionGuard> {
+		let _count = self.register_action()?;
+		let id     = self.next_action.fetch_add(1, Ordering::SeqCst);
+		drop(self.tracked.lock().insert(id, CriticalAction {
+			id,
+			name:    name.map(ToOwned::to_owned),
+			started: Instant::now(),
+		}));
+		Some(ActionGuard {
+			id,
+			updater: Arc::downgrade(self),
+		})
+	}
+	
+	//		deregister_action													
+	/// Deregisters a critical action.
+	/// 
+	/// This function decrements the critical actions counter, allowing the
+	/// application to be updated once the count reaches zero.
+	/// 
+	/// It returns the *likely new value* of the counter, or [`None`] if the
+	/// counter underflows. The new value is likely rather than guaranteed due
+	/// to the effect of concurrent updates, and therefore is the value known
+	/// and set at the time it was incremented, and may not be the value by the
+	/// time the function returns.
+	/// 
+	/// If a restart is pending, then when the critical actions counter reaches
+	/// zero, the restart will be triggered — unless the drain deadline has
+	/// already passed and the upgrade has been cancelled.
+	/// 
+	pub fn deregister_action(&self) -> Option<usize> {
+		let mut value = self.actions
+			.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |value| { value.checked_sub(1) })
+			.ok()?
+		;
+		value = value.saturating_sub(1);
+		if let Status::PendingRestart(version, _, _) = self.status() {
+			//	The drain deadline may pass at any moment, and so the status is only
+			//	changed if the restart is still pending
+			let is_pending = |status: &Status| matches!(*status, Status::PendingRestart(ref pending, _, _) if *pending == version);
+			if value > 0 {
+				if self.set_status_if(is_pending, Status::PendingRestart(version.clone(), value, self.drain_remaining())).is_ok() {
+					info!("Pending restart: {value} critical actions in progress");
+				}
+			} else if self.set_status_if(is_pending, Status::Restarting(version.clone())).is_ok() {
+				*self.deadline.lock() = None;
+				info!("Restarting");
+				self.shut_down();
+			}
+		}
+		Some(value)
+	}
+	
+	//		on_shutdown															
+	/// Registers a shutdown hook.
+	/// 
+	/// The hook is run when the application is about to be restarted, after
+	/// the status has been set to [`Status::Restarting`]. It allows the
+	/// application to shut down gracefully, for instance by telling a server to
+	/// stop accepting connections and finish the ones in progress, draining
+	/// queues, and flushing logs.
+	/// 
+	/// The restart will wait for the hook to finish, for up to the
+	/// `shutdown_timeout` specified in the [`Config`]. If the hook is still
+	/// running by then, the restart will go ahead anyway.
+	/// 
+	/// Only one hook can be registered, and registering another will replace
+	/// the existing one.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `hook` - The function to call before restarting. It returns a future
+	///            that resolves once the shutdown is complete.
+	/// 
+	pub fn on_shutdown<F, Fut>(&self, hook: F)
+	where
+		F:   FnOnce() -> Fut + Send + 'static,
+		Fut: Future<Output = ()> + Send + 'static,
+	{
+		*self.shutdown.lock() = Some(ShutdownHook(Box::new(move || Box::pin(hook()))));
+	}
+	
+	//		register_listener													
+	/// Registers a listening socket to pass on when restarting.
+	/// 
+	/// When the application is restarted by replacing the process or starting
+	/// a new one, registered sockets are kept open and passed on to the new
+	/// version, which can reclaim them using
+	/// [`InheritedListeners`](listeners::InheritedListeners). This allows the
+	/// new version to take over without refusing any connections. Sockets are
+	/// not passed on when the application exits for a supervisor to restart
+	/// it, or when a callback is used.
+	/// 
+	/// The socket is duplicated, so the application can continue to use it as
+	/// normal. Registering a socket with the same name as an existing one will
+	/// replace it.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`   - The name to pass the socket on with. This is used by the
+	///              new version to identify it.
+	/// * `socket` - The listening socket.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidListenerName`]
+	/// * [`UpdaterError::UnableToRegisterListener`]
+	/// 
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	pub fn register_listener<S: AsFd>(&self, name: &str, socket: &S) -> Result<(), UpdaterError> {
+		if name.is_empty() || name.len() > 255 || name.chars().any(|c| c == ':' || c.is_control()) {
+			return Err(UpdaterError::InvalidListenerName(name.to_owned()));
+		}
+		let fd            = socket.as_fd().try_clone_to_owned()
+			.map_err(|err| UpdaterError::UnableToRegisterListener(name.to_owned(), err.to_string()))?
+		;
+		let mut listeners = self.listeners.lock();
+		listeners.retain(|entry| entry.0 != name);
+		listeners.push((name.to_owned(), fd));
+		drop(listeners);
+		Ok(())
+	}
+	
+	//		actions																
+	/// Lists the critical actions that are in progress.
+	/// 
+	/// This includes the actions registered using
+	/// [`Updater::guard_action()`], in the order they were started. Actions
+	/// registered using [`Updater::register_action()`] are not included, as
+	/// they are only counted.
+	/// 
+	pub fn actions(&self) -> Vec<CriticalAction> {
+		self.tracked.lock().values().cloned().collect()
+	}
+	
+	//		is_safe_to_update													
+	/// Checks if it is safe to update.
+	/// 
+	/// This function checks the critical actions counter, to see if it is safe
+	/// to update the application — i.e. if the counter is zero.
+	/// 
+	/// Note that at present this is a naive implementation that does not lock
+	/// the counter, and so it is possible that the counter could change between
+	/// the time of checking and the time of updating.
+	/// 
+	pub fn is_safe_to_update(&self) -> bool {
+		self.actions.load(Ordering::SeqCst) == 0
+	}
+	
+	//		status																
+	/// Gets the current status of the updater.
+	/// 
+	/// This function returns the current status of the updater, correct at the
+	/// time of calling.
+	/// 
+	/// Note that the status may change between the time of calling and the time
 	/// of processing the result.
 	/// 
 	pub fn status(&self) -> Status {
@@ -499,287 +2134,1045 @@ impl Updater {
 	//		set_status															
 	/// Sets the current status of the updater.
 	/// 
-	/// This function changes the current status of the updater to the specified
-	/// value.
+	/// This function changes the current status of the updater to the specified
+	/// value.
+	/// 
+	pub fn set_status(&self, status: Status) {
+		let mut lock = self.status.write();                              //  //
+		*lock        = status.clone();
+		//	Events are sent while the lock is held, so that they are in order
+		self.send_event(&status);
+		let _previous = self.watch.send_replace(status.clone());
+		drop(lock);                                                      //  //
+		if let Err(err) = self.broadcast.send(status) {
+			error!("Failed to broadcast status change: {err}");
+		}
+	}
+	
+	//		subscribe															
+	/// Subscribes to the status change event broadcaster.
+	/// 
+	/// This function provides a receiver that is subscribed to the status
+	/// change event broadcaster, so that every time the status changes, it will
+	/// be notified.
+	/// 
+	/// Note that slow subscribers may miss events. If every event is needed,
+	/// [`subscribe_events()`](Self::subscribe_events()) should be used instead.
+	/// 
+	/// At present this simply subscribes to all status change events, but it
+	/// may be enhanced in future to allow for filtering.
+	/// 
+	pub fn subscribe(&self) -> Listener<Status> {
+		self.broadcast.subscribe()
+	}
+	
+	//		subscribe_events													
+	/// Subscribes to every status change event.
+	/// 
+	/// This function provides a receiver that will be sent every status change
+	/// event from this point on, in order. Unlike [`subscribe()`](Self::subscribe()),
+	/// no events are ever dropped, no matter how slowly they are received,
+	/// which makes this suitable for showing each phase of the update process.
+	/// The receiver can be dropped at any time to unsubscribe.
+	/// 
+	pub fn subscribe_events(&self) -> Receiver<Status> {
+		let (sender, receiver) = flume::unbounded();
+		self.events.lock().push(sender);
+		receiver
+	}
+	
+	//		status_stream														
+	/// Provides a stream of every status change event.
+	/// 
+	/// This is the same as [`subscribe_events()`](Self::subscribe_events()),
+	/// but in the form of a [`Stream`](futures_util::Stream), for use with
+	/// stream combinators.
+	/// 
+	pub fn status_stream(&self) -> RecvStream<'static, Status> {
+		self.subscribe_events().into_stream()
+	}
+	
+	//		watch																
+	/// Watches the current status.
+	/// 
+	/// This function provides a receiver that always holds the latest status.
+	/// Unlike [`subscribe()`](Self::subscribe()), there are no events to keep
+	/// up with, and so nothing can be missed by a slow receiver — although
+	/// intermediate statuses may be skipped if the status changes several
+	/// times in between looks.
+	/// 
+	pub fn watch(&self) -> Watcher<Status> {
+		self.watch.subscribe()
+	}
+	
+	//		wait_for															
+	/// Waits until the status satisfies a condition.
+	/// 
+	/// This function returns as soon as the status satisfies the given
+	/// condition, which may be straight away. If a timeout is specified and it
+	/// passes first, [`None`] is returned.
+	/// 
+	/// For example, a server can wait for a restart to become pending, and then
+	/// start shutting down gracefully:
+	/// 
+	/// ```ignore
+	/// updater.wait_for(|status| matches!(status, Status::PendingRestart(..)), None).await;
+	/// ```
+	/// 
+	/// # Parameters
+	/// 
+	/// * `condition` - The condition that the status must satisfy.
+	/// * `limit`     - The longest time to wait for. This is optional.
+	/// 
+	pub async fn wait_for<F>(&self, condition: F, limit: Option<Duration>) -> Option<Status>
+	where
+		F: FnMut(&Status) -> bool + Send,
+	{
+		let mut watcher = self.watch();
+		let waiting     = async move {
+			watcher.wait_for(condition).await.ok().map(|status| status.clone())
+		};
+		match limit {
+			Some(duration) => timeout(duration, waiting).await.ok().flatten(),
+			None           => waiting.await,
+		}
+	}
+	
+	//		wait_for_status														
+	/// Waits until the status is a particular one.
+	/// 
+	/// This is the same as [`wait_for()`](Self::wait_for()), but waits for an
+	/// exact status. It returns `true` if the status was reached, or `false` if
+	/// the timeout passed first.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `status` - The status to wait for.
+	/// * `limit`  - The longest time to wait for. This is optional.
+	/// 
+	pub async fn wait_for_status(&self, status: &Status, limit: Option<Duration>) -> bool {
+		self.wait_for(|current| current == status, limit).await.is_some()
+	}
+	
+	//		Private methods														
+	
+	//		set_status_if														
+	/// Sets the current status of the updater if a condition is met.
+	/// 
+	/// This function changes the current status of the updater to the specified
+	/// value, but only if the current status satisfies the given condition. The
+	/// check and the change are performed under the same lock, so that nothing
+	/// else can change the status in between.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `condition` - The condition that the current status must satisfy.
+	/// * `status`    - The new status to set.
+	/// 
+	/// # Errors
+	/// 
+	/// If the condition is not met, the status is left unchanged, and the
+	/// current status is returned.
+	/// 
+	fn set_status_if<F>(&self, condition: F, status: Status) -> Result<(), Status>
+	where
+		F: FnOnce(&Status) -> bool,
+	{
+		let mut lock = self.status.write();                              //  //
+		if !condition(&lock) {
+			return Err((*lock).clone());
+		}
+		*lock        = status.clone();
+		//	Events are sent while the lock is held, so that they are in order
+		self.send_event(&status);
+		let _previous = self.watch.send_replace(status.clone());
+		drop(lock);                                                      //  //
+		if let Err(err) = self.broadcast.send(status) {
+			error!("Failed to broadcast status change: {err}");
+		}
+		Ok(())
+	}
+	
+	//		send_event															
+	/// Sends a status change event to the lossless subscribers.
+	/// 
+	/// Any subscribers that have dropped their receivers are removed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `status` - The new status.
+	/// 
+	fn send_event(&self, status: &Status) {
+		self.events.lock().retain(|sender| sender.send(status.clone()).is_ok());
+	}
+	
+	//		falls_back_after													
+	/// Works out whether to check for updates at the fallback interval after a
+	/// check.
+	/// 
+	/// If the check failed with a retryable error, and a fallback interval has
+	/// been configured, then the fallback interval is used. If the check
+	/// succeeded, the usual interval or schedule is used. Otherwise, whichever
+	/// is currently being used is kept.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `outcome`      - The outcome of the check.
+	/// * `falling_back` - Whether the fallback interval is currently being
+	///                    used.
+	/// 
+	fn falls_back_after(&self, outcome: &CheckOutcome, falling_back: bool) -> bool {
+		if let CheckOutcome::Failed(ref err) = *outcome {
+			if err.is_retryable() {
+				return self.config.retry_policy.fallback_interval.is_some();
+			}
+			return falling_back;
+		}
+		false
+	}
+	
+	//		time_until_check													
+	/// Works out how long to wait until the next check for updates.
+	/// 
+	/// This is the fallback interval, if it is being used, or else the time
+	/// until the next scheduled check, or the usual interval, plus a random
+	/// amount of jitter. If no further checks are due, [`None`] is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `falling_back` - Whether the fallback interval is being used.
+	/// * `now`          - The current time.
+	/// 
+	fn time_until_check(&self, falling_back: bool, now: SystemTime) -> Option<Duration> {
+		let fallback = self.config.retry_policy.fallback_interval.filter(|_| falling_back);
+		let delay    = match (fallback, self.config.check_schedule.as_ref()) {
+			(Some(interval), _)    => interval,
+			(None, Some(schedule)) => schedule.next_after(now)?.duration_since(now).unwrap_or_default(),
+			(None, None)           => self.config.check_interval?,
+		};
+		Some(delay.saturating_add(self.jitter()))
+	}
+	
+	//		jitter																
+	/// Picks a random amount of jitter to add before a check for updates.
+	fn jitter(&self) -> Duration {
+		self.config.check_jitter.mul_f64(thread_rng().gen_range(0.0..=1.0))
+	}
+	
+	//		held_update															
+	/// Works out whether an update is being held at a step of the process.
+	/// 
+	/// The update process stops at [`Status::UpdateAvailable`],
+	/// [`Status::Downloaded`], or [`Status::Installed`] when the configured
+	/// [`UpdatePolicy`] goes no further, or when consent has been deferred. For
+	/// these, the version and the outcome reported for it are returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `status` - The status to examine.
+	/// 
+	fn held_update(status: &Status) -> Option<(Version, CheckOutcome)> {
+		match *status {
+			Status::UpdateAvailable(ref version) => Some((version.clone(), CheckOutcome::UpdateAvailable(version.clone()))),
+			Status::Downloaded(ref version)      => Some((version.clone(), CheckOutcome::Downloaded(version.clone()))),
+			Status::Installed(ref version)       => Some((version.clone(), CheckOutcome::PendingRestart(version.clone()))),
+			Status::Idle                         |
+			Status::Checking                     |
+			Status::Downloading(_, _)            |
+			Status::Verifying(_)                 |
+			Status::Installing(_, _)             |
+			Status::AwaitingWindow(_, _, _)      |
+			Status::PendingRestart(_, _, _)      |
+			Status::Restarting(_)                |
+			Status::RollingBack(_)               |
+			Status::Failed(_, _)                 => None,
+		}
+	}
+	
+	//		advance																
+	/// Advances the update process.
+	/// 
+	/// This function carries out the steps of the update process from the
+	/// current status onwards, as far as the configured [`UpdatePolicy`]
+	/// allows. The status must be [`Status::UpdateAvailable`],
+	/// [`Status::Downloaded`], or [`Status::Installed`] for the specified
+	/// version. Installing and restarting are held back until a maintenance
+	/// window opens, and consent is sought before each step, if configured.
+	/// 
+	/// Any error encountered is logged, and reported in the outcome. Note that
+	/// if the application is restarted, this function will not return.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version being updated to.
+	/// 
+	fn advance(&self, version: Version) -> BoxFuture<'_, CheckOutcome> {
+		Box::pin(async move {
+			let policy = self.config.update_policy;
+			//		Download update file										
+			if self.status() == Status::UpdateAvailable(version.clone()) {
+				if policy == UpdatePolicy::NotifyOnly {
+					return CheckOutcome::UpdateAvailable(version);
+				}
+				if let Some(outcome) = self.seek_consent(ConsentStep::Download, &version).await {
+					return outcome;
+				}
+				if let Err(status) = self.set_status_if(
+					|current| *current == Status::UpdateAvailable(version.clone()),
+					Status::Downloading(version.clone(), Progress::default()),
+				) {
+					return CheckOutcome::Failed(UpdaterError::InvalidStatus(status));
+				}
+				match self.fetch_update(&version).await {
+					Ok(staged) => *self.staged.lock() = Some(staged),
+					Err(err)   => return self.fail(version, err),
+				}
+				self.set_status(Status::Downloaded(version.clone()));
+			}
+			//		Install update												
+			if self.status() == Status::Downloaded(version.clone()) {
+				if policy == UpdatePolicy::DownloadOnly {
+					return CheckOutcome::Downloaded(version);
+				}
+				if let Some(outcome) = self.await_window(ConsentStep::Install, &version) {
+					return outcome;
+				}
+				if let Some(outcome) = self.seek_consent(ConsentStep::Install, &version).await {
+					return outcome;
+				}
+				if let Err(status) = self.set_status_if(
+					|current| *current == Status::Downloaded(version.clone()),
+					Status::Installing(version.clone(), Progress::default()),
+				) {
+					return CheckOutcome::Failed(UpdaterError::InvalidStatus(status));
+				}
+				let Some((download_dir, update_path)) = self.staged.lock().take() else {
+					self.set_status(Status::UpdateAvailable(version.clone()));
+					return CheckOutcome::Failed(UpdaterError::MissingDownload(version));
+				};
+				if let Err(err) = self.install_update(&version, &update_path).await {
+					return self.fail(version, err);
+				}
+				drop(download_dir);
+				self.set_status(Status::Installed(version.clone()));
+			}
+			//		Restart application											
+			if self.status() == Status::Installed(version.clone()) {
+				if policy == UpdatePolicy::InstallWithoutRestart {
+					return CheckOutcome::PendingRestart(version);
+				}
+				if let Some(outcome) = self.await_window(ConsentStep::Restart, &version) {
+					return outcome;
+				}
+				if let Some(outcome) = self.seek_consent(ConsentStep::Restart, &version).await {
+					return outcome;
+				}
+				if let Err(err) = self.restart() {
+					return CheckOutcome::Failed(err);
+				}
+				return CheckOutcome::PendingRestart(version);
+			}
+			CheckOutcome::Failed(UpdaterError::InvalidStatus(self.status()))
+		})
+	}
+	
+	//		fail																
+	/// Records a failed update.
+	/// 
+	/// This function sets the status to [`Status::Failed`], and schedules a
+	/// return to [`Status::Idle`] once the configured cool-down has passed. If
+	/// the status has been changed in the meantime, it is left alone.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version that was being updated to.
+	/// * `err`     - The error that occurred.
+	/// 
+	fn fail(&self, version: Version, err: UpdaterError) -> CheckOutcome {
+		let failed   = Status::Failed(version, Box::new(err.clone()));
+		let cooldown = self.config.failure_cooldown;
+		let this     = Weak::clone(&self.this);
+		self.set_status(failed.clone());
+		drop(spawn(async move {
+			sleep(cooldown).await;
+			if let Some(updater) = this.upgrade() {
+				if updater.set_status_if(|status| *status == failed, Status::Idle).is_ok() {
+					info!("Recovered from failed update");
+				}
+			}
+		}));
+		CheckOutcome::Failed(err)
+	}
+	
+	//		await_window														
+	/// Holds back a step of the update process until a maintenance window opens.
+	/// 
+	/// If a [`MaintenanceSchedule`] has been configured, and it covers the
+	/// specified step, then the step can only go ahead while a window is open.
+	/// If it can go ahead, [`None`] is returned. Otherwise, the status is set
+	/// to [`Status::AwaitingWindow`], and the update process is resumed from
+	/// the same point once the next window opens. If no window will ever open,
+	/// the status is left as it is, and it is up to the application to carry
+	/// out the step.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `step`    - The step that is about to be carried out.
+	/// * `version` - The version being updated to.
+	/// 
+	fn await_window(&self, step: ConsentStep, version: &Version) -> Option<CheckOutcome> {
+		let schedule = self.config.maintenance.as_ref()?;
+		let (covered, status, held) = match step {
+			ConsentStep::Download => (false,                            Status::UpdateAvailable(version.clone()), CheckOutcome::UpdateAvailable(version.clone())),
+			ConsentStep::Install  => (schedule.scope.covers_install(), Status::Downloaded(version.clone()),      CheckOutcome::Downloaded(version.clone())),
+			ConsentStep::Restart  => (schedule.scope.covers_restart(), Status::Installed(version.clone()),       CheckOutcome::PendingRestart(version.clone())),
+		};
+		let now      = SystemTime::now();
+		if !covered || schedule.is_open(now) {
+			return None;
+		}
+		let Some(opening) = schedule.next_opening(now) else {
+			warn!("{step} of version {version} held back, but no maintenance window will open");
+			return Some(held);
+		};
+		let awaiting = Status::AwaitingWindow(version.clone(), step, opening);
+		if let Err(current) = self.set_status_if(|current| *current == status, awaiting.clone()) {
+			return Some(CheckOutcome::Failed(UpdaterError::InvalidStatus(current)));
+		}
+		let delay    = opening.duration_since(now).unwrap_or_default();
+		info!("{step} of version {version} held back until the next maintenance window, in {}s", delay.as_secs());
+		let this             = Weak::clone(&self.this);
+		let awaiting_version = version.clone();
+		drop(spawn(async move {
+			sleep(delay).await;
+			if let Some(updater) = this.upgrade() {
+				if updater.set_status_if(|current| *current == awaiting, status).is_ok() {
+					info!("Maintenance window open");
+					let _outcome = updater.advance(awaiting_version).await;
+				}
+			}
+		}));
+		Some(CheckOutcome::AwaitingWindow(version.clone(), opening))
+	}
+	
+	//		seek_consent														
+	/// Seeks consent for a step of the update process.
+	/// 
+	/// This function asks the configured [`Consent`] implementation, if there
+	/// is one, whether the specified step can go ahead. If it can, [`None`] is
+	/// returned. Otherwise, the outcome of the check is returned:
+	/// 
+	///   - If the step is deferred, the status is left as it is, and the update
+	///     process is resumed from the same point once the deferral has passed.
+	///   - If the version is declined, any downloaded file is removed, the
+	///     status is set to [`Status::Idle`], and the version is remembered so
+	///     that it is not offered again.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `step`    - The step that consent is being sought for.
+	/// * `version` - The version being updated to.
+	/// 
+	async fn seek_consent(&self, step: ConsentStep, version: &Version) -> Option<CheckOutcome> {
+		let consent = self.config.consent.as_ref()?;
+		match consent.request(step, version).await {
+			ConsentDecision::Approve      => None,
+			ConsentDecision::Defer(delay) => {
+				info!("{step} of version {version} deferred for {}s", delay.as_secs());
+				let this            = Weak::clone(&self.this);
+				let deferred_version = version.clone();
+				drop(spawn(async move {
+					sleep(delay).await;
+					if let Some(updater) = this.upgrade() {
+						let _outcome = updater.advance(deferred_version).await;
+					}
+				}));
+				Some(CheckOutcome::Deferred(version.clone(), delay))
+			},
+			ConsentDecision::Decline      => {
+				info!("{step} of version {version} declined");
+				let _inserted = self.declined.write().insert(version.clone());
+				drop(self.staged.lock().take());
+				self.set_status(Status::Idle);
+				Some(CheckOutcome::Declined(version.clone()))
+			},
+		}
+	}
+	
+	//		fetch_update														
+	/// Downloads and verifies an application update.
+	/// 
+	/// This function obtains the signed metadata for an application update,
+	/// downloads the update from the URL given in the metadata if there is one,
+	/// or from the update source as usual if not, and checks it against the
+	/// hash in the metadata. The temporary directory containing the download is
+	/// returned along with the path to the file, as the file will be removed
+	/// when the directory is dropped.
+	/// 
+	/// # Errors
+	/// 
+	/// Any of the errors that can occur when downloading and verifying the
+	/// update may be returned.
+	/// 
+	async fn fetch_update(&self, version: &Version) -> Result<(TempDir, PathBuf), UpdaterError> {
+		let release = self.get_release_info(version).await.inspect_err(|err|
+			error!("Error obtaining update metadata: {err}")
+		)?;
+		info!("Downloading update {version}");
+		let (download_dir, update_path, file_hash) = self.download_update(version, release.url.as_ref()).await.inspect_err(|err|
+			error!("Error downloading update file: {err}")
+		)?;
+		info!("Update file downloaded");
+		info!("Verifying update {version}");
+		self.set_status(Status::Verifying(version.clone()));
+		Self::verify_update(&release, file_hash).inspect_err(|err|
+			error!("Error verifying update file: {err}")
+		)?;
+		info!("Update file verified");
+		Ok((download_dir, update_path))
+	}
+	
+	//		install_update														
+	/// Installs an application update.
+	/// 
+	/// This function replaces the current executable with the downloaded
+	/// update, logging any error that occurs. The progress of the installation
+	/// is reported using [`Status::Installing`]. Usually the update can simply
+	/// be moved into place, which is instantaneous, and so only the start and
+	/// the end are reported. If it has to be copied to another filesystem, the
+	/// progress of the copy is reported as well.
+	/// 
+	/// # Errors
+	/// 
+	/// Any of the errors that can occur when replacing the executable may be
+	/// returned.
+	/// 
+	async fn install_update(&self, version: &Version, update_path: &PathBuf) -> Result<(), UpdaterError> {
+		info!("Installing update");
+		let started = Instant::now();
+		let total   = fs::metadata(update_path).await
+			.map_or(0, |metadata| usize::try_from(metadata.len()).unwrap_or(usize::MAX))
+		;
+		self.set_status(Status::Installing(version.clone(), Progress::measure(0, total, started.elapsed())));
+		self.replace_executable(version, update_path).await.inspect_err(|err|
+			error!("Error installing update: {err}")
+		)?;
+		self.set_status(Status::Installing(version.clone(), Progress::measure(total, total, started.elapsed())));
+		Ok(())
+	}
+	
+	//		download_update														
+	/// Downloads an application update.
+	/// 
+	/// This function downloads an application update from the update source,
+	/// in the form of an executable binary, and calculates the SHA256 hash of
+	/// the downloaded file.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version to download.
+	/// * `url`     - The URL to download the release file from, if one was
+	///               given in the metadata for the version.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::MissingData`]
+	/// * [`UpdaterError::TooMuchData`]
+	/// * [`UpdaterError::UnableToCreateDownload`]
+	/// * [`UpdaterError::UnableToCreateTempDir`]
+	/// * [`UpdaterError::UnableToWriteToDownload`]
+	/// 
+	/// Any of the errors that can occur when opening the release file from the
+	/// update source may also be returned.
+	/// 
+	async fn download_update(&self, version: &Version, url: Option<&Url>) -> Result<(TempDir, PathBuf, Sha256Hash), UpdaterError> {
+		//		Prepare file to download to										
+		let download_dir = tempdir().map_err(|err| UpdaterError::UnableToCreateTempDir(err.to_string()))?;
+		let update_path  = download_dir.path().join(format!("update-{version}"));
+		let mut file     = AsyncFile::create(&update_path).await.map_err(|err|
+			UpdaterError::UnableToCreateDownload(update_path.clone(), err.to_string())
+		)?;
+		//		Open release file												
+		let mut release         = self.request(|source| url.map_or_else(
+			||             source.release(version),
+			|download_url| source.release_from(version, download_url),
+		)).await?;
+		let content_length      = release.length;
+		//		Download release to file										
+		let mut hasher          = Sha256::new();
+		let mut body_len        = 0_usize;
+		let started             = Instant::now();
+		let mut reported        = (Progress::default(), started);
+		//	Download in chunks, and update the SHA256 hash along the way. An error
+		//	part-way through is reported as such, rather than leaving it to the
+		//	hash check, as the length may not be known.
+		while let Some(next) = release.stream.next().await {
+			let chunk = next.map_err(|err| UpdaterError::HttpRequestFailed(release.url.clone(), err))?;
+			file.write_all(&chunk).await.map_err(|err|
+				UpdaterError::UnableToWriteToDownload(update_path.clone(), err.to_string())
+			)?;
+			hasher.update(&chunk);
+			body_len     = body_len.saturating_add(chunk.len());
+			let progress = Progress::measure(body_len, content_length.unwrap_or(0), started.elapsed());
+			if self.config.progress_policy.is_due(&reported.0, reported.1.elapsed(), &progress) {
+				self.set_status(Status::Downloading(version.clone(), progress));
+				reported = (progress, Instant::now());
+			}
+		}
+		file.flush().await.map_err(|err|
+			UpdaterError::UnableToWriteToDownload(update_path.clone(), err.to_string())
+		)?;
+		//		Check content length											
+		//	If the length is not known, the signed hash is relied upon instead
+		if let Some(expected_len) = content_length {
+			if body_len < expected_len {
+				return Err(UpdaterError::MissingData(release.url, body_len, expected_len));
+			}
+			if body_len > expected_len {
+				return Err(UpdaterError::TooMuchData(release.url, body_len, expected_len));
+			}
+		}
+		Ok((download_dir, update_path, hasher.finalize().into()))
+	}
+	
+	//		get_release_info													
+	/// Gets the metadata for an application update.
+	/// 
+	/// This function obtains the signed metadata for a version from the update
+	/// source, which contains the SHA256 hash of the release file, and possibly
+	/// a URL to download it from, and checks that it is for the right version.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::InvalidPayload`]
+	/// 
+	/// Any of the errors that can occur when obtaining and verifying the
+	/// metadata may also be returned.
+	/// 
+	async fn get_release_info(&self, version: &Version) -> Result<VersionHashResponse, UpdaterError> {
+		let payload = self.request(|source| source.version_hash(version)).await?;
+		let url     = payload.url.clone();
+		let json    = self.decode_and_verify::<VersionHashResponse>(payload)?;
+		if json.version != *version {
+			return Err(UpdaterError::InvalidPayload(url));
+		}
+		Ok(json)
+	}
+	
+	//		verify_update														
+	/// Verifies an application update.
+	/// 
+	/// This function checks that the SHA256 hash of a downloaded file matches
+	/// the hash in the signed metadata.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `release` - The metadata for the version.
+	/// * `hash`    - The SHA256 hash of the downloaded file.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::FailedHashVerification`]
+	/// 
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	fn verify_update(release: &VersionHashResponse, hash: Sha256Hash) -> Result<(), UpdaterError> {
+		if release.hash != hash {
+			return Err(UpdaterError::FailedHashVerification(release.version.clone()));
+		}
+		Ok(())
+	}
+	
+	//		listen_for_releases													
+	/// Listens for new releases being announced by the server.
+	/// 
+	/// This function connects to the server's events endpoint, and reconnects
+	/// whenever the connection drops or cannot be made, backing off according
+	/// to the [`RetryPolicy`]. It keeps going for as long as the updater
+	/// exists, unless the update source cannot announce new releases.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `this` - A weak reference to the updater.
+	/// 
+	async fn listen_for_releases(this: Weak<Self>) {
+		let mut attempt = 0_u32;
+		while let Some(updater) = this.upgrade() {
+			match updater.follow_events().await {
+				Ok(true)  => {
+					warn!("Stopped listening for new releases, so checking as usual until reconnected");
+					attempt = 0;
+				},
+				Ok(false) => {
+					warn!("The update source cannot announce new releases, so checking as usual");
+					break;
+				},
+				Err(err)  => warn!("Unable to listen for new releases: {err}"),
+			}
+			updater.subscribed.store(false, Ordering::SeqCst);
+			let delay = updater.config.retry_policy.delay(attempt);
+			drop(updater);
+			sleep(delay).await;
+			attempt   = attempt.saturating_add(1);
+		}
+	}
+	
+	//		follow_events														
+	/// Follows the server's events endpoint until the connection drops.
+	/// 
+	/// Each release event received is passed to
+	/// [`handle_release_event()`](Self::handle_release_event()). Events that
+	/// cannot be handled are logged and ignored, so that a bad event does not
+	/// end the connection. An event larger than [`MAX_EVENT_SIZE`] does end the
+	/// connection, as the server is not behaving as expected.
+	/// 
+	/// Returns `false` straight away if the update source cannot announce new
+	/// releases, and otherwise `true` once the connection drops.
+	/// 
+	/// # Errors
+	/// 
+	/// Any of the errors that can occur when connecting to the events endpoint
+	/// of the update source may be returned.
+	/// 
+	async fn follow_events(&self) -> Result<bool, UpdaterError> {
+		let Some(mut events) = self.source.events().await? else {
+			return Ok(false);
+		};
+		let url              = events.url;
+		info!("Listening for new releases");
+		self.subscribed.store(true, Ordering::SeqCst);
+		let mut buffer       = vec![];
+		while let Some(chunk) = events.stream.next().await {
+			match chunk {
+				Ok(bytes) => buffer.extend_from_slice(&bytes),
+				Err(err)  => {
+					warn!("Connection to {url} interrupted: {err}");
+					break;
+				},
+			}
+			//	Events are separated by a blank line
+			while let Some(end) = find_event_end(&buffer).filter(|&end| end <= MAX_EVENT_SIZE) {
+				let block: Vec<u8> = buffer.drain(..end).collect();
+				let Some(data)     = parse_event(&String::from_utf8_lossy(&block)) else {
+					continue;
+				};
+				if let Err(err) = self.handle_release_event(&url, &data).await {
+					warn!("Ignoring release event: {err}");
+				}
+			}
+			if buffer.len() > MAX_EVENT_SIZE {
+				warn!("Dropping connection to {url}: event exceeds {MAX_EVENT_SIZE} bytes");
+				break;
+			}
+		}
+		Ok(true)
+	}
+	
+	//		handle_release_event												
+	/// Handles an event announcing a release.
+	/// 
+	/// The event is verified against the public key, and if it announces a
+	/// version newer than the current one, and the updater is idle or holding
+	/// an older update, a check for updates is carried out. The outcome of the
+	/// check is returned, or [`None`] if no check was needed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url`  - The URL that the event was received from.
+	/// * `data` - The data of the event.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::FailedSignatureVerification`]
+	/// * [`UpdaterError::InvalidPayload`]
+	/// * [`UpdaterError::InvalidSignature`]
+	/// * [`UpdaterError::MissingSignature`]
+	/// 
+	async fn handle_release_event(&self, url: &Url, data: &str) -> Result<Option<CheckOutcome>, UpdaterError> {
+		let Ok(event) = serde_json::from_str::<SignedEvent>(data) else {
+			return Err(UpdaterError::InvalidPayload(url.clone()));
+		};
+		self.verify_signature(url, event.payload.as_bytes(), event.signature)?;
+		let Ok(release) = serde_json::from_str::<LatestVersionResponse>(&event.payload) else {
+			return Err(UpdaterError::InvalidPayload(url.clone()));
+		};
+		let status   = self.status();
+		let eligible = Self::held_update(&status).map_or(status == Status::Idle, |(held, _)| release.version > held);
+		if release.version <= self.config.version || !eligible {
+			return Ok(None);
+		}
+		info!("New version {} announced", release.version);
+		Ok(Some(self.check_for_updates().await))
+	}
+	
+	//		request																
+	/// Makes a request to the update source.
+	/// 
+	/// This function is responsible for handling communications with the update
+	/// source. Requests that fail with a retryable error are retried according
+	/// to the configured [`RetryPolicy`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `fetch` - The function that makes the request using the update source.
+	///             It is called once for each attempt.
+	/// 
+	/// # Errors
 	/// 
-	pub fn set_status(&self, status: Status) {
-		let mut lock = self.status.write();                              //  //
-		*lock        = status.clone();
-		drop(lock);                                                      //  //
-		if let Err(err) = self.broadcast.send(status) {
-			error!("Failed to broadcast status change: {err}");
+	/// Any of the errors returned by the update source may be returned.
+	/// 
+	async fn request<'a, T, F>(&'a self, fetch: F) -> Result<T, UpdaterError>
+	where
+		F: Fn(&'a dyn UpdateSource) -> BoxFuture<'a, Result<T, UpdaterError>>,
+	{
+		let policy      = self.config.retry_policy;
+		let mut attempt = 0;
+		loop {
+			match fetch(&*self.source).await {
+				Err(err) if err.is_retryable() && attempt < policy.max_retries => {
+					let delay = policy.delay(attempt);
+					warn!("Request failed, retrying in {}ms: {err}", delay.as_millis());
+					sleep(delay).await;
+					attempt   = attempt.saturating_add(1);
+				},
+				result => return result,
+			}
 		}
 	}
 	
-	//		subscribe															
-	/// Subscribes to the status change event broadcaster.
+	//		watch_bundles														
+	/// Watches the drop directory for update bundles.
 	/// 
-	/// This function provides a receiver that is subscribed to the status
-	/// change event broadcaster, so that every time the status changes, it will
-	/// be notified.
+	/// This function looks for new update bundles in the configured
+	/// [`bundle_dir`](Config::bundle_dir) at regular intervals, for as long as
+	/// the updater exists.
 	/// 
-	/// At present this simply subscribes to all status change events, but it
-	/// may be enhanced in future to allow for filtering.
+	/// # Parameters
 	/// 
-	pub fn subscribe(&self) -> Listener<Status> {
-		self.broadcast.subscribe()
+	/// * `this` - A weak reference to the updater.
+	/// 
+	async fn watch_bundles(this: Weak<Self>) {
+		while let Some(updater) = this.upgrade() {
+			let _outcome = updater.scan_bundles().await;
+			drop(updater);
+			sleep(BUNDLE_SCAN_INTERVAL).await;
+		}
 	}
 	
-	//		Private methods														
-	
-	//		check_for_updates													
-	/// Checks for updates.
+	//		scan_bundles														
+	/// Looks for a new update bundle in the drop directory, and installs it.
 	/// 
-	/// This function checks for updates by querying the API server. If an
-	/// update is found, it will be downloaded and installed, and the
-	/// application will be restarted.
+	/// Files with a `.bundle` extension are considered, in order of name, and
+	/// the first one that has not already been dealt with is installed using
+	/// [`install_bundle()`](Self::install_bundle()). A bundle that is replaced
+	/// by a different file of the same name is dealt with again. Nothing is
+	/// done unless the updater is idle.
 	/// 
-	async fn check_for_updates(&self) {
-		//		Ensure no updates are already underway							
+	/// The outcome of the installation is returned, or [`None`] if there was
+	/// nothing to install.
+	/// 
+	async fn scan_bundles(&self) -> Option<CheckOutcome> {
+		let dir = self.config.bundle_dir.as_ref()?;
 		if self.status() != Status::Idle {
-			return;
-		}
-		//		Get latest version												
-		self.set_status(Status::Checking);
-		info!("Checking for updates");
-		let (url, response) = match self.request("latest").await {
-			Ok(data) => data,
-			Err(err) => {
-				self.set_status(Status::Idle);
-				error!("Error checking for updates: {err}");
-				return;
-			},
-		};
-		let version = match self.decode_and_verify::<LatestVersionResponse>(url, response).await {
-			Ok(json) => json.version,
-			Err(err) => {
-				self.set_status(Status::Idle);
-				error!("Error checking for updates: {err}");
-				return;
-			},
-		};
-		//		Compare to current version										
-		if version <= self.config.version {
-			self.set_status(Status::Idle);
-			info!("The current version {} is the latest available", self.config.version);
-			return;
+			return None;
 		}
-		info!("New version {} available", version);
-		//		Download update file											
-		self.set_status(Status::Downloading(version.clone(), 0));
-		info!("Downloading update {version}");
-		let (_download_dir, update_path, file_hash) = match self.download_update(&version).await {
-			Ok(data) => data,
-			Err(err) => {
-				error!("Error downloading update file: {err}");
-				return;
-			},
-		};
-		info!("Update file downloaded");
-		//		Verify update file												
-		info!("Verifying update {version}");
-		if let Err(err) = self.verify_update(&version, file_hash).await {
-			error!("Error verifying update file: {err}");
-			return;
+		let mut entries = fs::read_dir(dir).await.inspect_err(|err|
+			warn!("Unable to read bundle directory {dir:?}: {err}")
+		).ok()?;
+		let mut found   = vec![];
+		while let Ok(Some(entry)) = entries.next_entry().await {
+			let path = entry.path();
+			if path.extension().is_none_or(|extension| extension != "bundle") {
+				continue;
+			}
+			let Ok(metadata) = entry.metadata().await else {
+				continue;
+			};
+			let bundle = (path, metadata.modified().unwrap_or(UNIX_EPOCH));
+			if metadata.is_file() && !self.bundles.lock().contains(&bundle) {
+				found.push(bundle);
+			}
 		}
-		info!("Update file verified");
-		//		Install update													
-		self.set_status(Status::Installing(version.clone()));
-		info!("Installing update");
-		if let Err(err) = self.replace_executable(&update_path).await {
-			error!("Error installing update: {err}");
-			return;
+		let bundle = found.into_iter().min()?;
+		info!("Found update bundle {:?}", bundle.0);
+		let outcome = self.install_bundle(&bundle.0).await;
+		if !matches!(outcome, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(_))) {
+			let _inserted = self.bundles.lock().insert(bundle);
 		}
-		//		Restart application												
-		if !self.is_safe_to_update() {
-			self.set_status(Status::PendingRestart(version.clone()));
-			info!("Pending restart: {} critical actions in progress", self.actions.load(Ordering::SeqCst));
-			return;
+		Some(outcome)
+	}
+	
+	//		open_bundle															
+	/// Opens an update bundle, and verifies its metadata.
+	/// 
+	/// The header of the bundle is read, and the signature of the metadata is
+	/// checked against the public key. The metadata is returned, along with a
+	/// reader positioned at the start of the release file.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The path to the update bundle.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::FailedSignatureVerification`]
+	/// * [`UpdaterError::InvalidBundle`]
+	/// * [`UpdaterError::InvalidPayload`]
+	/// * [`UpdaterError::InvalidSignature`]
+	/// * [`UpdaterError::MissingSignature`]
+	/// * [`UpdaterError::UnableToReadBundle`]
+	/// 
+	async fn open_bundle(&self, path: &Path) -> Result<(VersionHashResponse, BufReader<AsyncFile>), UpdaterError> {
+		let read_error = |err: IoError| UpdaterError::UnableToReadBundle(path.to_owned(), err.to_string());
+		let invalid    = || UpdaterError::InvalidBundle(path.to_owned());
+		let full_path  = fs::canonicalize(path).await.map_err(read_error)?;
+		//	Signature errors refer to the bundle by URL, as with API responses
+		let url        = Url::from_file_path(&full_path).map_err(|()| invalid())?;
+		let mut reader = BufReader::new(AsyncFile::open(&full_path).await.map_err(read_error)?);
+		if read_header_line(&mut reader).await.is_none_or(|format| format != BUNDLE_FORMAT) {
+			return Err(invalid());
 		}
-		self.set_status(Status::Restarting(version.clone()));
-		info!("Restarting");
-		self.restart();
+		let payload    = read_header_line(&mut reader).await.ok_or_else(invalid)?;
+		let signature  = read_header_line(&mut reader).await.ok_or_else(invalid)?;
+		self.verify_signature(&url, payload.as_bytes(), signature)?;
+		let Ok(release) = serde_json::from_str::<VersionHashResponse>(&payload) else {
+			return Err(UpdaterError::InvalidPayload(url));
+		};
+		Ok((release, reader))
 	}
 	
-	//		download_update														
-	/// Downloads an application update.
+	//		unpack_bundle														
+	/// Extracts the release file from an update bundle, and verifies it.
 	/// 
-	/// This function downloads an application update from the API server, in
-	/// the form of an executable binary, and calculates the SHA256 hash of the
-	/// downloaded file.
+	/// The release file is written to a temporary directory, and its SHA256
+	/// hash is checked against the hash from the signed metadata.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path`    - The path to the update bundle.
+	/// * `version` - The version contained in the bundle.
+	/// * `hash`    - The expected SHA256 hash of the release file.
+	/// * `reader`  - The reader for the bundle, positioned at the start of the
+	///               release file.
 	/// 
 	/// # Errors
 	/// 
-	/// * [`UpdaterError::MissingData`]
-	/// * [`UpdaterError::TooMuchData`]
+	/// * [`UpdaterError::FailedHashVerification`]
 	/// * [`UpdaterError::UnableToCreateDownload`]
 	/// * [`UpdaterError::UnableToCreateTempDir`]
+	/// * [`UpdaterError::UnableToReadBundle`]
 	/// * [`UpdaterError::UnableToWriteToDownload`]
-	/// * [`UpdaterError::UnexpectedContentType`]
 	/// 
-	async fn download_update(&self, version: &Version) -> Result<(TempDir, PathBuf, Sha256Hash), UpdaterError> {
-		//		Prepare file to download to										
+	async fn unpack_bundle(
+		&self,
+		path:       &Path,
+		version:    &Version,
+		hash:       Sha256Hash,
+		mut reader: BufReader<AsyncFile>,
+	) -> Result<(TempDir, PathBuf), UpdaterError> {
 		let download_dir = tempdir().map_err(|err| UpdaterError::UnableToCreateTempDir(err.to_string()))?;
 		let update_path  = download_dir.path().join(format!("update-{version}"));
 		let mut file     = AsyncFile::create(&update_path).await.map_err(|err|
 			UpdaterError::UnableToCreateDownload(update_path.clone(), err.to_string())
 		)?;
-		//		Get headers														
-		let (url, response) = self.request(&format!("releases/{version}")).await?;
-		let content_type:   String = get_header(&response, CONTENT_TYPE);
-		let content_length: usize  = get_header(&response, CONTENT_LENGTH);
-		//		Check content type												
-		if content_type != "application/octet-stream" {
-			return Err(UpdaterError::UnexpectedContentType(url, content_type, s!("application/octet-stream")));
-		}
-		//		Download release to file										
-		let mut response_stream = response.bytes_stream();
-		let mut hasher          = Sha256::new();
-		let mut body_len        = 0_usize;
-		//	Download in chunks, and update the SHA256 hash along the way
-		while let Some(Ok(chunk)) = response_stream.next().await {
-			file.write_all(&chunk).await.map_err(|err|
-				UpdaterError::UnableToWriteToDownload(update_path.clone(), err.to_string())
+		let write_error  = |err: IoError| UpdaterError::UnableToWriteToDownload(update_path.clone(), err.to_string());
+		let mut hasher   = Sha256::new();
+		let mut buffer   = vec![0_u8; 64 * 1_024];
+		loop {
+			let length = reader.read(&mut buffer).await.map_err(|err|
+				UpdaterError::UnableToReadBundle(path.to_owned(), err.to_string())
 			)?;
-			hasher.update(&chunk);
-			body_len = body_len.saturating_add(chunk.len());
-			#[expect(clippy::cast_possible_truncation, reason = "Loss of precision is not important here")]
-			#[expect(clippy::cast_precision_loss,      reason = "Loss of precision is not important here")]
-			#[expect(clippy::cast_sign_loss,           reason = "Loss of sign is not important here")]
-			self.set_status(Status::Downloading(version.clone(), (body_len as f64 / content_length as f64 * 100.0) as u8));
-		}
-		//		Check content length											
-		if body_len < content_length {
-			return Err(UpdaterError::MissingData(url, body_len, content_length));
+			let Some(chunk) = buffer.get(..length).filter(|chunk| !chunk.is_empty()) else {
+				break;
+			};
+			file.write_all(chunk).await.map_err(write_error)?;
+			hasher.update(chunk);
 		}
-		if body_len > content_length {
-			return Err(UpdaterError::TooMuchData(url, body_len, content_length));
+		file.flush().await.map_err(write_error)?;
+		if Sha256Hash::from(hasher.finalize()) != hash {
+			return Err(UpdaterError::FailedHashVerification(version.clone()));
 		}
-		Ok((download_dir, update_path, hasher.finalize().into()))
+		Ok((download_dir, update_path))
 	}
 	
-	//		verify_update														
-	/// Verifies an application update.
-	/// 
-	/// This function checks that the SHA256 hash of a downloaded file matches
-	/// the hash provided by the API server.
-	/// 
-	/// # Errors
+	//		decode_and_verify													
+	/// Decodes a JSON payload and verifies its signature.
 	/// 
-	/// * [`UpdaterError::InvalidPayload`]
-	/// * [`UpdaterError::FailedHashVerification`]
+	/// This function accepts a payload obtained from the update source,
+	/// verifies the signature against the public key, and decodes it.
 	/// 
-	async fn verify_update(&self, version: &Version, hash: Sha256Hash) -> Result<(), UpdaterError> {
-		let (url, response) = self.request(&format!("hashes/{version}")).await?;
-		match self.decode_and_verify::<VersionHashResponse>(url.clone(), response).await {
-			Ok(json) => {
-				if json.version != *version {
-					return Err(UpdaterError::InvalidPayload(url));
-				}
-				if json.hash != hash {
-					return Err(UpdaterError::FailedHashVerification(version.clone()));
-				}
-				Ok(())
-			},
-			Err(err) => Err(err),
-		}
-	}
-	
-	//		request																
-	/// Make HTTP request.
+	/// # Parameters
 	/// 
-	/// This function is responsible for handling communications with the API
-	/// server.
+	/// * `payload` - The payload and signature obtained from the update source.
 	/// 
 	/// # Errors
 	/// 
-	/// * [`UpdaterError::HttpError`]
-	/// * [`UpdaterError::HttpRequestFailed`]
-	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::FailedSignatureVerification`]
+	/// * [`UpdaterError::InvalidPayload`]
+	/// * [`UpdaterError::InvalidSignature`]
+	/// * [`UpdaterError::MissingSignature`]
 	/// 
-	async fn request(&self, endpoint: &str) -> Result<(Url, Response), UpdaterError> {
-		//		Perform request													
-		let Ok(url)  = self.config.api.join(endpoint) else {
-			return Err(UpdaterError::InvalidUrl(self.config.api.clone(), endpoint.to_owned()));
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	fn decode_and_verify<T: DeserializeOwned>(&self, payload: SignedPayload) -> Result<T, UpdaterError> {
+		//		Verify payload against signature								
+		self.verify_signature(&payload.url, payload.body.as_bytes(), payload.signature)?;
+		//		Decode payload													
+		let Ok(parsed) = serde_json::from_str::<T>(&payload.body) else {
+			return Err(UpdaterError::InvalidPayload(payload.url));
 		};
-		let response = self.http_client.get(url.clone()).send().await.map_err(|err|
-			UpdaterError::HttpRequestFailed(url.clone(), err.to_string())
-		)?;
-		//		Check status													
-		let status = response.status();
-		if !status.is_success() {
-			return Err(UpdaterError::HttpError(url, status));
-		}
-		Ok((url, response))
+		Ok(parsed)
 	}
 	
-	//		decode_and_verify													
-	/// Decodes a JSON HTTP response body and verifies signature.
+	//		verify_signature													
+	/// Verifies a signature against the public key.
+	/// 
+	/// # Parameters
 	/// 
-	/// This function accepts an HTTP response that contains a JSON payload,
-	/// decodes it, and verifies the signature against the public key.
+	/// * `url`       - The URL that the signed data was received from.
+	/// * `data`      - The data that was signed.
+	/// * `signature` - The signature, in hexadecimal.
 	/// 
 	/// # Errors
 	/// 
 	/// * [`UpdaterError::FailedSignatureVerification`]
-	/// * [`UpdaterError::InvalidBody`]
-	/// * [`UpdaterError::InvalidPayload`]
 	/// * [`UpdaterError::InvalidSignature`]
-	/// * [`UpdaterError::MissingData`]
 	/// * [`UpdaterError::MissingSignature`]
-	/// * [`UpdaterError::TooMuchData`]
-	/// * [`UpdaterError::UnexpectedContentType`]
-	/// 
-	async fn decode_and_verify<T: DeserializeOwned>(&self, url: Url, response: Response) -> Result<T, UpdaterError> {
-		//		Get headers														
-		let content_type:   String = get_header(&response, CONTENT_TYPE);
-		let content_length: usize  = get_header(&response, CONTENT_LENGTH);
-		let signature:      String = get_header(&response, "x-signature");
-		//		Get body														
-		let Ok(body) = response.text().await else {
-			return Err(UpdaterError::InvalidBody(url))
-		};
-		//		Check headers													
-		if content_type != "application/json" {
-			return Err(UpdaterError::UnexpectedContentType(url, content_type, s!("application/json")));
-		}
-		if body.len() < content_length {
-			return Err(UpdaterError::MissingData(url, body.len(), content_length));
-		}
-		if body.len() > content_length {
-			return Err(UpdaterError::TooMuchData(url, body.len(), content_length));
-		}
+	/// 
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	fn verify_signature(&self, url: &Url, data: &[u8], signature: String) -> Result<(), UpdaterError> {
 		if signature.is_empty() {
-			return Err(UpdaterError::MissingSignature(url));
+			return Err(UpdaterError::MissingSignature(url.clone()));
 		}
-		//		Verify payload against signature								
 		let Ok(signature_bytes) = hex::decode(&signature) else {
-			return Err(UpdaterError::InvalidSignature(url, signature))
+			return Err(UpdaterError::InvalidSignature(url.clone(), signature))
 		};
 		let signature_array: &[u8; 64] = signature_bytes.as_slice().try_into().map_err(|_err|
 			UpdaterError::InvalidSignature(url.clone(), signature)
 		)?;
-		if self.config.key.verify_strict(body.as_bytes(), &Signature::from_bytes(signature_array)).is_err() {
-			return Err(UpdaterError::FailedSignatureVerification(url));
+		if self.config.key.verify_strict(data, &Signature::from_bytes(signature_array)).is_err() {
+			return Err(UpdaterError::FailedSignatureVerification(url.clone()));
 		}
-		//		Decode payload													
-		let Ok(parsed) = serde_json::from_str::<T>(&body) else {
-			return Err(UpdaterError::InvalidPayload(url));
-		};
-		Ok(parsed)
+		Ok(())
 	}
 	
 	//		replace_executable													
 	/// Replaces the current executable with the updated one.
 	/// 
 	/// This function renames the currently-running executable with a `.old`
-	/// suffix, and moves the downloaded update into its place.
+	/// suffix, and moves the downloaded update into its place. If the update is
+	/// on another filesystem, it is copied instead, with the progress reported
+	/// using [`Status::Installing`].
 	/// 
 	/// Note that at present it naively assumes that the backup filename doesn't
 	/// exist. It also does not attempt to rename the backup executable back to
 	/// the original name if moving the new executable fails. This behaviour
 	/// will be improved in future.
 	/// 
+	/// # Parameters
+	/// 
+	/// * `version`     - The version being installed.
+	/// * `update_path` - The path to the downloaded update.
+	/// 
 	/// # Errors
 	/// 
 	/// * [`UpdaterError::UnableToGetFileMetadata`]
@@ -787,7 +3180,7 @@ impl Updater {
 	/// * [`UpdaterError::UnableToRenameCurrentExe`]
 	/// * [`UpdaterError::UnableToSetFilePermissions`]
 	/// 
-	async fn replace_executable(&self, update_path: &PathBuf) -> Result<(), UpdaterError> {
+	async fn replace_executable(&self, version: &Version, update_path: &PathBuf) -> Result<(), UpdaterError> {
 		let current_path = self.exe_path.clone();
 		let backup_path  = current_path.with_extension("old");
 		let move_error   = |err: IoError| -> UpdaterError {
@@ -803,7 +3196,7 @@ impl Updater {
 			if err.raw_os_error() != Some(18_i32) {
 				return Err(move_error(err));
 			}
-			let _size = fs::copy(&update_path, &current_path).await.map_err(move_error)?;
+			self.copy_executable(version, update_path, &current_path).await.map_err(move_error)?;
 			if let Err(err2) = fs::remove_file(&update_path).await {
 				warn!("Failed to delete temporary update file {update_path:?}: {err2}");
 			}
@@ -819,16 +3212,182 @@ impl Updater {
 		Ok(())
 	}
 	
-	//		restart																
-	/// Restarts the application.
+	//		copy_executable														
+	/// Copies the downloaded update into place, reporting progress.
+	/// 
+	/// This is used when the update cannot be moved into place, because it is
+	/// on another filesystem. The file is copied in chunks, and the progress is
+	/// reported using [`Status::Installing`], according to the configured
+	/// [`ProgressPolicy`].
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version being installed.
+	/// * `from`    - The path to the downloaded update.
+	/// * `to`      - The path to copy the update to.
+	/// 
+	/// # Errors
+	/// 
+	/// Returns an I/O error if the file cannot be read or written.
+	/// 
+	async fn copy_executable(&self, version: &Version, from: &Path, to: &Path) -> IoResult<()> {
+		let mut reader   = AsyncFile::open(from).await?;
+		let mut writer   = AsyncFile::create(to).await?;
+		let total        = usize::try_from(reader.metadata().await?.len()).unwrap_or(usize::MAX);
+		let mut buffer   = vec![0_u8; 64 * 1_024];
+		let mut done     = 0_usize;
+		let started      = Instant::now();
+		let mut reported = (Progress::default(), started);
+		loop {
+			let length = reader.read(&mut buffer).await?;
+			let Some(chunk) = buffer.get(..length).filter(|chunk| !chunk.is_empty()) else {
+				break;
+			};
+			writer.write_all(chunk).await?;
+			done         = done.saturating_add(length);
+			let progress = Progress::measure(done, total, started.elapsed());
+			if self.config.progress_policy.is_due(&reported.0, reported.1.elapsed(), &progress) {
+				self.set_status(Status::Installing(version.clone(), progress));
+				reported = (progress, Instant::now());
+			}
+		}
+		writer.flush().await
+	}
+	
+	//		drain_remaining														
+	/// Gets the time remaining until the drain deadline, if there is one.
+	fn drain_remaining(&self) -> Option<Duration> {
+		self.deadline.lock().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+	}
+	
+	//		watch_drain															
+	/// Watches the drain deadline while a restart is pending.
+	/// 
+	/// The status is updated at the interval specified in the [`DrainPolicy`],
+	/// to report the time remaining. If the deadline is reached while the
+	/// restart is still pending, the configured [`DrainAction`] is taken.
+	/// Watching stops as soon as the restart is no longer pending.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version waiting to be applied.
+	/// 
+	async fn watch_drain(&self, version: Version) {
+		let is_pending = |status: &Status| matches!(*status, Status::PendingRestart(ref pending, _, _) if *pending == version);
+		loop {
+			let Some(before) = self.drain_remaining() else {
+				return;
+			};
+			sleep(before.min(self.config.drain_policy.report_interval)).await;
+			let Some(remaining) = self.drain_remaining() else {
+				return;
+			};
+			let actions         = self.actions.load(Ordering::SeqCst);
+			if self.set_status_if(is_pending, Status::PendingRestart(version.clone(), actions, Some(remaining))).is_err() {
+				return;
+			}
+			if remaining.is_zero() {
+				break;
+			}
+		}
+		self.drain_expired(version).await;
+	}
+	
+	//		drain_expired														
+	/// Takes action when the drain deadline has passed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version waiting to be applied.
+	/// 
+	async fn drain_expired(&self, version: Version) {
+		let actions     = self.actions.load(Ordering::SeqCst);
+		let outstanding = self.actions();
+		warn!("Drain deadline passed with {actions} critical actions in progress");
+		for action in &outstanding {
+			warn!("Critical action still in progress: {action}");
+		}
+		let is_pending  = |status: &Status| matches!(*status, Status::PendingRestart(ref pending, _, _) if *pending == version);
+		match self.config.drain_policy.action {
+			DrainAction::ForceRestart         => {
+				if self.set_status_if(is_pending, Status::Restarting(version.clone())).is_ok() {
+					*self.deadline.lock() = None;
+					info!("Forcing restart");
+					self.shut_down();
+				}
+			},
+			DrainAction::Rollback             => {
+				//	The status is claimed first, so that the last critical action
+				//	finishing cannot start a restart while the backup is restored
+				if self.set_status_if(is_pending, Status::RollingBack(version.clone())).is_err() {
+					return;
+				}
+				*self.deadline.lock() = None;
+				let backup_path = self.exe_path.with_extension("old");
+				let err         = match fs::rename(&backup_path, &self.exe_path).await {
+					Ok(())   => {
+						info!("Rolled back to the previous version");
+						UpdaterError::DrainTimedOut(version.clone(), actions)
+					},
+					Err(err) => UpdaterError::UnableToRestoreBackup(backup_path, err.to_string()),
+				};
+				error!("Cancelled upgrade: {err}");
+				let _outcome    = self.fail(version, err);
+			},
+			DrainAction::Escalate(ref escalate) => {
+				escalate(&version, &outstanding);
+			},
+		}
+	}
+	
+	//		shut_down															
+	/// Shuts down the application, ready to relaunch it.
+	/// 
+	/// If a shutdown hook has been registered, it is run in the background,
+	/// and the application is relaunched once it has finished or the deadline
+	/// has passed. Otherwise, the application is relaunched straight away.
+	/// 
+	fn shut_down(&self) {
+		let registered = self.shutdown.lock().take();
+		if let (Some(hook), Some(updater)) = (registered, self.this.upgrade()) {
+			drop(spawn(async move {
+				updater.run_shutdown_hook(hook).await;
+				updater.relaunch();
+			}));
+		} else {
+			self.relaunch();
+		}
+	}
+	
+	//		run_shutdown_hook													
+	/// Runs a shutdown hook.
+	/// 
+	/// The hook is given until the `shutdown_timeout` specified in the
+	/// [`Config`] to finish. If it does not finish in time, a warning is logged
+	/// and the hook is abandoned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `hook` - The shutdown hook to run.
+	/// 
+	async fn run_shutdown_hook(&self, hook: ShutdownHook) {
+		info!("Running shutdown hook");
+		let limit = self.config.shutdown_timeout;
+		if timeout(limit, (hook.0)()).await.is_err() {
+			warn!("Shutdown hook did not finish within {}s, restarting anyway", limit.as_secs());
+		}
+	}
+	
+	//		relaunch															
+	/// Relaunches the application.
 	/// 
 	/// This function restarts the currently-running application, for the
-	/// primary purpose of replacing it with the newer version.
+	/// primary purpose of replacing it with the newer version, according to the
+	/// configured [`RestartStrategy`].
 	/// 
-	/// It does so while preserving all arguments originally specified. It will
-	/// inherit the standard I/O streams from the current process, ensuring
-	/// seamless input and output behaviour, and will replace the currently
-	/// running process with the new one.
+	/// When the new version is started by the updater, all arguments originally
+	/// specified are preserved, and the standard I/O streams are inherited
+	/// from the current process, ensuring seamless input and output behaviour.
 	/// 
 	/// If the application fails to restart, this function will log an error,
 	/// and then exit. In this situation there's not a lot else to do at
@@ -837,23 +3396,115 @@ impl Updater {
 	/// option. This behaviour will be considered carefully and improved in
 	/// future when it becomes clearer how to handle it.
 	/// 
-	fn restart(&self) {
-		//	Skip the first argument (current executable name)
-		let args = args().skip(1).collect::<Vec<_>>();
-		let err  = Command::new(self.exe_path.clone())
-			.args(args)
+	fn relaunch(&self) {
+		if self.config.systemd_notify {
+			self.notify_systemd();
+		}
+		match self.config.restart_strategy {
+			RestartStrategy::Exec               => {
+				let err = self.command(Some(process_id())).exec();
+				//	A failure to restart the application is fatal to the installer
+				//	process, so although we won't panic, we also won't continue. We'll
+				//	just exit the application. This is a candidate for potential
+				//	improvement in future, to allow for more graceful handling of this
+				//	situation.
+				error!("Failed to restart application: {err}");
+				exit(1);
+			},
+			RestartStrategy::SpawnAndExit       => match self.command(None).spawn() {
+				Ok(child) => {
+					info!("Started new version as process {}, exiting", child.id());
+					exit(0);
+				},
+				Err(err)  => {
+					error!("Failed to start new version: {err}");
+					exit(1);
+				},
+			},
+			RestartStrategy::Exit(code)         => {
+				info!("Exiting with code {code} for the new version to be started");
+				exit(code);
+			},
+			RestartStrategy::Callback(ref callback) => callback(&self.exe_path),
+		}
+	}
+	
+	//		command																
+	/// Prepares the command to start the new version.
+	/// 
+	/// The command uses the same arguments as the current process, skipping
+	/// the first (the current executable name), and inherits its standard I/O
+	/// streams.
+	/// 
+	/// Any registered listening sockets are passed on, starting from file
+	/// descriptor `3`, and described using the `LISTEN_FDS` and
+	/// `LISTEN_FDNAMES` environment variables. If a problem occurs when doing
+	/// so, it is logged, and the new version is started without them.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `pid` - The process ID that the new version will have, if known. This
+	///           is given in the `LISTEN_PID` environment variable.
+	/// 
+	fn command(&self, pid: Option<u32>) -> Command {
+		let mut command = Command::new(self.exe_path.clone());
+		let _builder    = command
+			.args(args().skip(1))
 			.stdin(Stdio::inherit())
 			.stdout(Stdio::inherit())
 			.stderr(Stdio::inherit())
-			.exec()
 		;
-		//	A failure to restart the application is fatal to the installer
-		//	process, so although we won't panic, we also won't continue. We'll
-		//	just exit the application. This is a candidate for potential
-		//	improvement in future, to allow for more graceful handling of this
-		//	situation.
-		error!("Failed to restart application: {err}");
-		exit(1);
+		let listeners   = self.listeners.lock();
+		if listeners.is_empty() {
+			return command;
+		}
+		let names       = listeners.iter().map(|entry| entry.0.as_str()).collect::<Vec<_>>().join(":");
+		let mappings    = listeners.iter().zip(3_i32..).map(|(entry, child_fd)| {
+			entry.1.try_clone().map(|parent_fd| FdMapping { parent_fd, child_fd })
+		}).collect::<Result<Vec<_>, _>>();
+		drop(listeners);
+		let passed      = match mappings {
+			Ok(fds)      => {
+				let count = fds.len();
+				command.fd_mappings(fds).map(|_| count).map_err(|err| err.to_string())
+			},
+			Err(err)     => Err(err.to_string()),
+		};
+		match passed {
+			Ok(count) => {
+				let _envs = command
+					.env("LISTEN_FDS",     count.to_string())
+					.env("LISTEN_FDNAMES", names)
+				;
+				let _pid  = match pid {
+					Some(new_pid) => command.env("LISTEN_PID", new_pid.to_string()),
+					None          => command.env_remove("LISTEN_PID"),
+				};
+				info!("Passing on {count} listening sockets");
+			},
+			Err(err)  => error!("Unable to pass on listening sockets: {err}"),
+		}
+		command
+	}
+	
+	//		notify_systemd														
+	/// Reports the restart to systemd.
+	/// 
+	/// When the process is to be replaced in place, systemd is told that the
+	/// service is reloading. Otherwise, it is told that the service is
+	/// stopping. Nothing is reported when a callback is used, as the callback
+	/// decides what happens. Failures are logged, but do not stop the restart.
+	/// 
+	fn notify_systemd(&self) {
+		let state = match self.config.restart_strategy {
+			RestartStrategy::Exec         => "RELOADING=1\nSTATUS=Restarting to apply update",
+			RestartStrategy::SpawnAndExit |
+			RestartStrategy::Exit(_)      => "STOPPING=1\nSTATUS=Stopping to apply update",
+			RestartStrategy::Callback(_)  => return,
+		};
+		if let Err(err) = systemd::notify(state) {
+			warn!("Failed to notify systemd: {err}");
+		}
 	}
 	
 	//																			
@@ -872,27 +3523,90 @@ impl Drop for Updater {
 
 //		Functions																										
 
-//		get_header																
-/// Gets a header from an HTTP response.
+//		check_redirect															
+/// Checks whether a redirect should be followed.
+/// 
+/// Redirects are not followed if there have already been more than the
+/// maximum number, or if they would go from HTTPS to plain HTTP, which would
+/// expose the request and the response.
+/// 
+/// # Parameters
+/// 
+/// * `next`          - The URL being redirected to.
+/// * `previous`      - The URLs requested so far, starting with the original.
+/// * `max_redirects` - The maximum number of redirects to follow.
+/// 
+/// # Errors
+/// 
+/// A message describing why the redirect is not followed.
+/// 
+fn check_redirect(next: &Url, previous: &[Url], max_redirects: usize) -> Result<(), String> {
+	if previous.len() > max_redirects {
+		return Err(format!("Too many redirects, stopped at {next}"));
+	}
+	if next.scheme() == "http" && previous.iter().any(|url| url.scheme() == "https") {
+		return Err(format!("Refused to follow a redirect from HTTPS to plain HTTP: {next}"));
+	}
+	Ok(())
+}
+
+//		find_event_end															
+/// Finds the end of the first Server-Sent Event in a buffer.
 /// 
-/// This function gets a header from an HTTP response, and converts it to the
-/// specified type.
+/// Events are separated by a blank line, and lines may end with `\r\n`, `\n`,
+/// or `\r`. The position just after the separator is returned, or [`None`] if
+/// the buffer does not yet hold a complete event.
 /// 
 /// # Parameters
 /// 
-/// * `response` - The HTTP response to get the header from.
-/// * `header`   - The header to get from the response.
+/// * `buffer` - The data received so far.
 /// 
-fn get_header<K, T>(response: &Response, header: K) -> T
-where
-	K: AsHeaderName,
-	T: Default + FromStr
-{
-	response.headers()
-		.get(header)
-		.and_then(|h| h.to_str().ok())
-		.and_then(|s| T::from_str(s).ok())
-		.unwrap_or_default()
+fn find_event_end(buffer: &[u8]) -> Option<usize> {
+	[&b"\r\n\r\n"[..], b"\n\n", b"\r\r"].into_iter()
+		.filter_map(|separator| buffer.windows(separator.len())
+			.position(|window| window == separator)
+			.map(|position| position.saturating_add(separator.len()))
+		)
+		.min()
 }
 
+//		parse_event																
+/// Parses a Server-Sent Event, and gets its data if it announces a release.
+/// 
+/// Comments, unknown fields, and events of other types are ignored. If the
+/// event has more than one line of data, the lines are joined together.
+/// 
+/// # Parameters
+/// 
+/// * `block` - The lines making up the event.
+/// 
+fn parse_event(block: &str) -> Option<String> {
+	let mut event = "message";
+	let mut data  = vec![];
+	for line in block.split(['\r', '\n']) {
+		let (field, raw) = line.split_once(':').unwrap_or((line, ""));
+		let value        = raw.strip_prefix(' ').unwrap_or(raw);
+		match field {
+			"event" => event = value,
+			"data"  => data.push(value),
+			_       => {},
+		}
+	}
+	(event == "release" && !data.is_empty()).then(|| data.join("\n"))
+}
 
+//		read_header_line														
+/// Reads a header line from an update bundle.
+/// 
+/// The line must end with a newline character, which is removed. If the line
+/// is missing, incomplete, too long, or not valid UTF-8, [`None`] is returned.
+/// 
+/// # Parameters
+/// 
+/// * `reader` - The reader for the bundle.
+/// 
+async fn read_header_line<R: AsyncBufRead + Unpin>(reader: &mut R) -> Option<String> {
+	let mut line = String::new();
+	let _length  = (&mut *reader).take(MAX_HEADER_LINE).read_line(&mut line).await.ok()?;
+	line.strip_suffix('\n').map(ToOwned::to_owned)
+}
@@ -0,0 +1,344 @@
+//! Consent functionality for the client updater.
+//! 
+//! Applications that are used interactively, such as desktop and terminal
+//! tools, should not be updated or restarted without the user's agreement. The
+//! [`Consent`] trait allows an application to be asked before each step of the
+//! update process is carried out, and to approve it, defer it for a while, or
+//! decline the version altogether.
+//! 
+//! A ready-made implementation for terminal applications is provided as
+//! [`TerminalConsent`], which prompts the user on the standard input and output
+//! streams, and can also display download progress.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/consent.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use super::{Progress, Status};
+use core::fmt::{Debug, self};
+use futures_util::future::BoxFuture;
+use rubedo::sugar::s;
+use semver::Version;
+use std::sync::Arc;
+use tokio::{
+	io::{
+		AsyncBufRead,
+		AsyncBufReadExt as _,
+		AsyncRead,
+		AsyncWrite,
+		AsyncWriteExt as _,
+		BufReader,
+		stdin,
+		stdout,
+	},
+	spawn,
+	sync::{
+		Mutex as AsyncMutex,
+		broadcast::{Receiver as Listener, error::RecvError},
+	},
+	task::JoinHandle,
+	time::Duration,
+};
+
+
+
+//		Constants																										
+
+/// The width of the progress bar, in characters.
+const PROGRESS_BAR_WIDTH: usize = 30;
+
+
+
+//		Enums																											
+
+//		ConsentDecision															
+/// The possible decisions that can be given when consent is requested.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ConsentDecision {
+	/// The step can go ahead now.
+	Approve,
+	
+	/// The step should not happen yet. The updater will wait for the specified
+	/// duration, and then ask again.
+	Defer(Duration),
+	
+	/// The version should not be installed. The updater will abandon the update,
+	/// and will not offer the same version again.
+	Decline,
+}
+
+//		ConsentStep																
+/// The points in the update process at which consent is requested.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum ConsentStep {
+	/// Before downloading the release file for a newer version.
+	Download,
+	
+	/// Before installing a newer version that has been downloaded.
+	Install,
+	
+	/// Before restarting the application to apply a newer version that has
+	/// been installed.
+	Restart,
+}
+
+//󰭅		Display																	
+impl fmt::Display for ConsentStep {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", match *self {
+			Self::Download => "Download",
+			Self::Install  => "Install",
+			Self::Restart  => "Restart",
+		})
+	}
+}
+
+
+
+//		Structs																											
+
+//		TerminalConsent															
+/// A consent implementation for terminal applications.
+/// 
+/// This asks the user for consent by printing a prompt to the standard output
+/// stream, and reading the answer from the standard input stream. The user can
+/// answer "yes" to approve, "later" to defer for the configured duration, or
+/// "never" to decline the version. Pressing Enter without typing anything
+/// approves the step.
+/// 
+/// It can also display the progress of downloads, using
+/// [`TerminalConsent::show_progress()`].
+/// 
+pub struct TerminalConsent {
+	//		Private properties													
+	/// How long to defer for when the user answers "later".
+	defer_for: Duration,
+	
+	/// The stream that the user's answers are read from.
+	input:     AsyncMutex<Box<dyn AsyncBufRead + Send + Unpin>>,
+	
+	/// The stream that prompts and progress are written to. This is shared with
+	/// the progress display task, if there is one.
+	output:    Arc<AsyncMutex<Box<dyn AsyncWrite + Send + Unpin>>>,
+}
+
+//󰭅		TerminalConsent															
+impl TerminalConsent {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new terminal consent instance.
+	/// 
+	/// This uses the standard input and output streams of the process.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `defer_for` - How long to defer for when the user answers "later".
+	/// 
+	#[must_use]
+	pub fn new(defer_for: Duration) -> Self {
+		Self::with_io(defer_for, stdin(), stdout())
+	}
+	
+	//		with_io																
+	/// Creates a new terminal consent instance using the specified streams.
+	/// 
+	/// This is the same as [`TerminalConsent::new()`], but allows the input and
+	/// output streams to be specified, which is useful when the application
+	/// talks to the user through something other than the standard streams.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `defer_for` - How long to defer for when the user answers "later".
+	/// * `input`     - The stream to read the user's answers from.
+	/// * `output`    - The stream to write prompts and progress to.
+	/// 
+	pub fn with_io<R, W>(defer_for: Duration, input: R, output: W) -> Self
+	where
+		R: AsyncRead + Send + Unpin + 'static,
+		W: AsyncWrite + Send + Unpin + 'static,
+	{
+		Self {
+			defer_for,
+			input:  AsyncMutex::new(Box::new(BufReader::new(input))),
+			output: Arc::new(AsyncMutex::new(Box::new(output))),
+		}
+	}
+	
+	//		Public methods														
+	
+	//		show_progress														
+	/// Displays download progress.
+	/// 
+	/// This spawns a task that listens to the status change events from the
+	/// updater, and draws a progress bar whenever [`Status::Downloading`] is
+	/// received. The task ends when the updater is dropped.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `listener` - A subscription to the updater's status change events, as
+	///                obtained from [`Updater::subscribe()`](super::Updater::subscribe()).
+	/// 
+	pub fn show_progress(&self, mut listener: Listener<Status>) -> JoinHandle<()> {
+		let output = Arc::clone(&self.output);
+		spawn(async move {
+			let mut drawn = false;
+			loop {
+				let status = match listener.recv().await {
+					Ok(status)                 => status,
+					Err(RecvError::Lagged(_))  => continue,
+					Err(RecvError::Closed)     => break,
+				};
+				let text = if let Status::Downloading(ref version, progress) = status {
+					drawn = true;
+					format!("\r{}", progress_bar(version, &progress))
+				} else if drawn {
+					drawn = false;
+					s!("\n")
+				} else {
+					continue;
+				};
+				if !write_to(&output, &text).await {
+					break;
+				}
+			}
+		})
+	}
+	
+	//		Private methods														
+	
+	//		ask																	
+	/// Asks the user for consent.
+	/// 
+	/// This prints the prompt for the step, and reads answers until a valid one
+	/// is given. If the input stream is closed, or cannot be written to or read
+	/// from, the step is deferred, as it is not possible to ask the user.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `step`    - The step that consent is being requested for.
+	/// * `version` - The version that the step relates to.
+	/// 
+	async fn ask(&self, step: ConsentStep, version: &Version) -> ConsentDecision {
+		let question = match step {
+			ConsentStep::Download => format!("Version {version} is available. Download it now?"),
+			ConsentStep::Install  => format!("Version {version} has been downloaded. Install it now?"),
+			ConsentStep::Restart  => format!("Version {version} has been installed. Restart now to apply it?"),
+		};
+		loop {
+			if !write_to(&self.output, &format!("{question} [Y]es, [l]ater, or [n]ever: ")).await {
+				return ConsentDecision::Defer(self.defer_for);
+			}
+			let mut answer = String::new();
+			match self.input.lock().await.read_line(&mut answer).await {
+				Ok(0) | Err(_) => return ConsentDecision::Defer(self.defer_for),
+				Ok(_)          => {},
+			}
+			match answer.trim().to_lowercase().as_str() {
+				"" | "y" | "yes" => return ConsentDecision::Approve,
+				"l" | "later"    => return ConsentDecision::Defer(self.defer_for),
+				"n" | "never"    => return ConsentDecision::Decline,
+				_                => {},
+			}
+		}
+	}
+}
+
+//		Consent																	
+impl Consent for TerminalConsent {
+	//		request																
+	fn request<'a>(&'a self, step: ConsentStep, version: &'a Version) -> BoxFuture<'a, ConsentDecision> {
+		Box::pin(self.ask(step, version))
+	}
+}
+
+//		Debug																	
+impl Debug for TerminalConsent {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("TerminalConsent")
+			.field("defer_for", &self.defer_for)
+			.finish_non_exhaustive()
+	}
+}
+
+
+
+//		Traits																											
+
+//§		Consent																	
+/// A means of asking for consent before each step of the update process.
+/// 
+/// When a consent implementation has been configured, the updater will ask it
+/// for a decision before downloading, installing, and restarting, using the
+/// [`Consent::request()`] method. Steps that the configured
+/// [`UpdatePolicy`](super::UpdatePolicy) does not carry out automatically are
+/// not asked about, and neither are steps that the application starts itself,
+/// as calling the relevant method is considered to be consent.
+/// 
+pub trait Consent: Debug + Send + Sync {
+	//		request																
+	/// Requests consent for a step of the update process.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `step`    - The step that consent is being requested for.
+	/// * `version` - The version that the step relates to.
+	/// 
+	fn request<'a>(&'a self, step: ConsentStep, version: &'a Version) -> BoxFuture<'a, ConsentDecision>;
+}
+
+
+
+//		Functions																										
+
+//		progress_bar															
+/// Renders a progress bar.
+/// 
+/// # Parameters
+/// 
+/// * `version`  - The version being downloaded.
+/// * `progress` - The progress of the download.
+/// 
+#[expect(clippy::integer_division, reason = "Precision is not important here")]
+fn progress_bar(version: &Version, progress: &Progress) -> String {
+	let percent   = progress.percent();
+	let filled    = usize::from(percent) * PROGRESS_BAR_WIDTH / 100;
+	let remaining = progress.eta.map_or_else(String::new, |eta| format!("{}s remaining", eta.as_secs()));
+	format!(
+		"Downloading {version}: [{}{}] {percent:>3}% {remaining:<20}",
+		"#".repeat(filled),
+		" ".repeat(PROGRESS_BAR_WIDTH.saturating_sub(filled)),
+	)
+}
+
+//		write_to																
+/// Writes text to an output stream.
+/// 
+/// The text is flushed straight away, so that prompts and progress are shown
+/// without waiting for a newline. If the text cannot be written, `false` is
+/// returned.
+/// 
+/// # Parameters
+/// 
+/// * `output` - The stream to write to.
+/// * `text`   - The text to write.
+/// 
+async fn write_to(output: &AsyncMutex<Box<dyn AsyncWrite + Send + Unpin>>, text: &str) -> bool {
+	let mut writer = output.lock().await;
+	let written    = writer.write_all(text.as_bytes()).await.is_ok() && writer.flush().await.is_ok();
+	drop(writer);
+	written
+}
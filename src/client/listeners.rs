@@ -0,0 +1,235 @@
+//! Listening socket hand-off between versions of the application.
+//! 
+//! When the application is restarted to apply an update, any listening sockets
+//! that have been registered using [`Updater::register_listener()`](super::Updater::register_listener())
+//! are kept open and passed on to the new version. This means that connections
+//! are queued up by the operating system while the restart takes place, rather
+//! than being refused.
+//! 
+//! The sockets are passed on using the same convention as systemd socket
+//! activation: they are given file descriptors starting from `3`, and the
+//! `LISTEN_FDS`, `LISTEN_FDNAMES`, and `LISTEN_PID` environment variables
+//! describe them. The new version can then reclaim them by name using
+//! [`InheritedListeners`], which means that sockets passed in by systemd can be
+//! reclaimed in the same way.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/listeners.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use core::fmt::{Debug, self};
+use listenfd::ListenFd;
+use std::{
+	env::var,
+	io::Result as IoResult,
+	net::{TcpListener, ToSocketAddrs},
+	os::unix::net::UnixListener,
+	path::Path,
+};
+
+
+
+//		Structs																											
+
+//		InheritedListeners														
+/// Listening sockets inherited from a previous version of the application.
+/// 
+/// This is used by the new version of the application, after a restart, to
+/// take over the listening sockets that the previous version registered. Each
+/// socket can be taken once, by the name it was registered with. If no socket
+/// was passed on with that name, for instance because the application has been
+/// started normally, then nothing is returned, and the application should open
+/// the socket itself. The [`InheritedListeners::tcp_listener()`] and
+/// [`InheritedListeners::unix_listener()`] methods take care of this.
+/// 
+/// Note that the environment variables are read once, when the instance is
+/// created, so only one instance should be created by the application.
+/// 
+pub struct InheritedListeners {
+	//		Private properties													
+	/// The inherited file descriptors.
+	fds:   ListenFd,
+	
+	/// The names of the inherited file descriptors, in the same order.
+	names: Vec<String>,
+}
+
+//󰭅		InheritedListeners														
+impl InheritedListeners {
+	//		Constructors														
+	
+	//		from_env															
+	/// Reclaims the listening sockets passed on by the previous version.
+	/// 
+	/// This reads the environment variables that describe the sockets, and
+	/// returns an instance that can be used to take them. If no sockets were
+	/// passed on, the instance will be empty.
+	/// 
+	#[must_use]
+	pub fn from_env() -> Self {
+		Self {
+			names: var("LISTEN_FDNAMES").map(|names| parse_names(&names)).unwrap_or_default(),
+			fds:   ListenFd::from_env(),
+		}
+	}
+	
+	//		Public methods														
+	
+	//		is_empty															
+	/// Checks whether any sockets were inherited.
+	#[must_use]
+	pub fn is_empty(&self) -> bool {
+		self.fds.len() == 0
+	}
+	
+	//		len																	
+	/// Gets the number of sockets that were inherited.
+	/// 
+	/// This includes any that have already been taken.
+	/// 
+	#[must_use]
+	pub fn len(&self) -> usize {
+		self.fds.len()
+	}
+	
+	//		names																
+	/// Gets the names of the sockets that were inherited.
+	#[must_use]
+	pub fn names(&self) -> &[String] {
+		&self.names
+	}
+	
+	//		take_tcp_listener													
+	/// Takes an inherited TCP listener.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name that the listener was registered with.
+	/// 
+	/// # Errors
+	/// 
+	/// Returns an I/O error if the inherited socket is not a TCP listener.
+	/// 
+	pub fn take_tcp_listener(&mut self, name: &str) -> IoResult<Option<TcpListener>> {
+		match self.index_of(name) {
+			Some(index) => self.fds.take_tcp_listener(index),
+			None        => Ok(None),
+		}
+	}
+	
+	//		take_unix_listener													
+	/// Takes an inherited Unix domain socket listener.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name that the listener was registered with.
+	/// 
+	/// # Errors
+	/// 
+	/// Returns an I/O error if the inherited socket is not a Unix domain socket
+	/// listener.
+	/// 
+	pub fn take_unix_listener(&mut self, name: &str) -> IoResult<Option<UnixListener>> {
+		match self.index_of(name) {
+			Some(index) => self.fds.take_unix_listener(index),
+			None        => Ok(None),
+		}
+	}
+	
+	//		tcp_listener														
+	/// Takes an inherited TCP listener, or binds a new one.
+	/// 
+	/// If a listener was inherited with the specified name, it is returned.
+	/// Otherwise, a new listener is bound to the specified address. Note that
+	/// the listener is in blocking mode, so for use with Tokio it will need to
+	/// be set to non-blocking before being converted.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name`    - The name that the listener was registered with.
+	/// * `address` - The address to bind to, if there is no inherited listener.
+	/// 
+	/// # Errors
+	/// 
+	/// Returns an I/O error if the inherited socket is not a TCP listener, or
+	/// if a new listener cannot be bound.
+	/// 
+	pub fn tcp_listener<A: ToSocketAddrs>(&mut self, name: &str, address: A) -> IoResult<TcpListener> {
+		self.take_tcp_listener(name)?.map_or_else(|| TcpListener::bind(address), Ok)
+	}
+	
+	//		unix_listener														
+	/// Takes an inherited Unix domain socket listener, or binds a new one.
+	/// 
+	/// If a listener was inherited with the specified name, it is returned.
+	/// Otherwise, a new listener is bound to the specified path.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name that the listener was registered with.
+	/// * `path` - The path to bind to, if there is no inherited listener.
+	/// 
+	/// # Errors
+	/// 
+	/// Returns an I/O error if the inherited socket is not a Unix domain socket
+	/// listener, or if a new listener cannot be bound.
+	/// 
+	pub fn unix_listener<P: AsRef<Path>>(&mut self, name: &str, path: P) -> IoResult<UnixListener> {
+		self.take_unix_listener(name)?.map_or_else(|| UnixListener::bind(path), Ok)
+	}
+	
+	//		Private methods														
+	
+	//		index_of															
+	/// Finds the position of the socket with the specified name.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The name of the socket.
+	/// 
+	fn index_of(&self, name: &str) -> Option<usize> {
+		self.names.iter().position(|candidate| candidate == name).filter(|&index| index < self.fds.len())
+	}
+}
+
+//		Debug																	
+impl Debug for InheritedListeners {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("InheritedListeners")
+			.field("names", &self.names)
+			.finish_non_exhaustive()
+	}
+}
+
+
+
+//		Functions																										
+
+//		parse_names																
+/// Parses the names of the inherited sockets.
+/// 
+/// The names are given as a colon-separated list, in the same order as the
+/// file descriptors.
+/// 
+/// # Parameters
+/// 
+/// * `names` - The contents of the `LISTEN_FDNAMES` environment variable.
+/// 
+fn parse_names(names: &str) -> Vec<String> {
+	if names.is_empty() {
+		return vec![];
+	}
+	names.split(':').map(ToOwned::to_owned).collect()
+}
+
+
@@ -0,0 +1,434 @@
+//! Maintenance windows for the client updater.
+//! 
+//! Some applications must not be interrupted at certain times, for instance
+//! during trading hours. A [`MaintenanceSchedule`] can be set in the
+//! [`Config`](super::Config), to restrict when the updater installs updates
+//! and restarts the application by itself. Updates can still be found and
+//! downloaded at any time, but the steps covered by the [`MaintenanceScope`]
+//! are held back until a [`MaintenanceWindow`] opens.
+//! 
+//! Windows are specified as a range of times of day, either every day or on
+//! particular days of the week, and are interpreted using a fixed offset from
+//! UTC. A window that ends at or before the time it starts is taken to run
+//! past midnight, into the following day.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/maintenance.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use core::{
+	fmt::{Display, self},
+	time::Duration,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+
+
+//		Constants																										
+
+/// The number of seconds in a day.
+const SECONDS_PER_DAY: i64 = 86_400;
+
+
+
+//		Enums																											
+
+//		MaintenanceScope														
+/// The steps of the update process that are held back until a maintenance
+/// window opens.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub enum MaintenanceScope {
+	/// Hold back installing the update. The application is restarted as soon
+	/// as the update has been installed.
+	Install,
+	
+	/// Hold back restarting the application. The update is installed as soon
+	/// as it has been downloaded.
+	Restart,
+	
+	/// Hold back both installing the update and restarting the application.
+	#[default]
+	InstallAndRestart,
+}
+
+//󰭅		MaintenanceScope														
+impl MaintenanceScope {
+	//		Public methods														
+	
+	//		covers_install														
+	/// Whether installing the update is held back.
+	#[must_use]
+	pub const fn covers_install(self) -> bool {
+		matches!(self, Self::Install | Self::InstallAndRestart)
+	}
+	
+	//		covers_restart														
+	/// Whether restarting the application is held back.
+	#[must_use]
+	pub const fn covers_restart(self) -> bool {
+		matches!(self, Self::Restart | Self::InstallAndRestart)
+	}
+}
+
+//		Weekday																	
+/// The days of the week.
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[expect(clippy::exhaustive_enums, reason = "The days of the week are fixed")]
+pub enum Weekday {
+	/// Monday.
+	Monday,
+	
+	/// Tuesday.
+	Tuesday,
+	
+	/// Wednesday.
+	Wednesday,
+	
+	/// Thursday.
+	Thursday,
+	
+	/// Friday.
+	Friday,
+	
+	/// Saturday.
+	Saturday,
+	
+	/// Sunday.
+	Sunday,
+}
+
+//󰭅		Weekday																	
+impl Weekday {
+	//		Constructors														
+	
+	//		from_day_number														
+	/// Gets the day of the week for a number of days since the Unix epoch.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `day` - The number of days since 1st January 1970, which was a
+	///           Thursday.
+	/// 
+	const fn from_day_number(day: i64) -> Self {
+		match day.rem_euclid(7) {
+			0 => Self::Thursday,
+			1 => Self::Friday,
+			2 => Self::Saturday,
+			3 => Self::Sunday,
+			4 => Self::Monday,
+			5 => Self::Tuesday,
+			_ => Self::Wednesday,
+		}
+	}
+}
+
+//		Display																	
+impl Display for Weekday {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", match *self {
+			Self::Monday    => "Monday",
+			Self::Tuesday   => "Tuesday",
+			Self::Wednesday => "Wednesday",
+			Self::Thursday  => "Thursday",
+			Self::Friday    => "Friday",
+			Self::Saturday  => "Saturday",
+			Self::Sunday    => "Sunday",
+		})
+	}
+}
+
+
+
+//		Structs																											
+
+//		MaintenanceSchedule														
+/// When the updater is allowed to install updates and restart the application.
+/// 
+/// The schedule is made up of one or more [`MaintenanceWindow`]s. The steps
+/// covered by the [`MaintenanceScope`] will only be carried out by the updater
+/// while one of the windows is open. If no windows are specified, they will
+/// never be carried out automatically, and it is left to the application to
+/// do so.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MaintenanceSchedule {
+	//		Public properties													
+	/// The windows during which the held-back steps are allowed.
+	pub windows:    Vec<MaintenanceWindow>,
+	
+	/// The offset from UTC, in seconds, of the timezone that the windows are
+	/// specified in. For example, `3600` for UTC+1, or `-18000` for UTC-5.
+	pub utc_offset: i32,
+	
+	/// The steps of the update process that are held back until a window
+	/// opens.
+	pub scope:      MaintenanceScope,
+}
+
+//󰭅		MaintenanceSchedule														
+impl MaintenanceSchedule {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new maintenance schedule.
+	/// 
+	/// Both installing and restarting are held back. This can be changed by
+	/// setting the [`scope`](Self::scope) afterwards.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `windows`    - The windows during which updates are allowed.
+	/// * `utc_offset` - The offset from UTC, in seconds, of the timezone that
+	///                  the windows are specified in.
+	/// 
+	#[must_use]
+	pub fn new(windows: Vec<MaintenanceWindow>, utc_offset: i32) -> Self {
+		Self {
+			windows,
+			utc_offset,
+			scope: MaintenanceScope::default(),
+		}
+	}
+	
+	//		Public methods														
+	
+	//		is_open																
+	/// Checks whether a maintenance window is open at the specified time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at` - The time to check.
+	/// 
+	#[must_use]
+	pub fn is_open(&self, at: SystemTime) -> bool {
+		let local = self.local_seconds(at);
+		let today = local.div_euclid(SECONDS_PER_DAY);
+		self.windows.iter().any(|window| {
+			//	A window that started yesterday may still be open
+			[today.saturating_sub(1), today].into_iter().any(|day| {
+				window.occurrence(day).is_some_and(|(start, end)| start <= local && local < end)
+			})
+		})
+	}
+	
+	//		next_opening														
+	/// Gets the time at which the next maintenance window opens.
+	/// 
+	/// This is the first time after the specified time at which a window
+	/// opens, regardless of whether a window is open at the specified time. If
+	/// no window will ever open, [`None`] is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at` - The time to look from.
+	/// 
+	#[must_use]
+	pub fn next_opening(&self, at: SystemTime) -> Option<SystemTime> {
+		let local = self.local_seconds(at);
+		let today = local.div_euclid(SECONDS_PER_DAY);
+		let start = self.windows.iter()
+			.filter_map(|window| {
+				(0_i64..=7)
+					.filter_map(|offset| window.occurrence(today.saturating_add(offset)))
+					.map(|(start, _)| start)
+					.find(|&start| start > local)
+			})
+			.min()?
+		;
+		let utc   = u64::try_from(start.saturating_sub(i64::from(self.utc_offset))).ok()?;
+		UNIX_EPOCH.checked_add(Duration::from_secs(utc))
+	}
+	
+	//		time_until_open														
+	/// Gets how long it is until a maintenance window is open.
+	/// 
+	/// If a window is open at the specified time, a zero duration is returned.
+	/// If no window will ever open, [`None`] is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at` - The time to look from.
+	/// 
+	#[must_use]
+	pub fn time_until_open(&self, at: SystemTime) -> Option<Duration> {
+		if self.is_open(at) {
+			return Some(Duration::ZERO);
+		}
+		self.next_opening(at).map(|opening| opening.duration_since(at).unwrap_or_default())
+	}
+	
+	//		Private methods														
+	
+	//		local_seconds														
+	/// Converts a time to seconds since the Unix epoch in local time.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at` - The time to convert.
+	/// 
+	fn local_seconds(&self, at: SystemTime) -> i64 {
+		let utc = at.duration_since(UNIX_EPOCH).map_or(0, |since| i64::try_from(since.as_secs()).unwrap_or(i64::MAX));
+		utc.saturating_add(i64::from(self.utc_offset))
+	}
+}
+
+//		MaintenanceWindow														
+/// A range of times during which the updater is allowed to proceed.
+/// 
+/// The window can apply every day, or only on particular days of the week. If
+/// the end time is at or before the start time, the window runs past midnight
+/// and finishes on the following day — in which case the days specified are
+/// the days on which the window opens. A window that ends at the same time as
+/// it starts lasts for a whole day.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct MaintenanceWindow {
+	//		Public properties													
+	/// The days of the week on which the window opens.
+	pub days:  Vec<Weekday>,
+	
+	/// The time of day at which the window opens.
+	pub start: TimeOfDay,
+	
+	/// The time of day at which the window closes.
+	pub end:   TimeOfDay,
+}
+
+//󰭅		MaintenanceWindow														
+impl MaintenanceWindow {
+	//		Constructors														
+	
+	//		daily																
+	/// Creates a window that opens every day.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `start` - The time of day at which the window opens.
+	/// * `end`   - The time of day at which the window closes.
+	/// 
+	#[must_use]
+	pub fn daily(start: TimeOfDay, end: TimeOfDay) -> Self {
+		Self::weekly(&[
+			Weekday::Monday,
+			Weekday::Tuesday,
+			Weekday::Wednesday,
+			Weekday::Thursday,
+			Weekday::Friday,
+			Weekday::Saturday,
+			Weekday::Sunday,
+		], start, end)
+	}
+	
+	//		weekly																
+	/// Creates a window that opens on particular days of the week.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `days`  - The days of the week on which the window opens.
+	/// * `start` - The time of day at which the window opens.
+	/// * `end`   - The time of day at which the window closes.
+	/// 
+	#[must_use]
+	pub fn weekly(days: &[Weekday], start: TimeOfDay, end: TimeOfDay) -> Self {
+		Self {
+			days: days.to_vec(),
+			start,
+			end,
+		}
+	}
+	
+	//		Private methods														
+	
+	//		occurrence															
+	/// Gets the occurrence of the window that opens on a particular day.
+	/// 
+	/// The start and end times are returned in local seconds since the Unix
+	/// epoch, or [`None`] if the window does not open on that day.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `day` - The number of days since the Unix epoch, in local time.
+	/// 
+	fn occurrence(&self, day: i64) -> Option<(i64, i64)> {
+		if !self.days.contains(&Weekday::from_day_number(day)) {
+			return None;
+		}
+		let midnight = day.saturating_mul(SECONDS_PER_DAY);
+		let start    = midnight.saturating_add(i64::from(self.start.seconds));
+		let mut end  = midnight.saturating_add(i64::from(self.end.seconds));
+		if end <= start {
+			end = end.saturating_add(SECONDS_PER_DAY);
+		}
+		Some((start, end))
+	}
+}
+
+//		TimeOfDay																
+/// A time of day, to the minute.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+#[non_exhaustive]
+pub struct TimeOfDay {
+	//		Private properties													
+	/// The number of seconds since midnight.
+	seconds: u32,
+}
+
+//󰭅		TimeOfDay																
+impl TimeOfDay {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new time of day.
+	/// 
+	/// [`None`] is returned if the hour or minute is out of range.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `hour`   - The hour, from `0` to `23`.
+	/// * `minute` - The minute, from `0` to `59`.
+	/// 
+	#[must_use]
+	pub fn new(hour: u8, minute: u8) -> Option<Self> {
+		if hour > 23 || minute > 59 {
+			return None;
+		}
+		Some(Self { seconds: u32::from(hour).saturating_mul(60).saturating_add(u32::from(minute)).saturating_mul(60) })
+	}
+	
+	//		Public methods														
+	
+	//		hour																
+	/// Gets the hour.
+	#[must_use]
+	pub const fn hour(&self) -> u32 {
+		self.seconds.div_euclid(3_600)
+	}
+	
+	//		minute																
+	/// Gets the minute.
+	#[must_use]
+	pub const fn minute(&self) -> u32 {
+		self.seconds.div_euclid(60).rem_euclid(60)
+	}
+}
+
+//		Display																	
+impl Display for TimeOfDay {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{:02}:{:02}", self.hour(), self.minute())
+	}
+}
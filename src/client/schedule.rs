@@ -0,0 +1,324 @@
+//! Schedules for checking for updates.
+//! 
+//! As well as checking at a fixed interval, the updater can check at
+//! particular times, specified using a [`CheckSchedule`]. This is useful for
+//! spreading the load on the server across a fleet of devices, in conjunction
+//! with the random jitter that can be configured in the
+//! [`Config`](super::Config), and for making sure that checks happen at quiet
+//! times.
+//! 
+//! Schedules are parsed from strings, which can be either standard five-field
+//! cron expressions, a shorthand such as `@hourly` or `@daily`, or a simple
+//! `daily at HH:MM` form. The times are interpreted using a fixed offset from
+//! UTC, which defaults to UTC itself.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/schedule.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use core::{
+	fmt::{Display, self},
+	str::FromStr,
+	time::Duration,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error as ThisError;
+
+
+
+//		Constants																										
+
+/// The number of minutes in a day.
+const MINUTES_PER_DAY: i64 = 1_440;
+
+/// How many days ahead to search for the next matching time. This is long
+/// enough to find the next 29th of February.
+const SEARCH_DAYS: i64 = 366 * 8;
+
+
+
+//		Enums																											
+
+//		ScheduleError															
+/// Errors that can occur when parsing a schedule.
+#[derive(Clone, Debug, Eq, Hash, PartialEq, ThisError)]
+#[non_exhaustive]
+pub enum ScheduleError {
+	/// The expression does not have the five fields expected of a cron
+	/// expression, and is not one of the other recognised forms.
+	#[error(r#"Invalid schedule "{0}": expected five fields, or "daily at HH:MM""#)]
+	InvalidFieldCount(String),
+	
+	/// One of the fields of a cron expression is not valid. The name of the
+	/// field and its value are included.
+	#[error(r#"Invalid {0} field "{1}" in schedule"#)]
+	InvalidField(String, String),
+	
+	/// The time given in the `daily at HH:MM` form is not valid.
+	#[error(r#"Invalid time "{0}" in schedule, expected HH:MM"#)]
+	InvalidTime(String),
+}
+
+
+
+//		Structs																											
+
+//		CheckSchedule															
+/// A schedule of times at which to check for updates.
+/// 
+/// This is created by parsing a string, which can take any of the following
+/// forms:
+/// 
+///   - A standard five-field cron expression, made up of the minute, hour,
+///     day of the month, month, and day of the week, in that order. Each field
+///     can be `*`, a number, a range such as `1-5`, a step such as `*/15` or
+///     `0-30/10`, or a comma-separated list of these. Days of the week are
+///     numbered from `0` for Sunday, with `7` also meaning Sunday. As with
+///     cron, if both the day of the month and the day of the week are
+///     restricted, a day matching either of them is used.
+///   - One of the shorthands `@hourly`, `@daily` (or `@midnight`), `@weekly`,
+///     or `@monthly`.
+///   - `daily at HH:MM`, for a check once a day at the specified time.
+/// 
+/// The times are interpreted using the [`utc_offset`](Self::utc_offset),
+/// which is zero by default.
+/// 
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[non_exhaustive]
+pub struct CheckSchedule {
+	//		Public properties													
+	/// The offset from UTC, in seconds, of the timezone that the schedule is
+	/// specified in. For example, `3600` for UTC+1, or `-18000` for UTC-5.
+	pub utc_offset: i32,
+	
+	//		Private properties													
+	/// The expression that the schedule was parsed from.
+	expression:     String,
+	
+	/// The minutes of the hour to match, as a bitmask.
+	minutes:        u64,
+	
+	/// The hours of the day to match, as a bitmask.
+	hours:          u64,
+	
+	/// The days of the month to match, as a bitmask.
+	days:           u64,
+	
+	/// The months of the year to match, as a bitmask.
+	months:         u64,
+	
+	/// The days of the week to match, as a bitmask, starting from Sunday.
+	weekdays:       u64,
+	
+	/// Whether the days of the month are restricted, i.e. not `*`.
+	days_set:       bool,
+	
+	/// Whether the days of the week are restricted, i.e. not `*`.
+	weekdays_set:   bool,
+}
+
+//󰭅		CheckSchedule															
+impl CheckSchedule {
+	//		Public methods														
+	
+	//		next_after															
+	/// Gets the next time in the schedule.
+	/// 
+	/// This is the first whole minute matching the schedule that is after the
+	/// specified time. If there is no such time, for instance because the
+	/// schedule asks for the 31st of February, [`None`] is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `at` - The time to look from.
+	/// 
+	#[must_use]
+	pub fn next_after(&self, at: SystemTime) -> Option<SystemTime> {
+		let offset  = i64::from(self.utc_offset);
+		let utc     = at.duration_since(UNIX_EPOCH).map_or(0, |since| i64::try_from(since.as_secs()).unwrap_or(i64::MAX));
+		let first   = utc.saturating_add(offset).div_euclid(60).saturating_add(1);
+		let today   = first.div_euclid(MINUTES_PER_DAY);
+		let next    = (0..SEARCH_DAYS).map(|ahead| today.saturating_add(ahead)).find_map(|day| {
+			if !self.matches_day(day) {
+				return None;
+			}
+			let midnight = day.saturating_mul(MINUTES_PER_DAY);
+			let from     = if day == today { first.rem_euclid(MINUTES_PER_DAY) } else { 0 };
+			(from..MINUTES_PER_DAY)
+				.find(|&minute| has(self.hours, minute.div_euclid(60)) && has(self.minutes, minute.rem_euclid(60)))
+				.map(|minute| midnight.saturating_add(minute))
+		})?;
+		let seconds = u64::try_from(next.saturating_mul(60).saturating_sub(offset)).ok()?;
+		UNIX_EPOCH.checked_add(Duration::from_secs(seconds))
+	}
+	
+	//		Private methods														
+	
+	//		matches_day															
+	/// Checks whether a day matches the schedule.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `day` - The number of days since the Unix epoch, in local time.
+	/// 
+	fn matches_day(&self, day: i64) -> bool {
+		let (month, day_of_month) = civil_date(day);
+		if !has(self.months, month) {
+			return false;
+		}
+		//	1st January 1970 was a Thursday
+		let by_date    = has(self.days,     day_of_month);
+		let by_weekday = has(self.weekdays, day.saturating_add(4).rem_euclid(7));
+		match (self.days_set, self.weekdays_set) {
+			(true,  true)  => by_date || by_weekday,
+			(true,  false) => by_date,
+			(false, true)  => by_weekday,
+			(false, false) => true,
+		}
+	}
+}
+
+//		Display																	
+impl Display for CheckSchedule {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		write!(f, "{}", self.expression)
+	}
+}
+
+//		FromStr																	
+impl FromStr for CheckSchedule {
+	type Err = ScheduleError;
+	
+	//		from_str															
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let expression = s.trim();
+		let fields     = match expression.to_lowercase().as_str() {
+			"@hourly"                   => "0 * * * *".to_owned(),
+			"@daily" | "@midnight"      => "0 0 * * *".to_owned(),
+			"@weekly"                   => "0 0 * * 0".to_owned(),
+			"@monthly"                  => "0 0 1 * *".to_owned(),
+			lower                       => {
+				if let Some(time) = lower.strip_prefix("daily at ") {
+					let (hour, minute) = time.trim().split_once(':')
+						.and_then(|(hour, minute)| Some((hour.parse::<u8>().ok()?, minute.parse::<u8>().ok()?)))
+						.filter(|&(hour, minute)| hour < 24 && minute < 60)
+						.ok_or_else(|| ScheduleError::InvalidTime(time.trim().to_owned()))?
+					;
+					format!("{minute} {hour} * * *")
+				} else {
+					lower.to_owned()
+				}
+			},
+		};
+		let parts: Vec<&str> = fields.split_whitespace().collect();
+		let &[minutes, hours, days, months, weekdays] = parts.as_slice() else {
+			return Err(ScheduleError::InvalidFieldCount(expression.to_owned()));
+		};
+		//	Sunday can be given as either 0 or 7
+		let weekday_mask = parse_field("day of week", weekdays, 0, 7)?;
+		Ok(Self {
+			utc_offset:   0,
+			expression:   expression.to_owned(),
+			minutes:      parse_field("minute",       minutes,  0, 59)?,
+			hours:        parse_field("hour",         hours,    0, 23)?,
+			days:         parse_field("day of month", days,     1, 31)?,
+			months:       parse_field("month",        months,   1, 12)?,
+			weekdays:     (weekday_mask | (weekday_mask >> 7_u32)) & 0x7F,
+			days_set:     !days.starts_with('*'),
+			weekdays_set: !weekdays.starts_with('*'),
+		})
+	}
+}
+
+
+
+//		Functions																										
+
+//		civil_date																
+/// Works out the month and day of the month for a number of days since the
+/// Unix epoch.
+/// 
+/// This uses the algorithm described by Howard Hinnant, for the proleptic
+/// Gregorian calendar.
+/// 
+/// # Parameters
+/// 
+/// * `day` - The number of days since 1st January 1970.
+/// 
+#[expect(clippy::arithmetic_side_effects, reason = "The values are always in range")]
+#[expect(clippy::integer_division,        reason = "Rounding down is intended")]
+const fn civil_date(day: i64) -> (i64, i64) {
+	let shifted      = day + 719_468;
+	let day_of_era   = shifted.rem_euclid(146_097);
+	let year_of_era  = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+	let day_of_year  = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+	let month_index  = (5 * day_of_year + 2) / 153;
+	let day_of_month = day_of_year - (153 * month_index + 2) / 5 + 1;
+	let month        = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+	(month, day_of_month)
+}
+
+//		has																		
+/// Checks whether a bitmask includes a value.
+/// 
+/// # Parameters
+/// 
+/// * `mask`  - The bitmask to check.
+/// * `value` - The value to look for.
+/// 
+fn has(mask: u64, value: i64) -> bool {
+	u32::try_from(value).ok()
+		.and_then(|bit| 1_u64.checked_shl(bit))
+		.is_some_and(|bit| mask & bit != 0)
+}
+
+//		parse_field																
+/// Parses a field of a cron expression into a bitmask.
+/// 
+/// # Parameters
+/// 
+/// * `name`  - The name of the field, for error reporting.
+/// * `field` - The value of the field.
+/// * `min`   - The lowest value allowed.
+/// * `max`   - The highest value allowed.
+/// 
+/// # Errors
+/// 
+/// * [`ScheduleError::InvalidField`]
+/// 
+fn parse_field(name: &str, field: &str, min: u32, max: u32) -> Result<u64, ScheduleError> {
+	let invalid  = || ScheduleError::InvalidField(name.to_owned(), field.to_owned());
+	let mut mask = 0_u64;
+	for part in field.split(',') {
+		let (range, step) = match part.split_once('/') {
+			Some((range, every)) => (range, every.parse::<usize>().ok().filter(|&step| step > 0).ok_or_else(invalid)?),
+			None                 => (part, 1),
+		};
+		let (start, end)  = if range == "*" {
+			(min, max)
+		} else if let Some((start, end)) = range.split_once('-') {
+			(start.parse::<u32>().map_err(|_err| invalid())?, end.parse::<u32>().map_err(|_err| invalid())?)
+		} else {
+			//	A single value with a step means from that value to the end
+			let value = range.parse::<u32>().map_err(|_err| invalid())?;
+			(value, if part.contains('/') { max } else { value })
+		};
+		if start < min || end > max || start > end {
+			return Err(invalid());
+		}
+		for value in (start..=end).step_by(step) {
+			mask |= 1_u64.checked_shl(value).ok_or_else(invalid)?;
+		}
+	}
+	Ok(mask)
+}
@@ -0,0 +1,977 @@
+//! Sources that the updater obtains updates from.
+//! 
+//! The [`Updater`](super::Updater) obtains the metadata for the latest version,
+//! the metadata for a specific version, and the release files, from an
+//! [`UpdateSource`]. By default, this is an [`HttpSource`], which talks to the
+//! API server given by the [`api`](super::Config::api) and
+//! [`mirrors`](super::Config::mirrors) settings. A different source can be set
+//! using the [`source`](super::Config::source) setting in the
+//! [`Config`](super::Config).
+//! 
+//! A [`DirectorySource`] is also provided, which reads the same files from a
+//! local directory, such as a network share or a mounted volume.
+//! 
+//! # Verification
+//! 
+//! Sources do not need to be trusted. The metadata is returned along with its
+//! signature, and the updater verifies it against the public key, and then
+//! checks each release file against the signed hash. A source is therefore
+//! only responsible for obtaining the data, and not for checking it.
+//! 
+//! # Directory layout
+//! 
+//! A [`DirectorySource`] expects the same paths as the API endpoints, relative
+//! to its directory, with the signature for each item of metadata held in a
+//! file alongside it, with a `.sig` extension:
+//! 
+//!   - `latest` — The latest version, as returned by the `latest` endpoint.
+//!   - `latest.sig` — The signature of the `latest` file, in hexadecimal.
+//!   - `hashes/{version}` — The hash of the release file for each version, as
+//!     returned by the `hashes/{version}` endpoint.
+//!   - `hashes/{version}.sig` — The signature of each hash file.
+//!   - `releases/{version}` — The release file for each version.
+//! 
+//! This is the layout written by [`Core::export()`](crate::server::Core::export()),
+//! so an exported directory can be used as it is. It can also be hosted by a
+//! static web server and used with an [`HttpSource`], which reads the `.sig`
+//! files when there is no signature header in the response.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/source.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use super::{HttpSettings, TokenSource, UpdaterError};
+use bytes::Bytes;
+use core::{
+	error::Error,
+	fmt::{Debug, self},
+	iter::once,
+	str::FromStr,
+};
+use futures_util::{
+	StreamExt as _,
+	future::BoxFuture,
+	stream::BoxStream,
+};
+use parking_lot::Mutex;
+use reqwest::{
+	StatusCode,
+	Url,
+	header::{AUTHORIZATION, AsHeaderName, CONTENT_LENGTH, CONTENT_TYPE, HeaderMap, HeaderValue},
+};
+use rubedo::sugar::s;
+use semver::Version;
+use std::{
+	io::{Error as IoError, ErrorKind as IoErrorKind},
+	path::PathBuf,
+};
+use tokio::{
+	fs::{File as AsyncFile, self},
+	time::{Duration, Instant},
+};
+use tokio_util::io::ReaderStream;
+use tracing::{info, warn};
+
+#[cfg(not(test))]
+use reqwest::{Client, Response};
+#[cfg(test)]
+use sham::reqwest::{MockClient as Client, MockResponse as Response};
+
+
+
+//		Structs																											
+
+//		SignedPayload															
+/// Metadata obtained from an update source, along with its signature.
+#[expect(clippy::exhaustive_structs, reason = "Provided for implementations")]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SignedPayload {
+	//		Public properties													
+	/// Where the metadata was obtained from. This is used to identify the
+	/// source of any problems with the metadata.
+	pub url:       Url,
+	
+	/// The metadata, as JSON. This is the same payload as returned by the
+	/// corresponding API endpoint.
+	pub body:      String,
+	
+	/// The signature of the metadata, in hexadecimal. This should be empty if
+	/// the source does not have a signature for the metadata.
+	pub signature: String,
+}
+
+//		ReleaseStream															
+/// A release file obtained from an update source.
+#[expect(clippy::exhaustive_structs, reason = "Provided for implementations")]
+pub struct ReleaseStream {
+	//		Public properties													
+	/// Where the release file was obtained from. This is used to identify the
+	/// source of any problems with the release file.
+	pub url:    Url,
+	
+	/// The expected length of the release file, in bytes, if known. The length
+	/// is not always given, for instance when a release is served in chunks by
+	/// external storage, in which case the signed hash is relied upon instead.
+	pub length: Option<usize>,
+	
+	/// The contents of the release file, in chunks. An error ends the stream.
+	pub stream: BoxStream<'static, Result<Bytes, String>>,
+}
+
+//		Debug																	
+impl Debug for ReleaseStream {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("ReleaseStream")
+			.field("url",    &self.url)
+			.field("length", &self.length)
+			.finish_non_exhaustive()
+	}
+}
+
+//		EventStream																
+/// A stream of Server-Sent Events obtained from an update source.
+#[expect(clippy::exhaustive_structs, reason = "Provided for implementations")]
+pub struct EventStream {
+	//		Public properties													
+	/// Where the events are being received from.
+	pub url:    Url,
+	
+	/// The raw event data, in chunks, in the Server-Sent Events format. Chunks
+	/// do not need to line up with the events. An error ends the stream.
+	pub stream: BoxStream<'static, Result<Bytes, String>>,
+}
+
+//		Debug																	
+impl Debug for EventStream {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		f.debug_struct("EventStream")
+			.field("url", &self.url)
+			.finish_non_exhaustive()
+	}
+}
+
+//		HttpSource																
+/// An update source that uses the API server.
+/// 
+/// This sends HTTP requests to the API server, failing over to the mirrors if
+/// necessary. This is the source that is used by default, using the
+/// [`api`](super::Config::api) and [`mirrors`](super::Config::mirrors)
+/// settings.
+/// 
+/// If the API server in use cannot be reached, or responds with a server
+/// error, each of the other API URLs is tried in turn, and the first one to
+/// work is used for subsequent requests. While a mirror is in use, the primary
+/// API URL is tried again periodically, so that the source returns to it once
+/// it recovers.
+/// 
+/// If the API server requires authentication, a [`TokenSource`] can be given
+/// using [`with_token()`](Self::with_token()), and the token is then sent as a
+/// bearer token with each request to the API server or its mirrors. It is not
+/// sent to any other host, such as one given in a download URL.
+/// 
+#[derive(Debug)]
+pub struct HttpSource {
+	//		Private properties													
+	/// The primary URL of the API server.
+	api:           Url,
+	
+	/// The HTTP client instance that is used for communicating with the API
+	/// server.
+	client:        Client,
+	
+	/// The index of the API URL currently in use, where `0` is the primary
+	/// [`api`](Self::api) URL and the rest are the [`mirrors`](Self::mirrors),
+	/// along with when the primary last failed.
+	mirror:        Mutex<(usize, Option<Instant>)>,
+	
+	/// Additional URLs of the API to fall back to, in order of preference.
+	mirrors:       Vec<Url>,
+	
+	/// How long to keep using a mirror before trying the primary API URL again.
+	primary_retry: Duration,
+	
+	/// Where to obtain the API token to send with each request, if one is
+	/// required.
+	token:         Option<TokenSource>,
+}
+
+//󰭅		HttpSource																
+impl HttpSource {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new HTTP update source.
+	/// 
+	/// The HTTP client is set up using the default [`HttpSettings`], and so
+	/// redirects are followed in the same safe way as for any other settings.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `api`           - The primary URL of the API server.
+	/// * `mirrors`       - Additional URLs of the API to fall back to, in order
+	///                     of preference.
+	/// * `primary_retry` - How long to keep using a mirror before trying the
+	///                     primary API URL again.
+	/// 
+	#[must_use]
+	pub fn new(api: Url, mirrors: Vec<Url>, primary_retry: Duration) -> Self {
+		//	The default settings are always valid
+		let client = HttpSettings::default().build_client().unwrap_or_else(|_err| Client::new());
+		Self::with_client(client, api, mirrors, primary_retry)
+	}
+	
+	//		with_client															
+	/// Creates a new HTTP update source that uses the given HTTP client.
+	/// 
+	/// This allows a client that has already been configured to be used for
+	/// communicating with the API server.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `client`        - The HTTP client to use.
+	/// * `api`           - The primary URL of the API server.
+	/// * `mirrors`       - Additional URLs of the API to fall back to, in order
+	///                     of preference.
+	/// * `primary_retry` - How long to keep using a mirror before trying the
+	///                     primary API URL again.
+	/// 
+	#[must_use]
+	pub const fn with_client(client: Client, api: Url, mirrors: Vec<Url>, primary_retry: Duration) -> Self {
+		Self {
+			api,
+			client,
+			mirror: Mutex::new((0, None)),
+			mirrors,
+			primary_retry,
+			token: None,
+		}
+	}
+	
+	//		Public methods														
+	
+	//		with_token															
+	/// Sets where to obtain the API token to send with each request.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `token` - Where to obtain the API token.
+	/// 
+	#[must_use]
+	pub fn with_token(mut self, token: TokenSource) -> Self {
+		self.token = Some(token);
+		self
+	}
+	
+	//		Private methods														
+	
+	//		get_signed															
+	/// Gets signed metadata from the API server.
+	/// 
+	/// This function requests an endpoint that returns a JSON payload, checks
+	/// the response, and returns the payload along with the signature from the
+	/// response headers.
+	/// 
+	/// If the response has no signature header, as is the case when the files
+	/// are hosted by a static web server, the signature is fetched from the
+	/// detached signature file alongside the payload instead, using
+	/// [`get_detached_signature()`](Self::get_detached_signature()). The
+	/// content type is not checked in that case, as static web servers cannot
+	/// always be told that a file with no extension contains JSON. This does
+	/// not weaken anything, as the payload still has to pass signature
+	/// verification.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `endpoint` - The endpoint to request, relative to the API URL.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidBody`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::MissingData`]
+	/// * [`UpdaterError::TooMuchData`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// * [`UpdaterError::UnexpectedContentType`]
+	/// 
+	async fn get_signed(&self, endpoint: &str) -> Result<SignedPayload, UpdaterError> {
+		let (url, response) = self.failover_request(endpoint).await?;
+		//		Get headers														
+		let content_type:     String = get_header(&response, CONTENT_TYPE);
+		let content_length           = get_content_length(&response);
+		let header_signature: String = get_header(&response, "x-signature");
+		//		Get body														
+		let Ok(body) = response.text().await else {
+			return Err(UpdaterError::InvalidBody(url))
+		};
+		//		Check headers													
+		let detached = header_signature.is_empty();
+		if !detached && content_type != "application/json" {
+			return Err(UpdaterError::UnexpectedContentType(url, content_type, s!("application/json")));
+		}
+		//	The length is not always given, for instance by static hosts serving
+		//	compressed responses, in which case the signature is relied upon
+		if let Some(expected_len) = content_length {
+			if body.len() < expected_len {
+				return Err(UpdaterError::MissingData(url, body.len(), expected_len));
+			}
+			if body.len() > expected_len {
+				return Err(UpdaterError::TooMuchData(url, body.len(), expected_len));
+			}
+		}
+		let signature = if detached { self.get_detached_signature(&url).await? } else { header_signature };
+		Ok(SignedPayload { url, body, signature })
+	}
+	
+	//		get_detached_signature												
+	/// Gets the detached signature for a payload.
+	/// 
+	/// The signature is fetched from the same URL as the payload, with `.sig`
+	/// added to the end, as written by [`Core::export()`](crate::server::Core::export()).
+	/// If there is no signature file, the signature is left empty, and so the
+	/// payload will fail verification as being unsigned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL of the payload.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidBody`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// 
+	async fn get_detached_signature(&self, url: &Url) -> Result<String, UpdaterError> {
+		let mut sig_url = url.clone();
+		sig_url.set_path(&format!("{}.sig", url.path()));
+		let response = self.get(&sig_url).await?;
+		let status   = response.status();
+		if status == StatusCode::NOT_FOUND {
+			return Ok(String::new());
+		}
+		if !status.is_success() {
+			return Err(UpdaterError::HttpError(sig_url, status));
+		}
+		let Ok(signature) = response.text().await else {
+			return Err(UpdaterError::InvalidBody(sig_url))
+		};
+		Ok(signature.trim().to_owned())
+	}
+	
+	//		get_release															
+	/// Gets a release file from the API server.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the release file.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// * [`UpdaterError::UnexpectedContentType`]
+	/// 
+	async fn get_release(&self, version: &Version) -> Result<ReleaseStream, UpdaterError> {
+		let (_url, response) = self.failover_request(&format!("releases/{version}")).await?;
+		Self::release_stream(response)
+	}
+	
+	//		get_release_from													
+	/// Gets a release file from a download URL.
+	/// 
+	/// The URL is requested as it is, without failing over to the mirrors, as
+	/// it has been given by the API server and may be signed.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL to download the release file from.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// * [`UpdaterError::UnexpectedContentType`]
+	/// 
+	async fn get_release_from(&self, url: &Url) -> Result<ReleaseStream, UpdaterError> {
+		let response = self.get(url).await?;
+		let status   = response.status();
+		if !status.is_success() {
+			return Err(UpdaterError::HttpError(url.clone(), status));
+		}
+		Self::release_stream(response)
+	}
+	
+	//		release_stream														
+	/// Checks a release file response, and turns it into a stream.
+	/// 
+	/// If any redirects were followed, the URL of the release file is the one
+	/// it was finally obtained from, so that any problems with it are reported
+	/// against the right host.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `response` - The response containing the release file.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::UnexpectedContentType`]
+	/// 
+	#[cfg_attr(test, expect(clippy::needless_pass_by_value, reason = "Not consumed by the mock"))]
+	#[expect(clippy::result_large_err, reason = "Doesn't matter here")]
+	fn release_stream(response: Response) -> Result<ReleaseStream, UpdaterError> {
+		let url                    = response.url().clone();
+		let content_type:   String = get_header(&response, CONTENT_TYPE);
+		let content_length         = get_content_length(&response);
+		if content_type != "application/octet-stream" {
+			return Err(UpdaterError::UnexpectedContentType(url, content_type, s!("application/octet-stream")));
+		}
+		let stream = response.bytes_stream().map(|chunk| chunk.map_err(|err| err.to_string())).boxed();
+		Ok(ReleaseStream { url, length: content_length, stream })
+	}
+	
+	//		get_events															
+	/// Connects to the API server's events endpoint.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// * [`UpdaterError::UnexpectedContentType`]
+	/// 
+	async fn get_events(&self) -> Result<Option<EventStream>, UpdaterError> {
+		let (url, response)      = self.failover_request("events").await?;
+		let content_type: String = get_header(&response, CONTENT_TYPE);
+		if content_type != "text/event-stream" {
+			return Err(UpdaterError::UnexpectedContentType(url, content_type, s!("text/event-stream")));
+		}
+		let stream = response.bytes_stream().map(|chunk| chunk.map_err(|err| err.to_string())).boxed();
+		Ok(Some(EventStream { url, stream }))
+	}
+	
+	//		failover_request													
+	/// Sends an HTTP request, failing over to mirrors if necessary.
+	/// 
+	/// This function sends the request to the API URL currently in use. If the
+	/// server cannot be reached, or responds with a server error, each of the
+	/// other API URLs is tried in turn, and the first one to work is used for
+	/// subsequent requests. If a mirror is in use and the primary API URL has
+	/// not been tried for the [`primary_retry`](Self::primary_retry) interval,
+	/// the primary is tried first. There are no retries beyond trying each API
+	/// URL once; these are handled by the updater.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `endpoint` - The endpoint to request, relative to the API URL.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// 
+	async fn failover_request(&self, endpoint: &str) -> Result<(Url, Response), UpdaterError> {
+		let count                = self.mirrors.len().saturating_add(1);
+		let (current, failed_at) = *self.mirror.lock();
+		let primary_due          = failed_at.is_none_or(|at| at.elapsed() >= self.primary_retry);
+		let start                = if primary_due { 0 } else { current };
+		let mut primary_failed   = failed_at;
+		let mut index            = start;
+		loop {
+			let base   = self.api_url(index);
+			let result = self.send_request(base, endpoint).await;
+			let next   = index.saturating_add(1).rem_euclid(count);
+			if let Err(ref err) = result {
+				if !err.warrants_failover() {
+					return result;
+				}
+				if index == 0 {
+					primary_failed = Some(Instant::now());
+				}
+				if next != start {
+					warn!("Unable to use {base}, trying the next mirror: {err}");
+					index = next;
+					continue;
+				}
+				self.mirror.lock().1 = primary_failed;
+				return result;
+			}
+			if index != current {
+				info!("Switched to {base} for update requests");
+			}
+			*self.mirror.lock() = (index, primary_failed.filter(|_| index != 0));
+			return result;
+		}
+	}
+	
+	//		api_url																
+	/// Gets an API URL by index.
+	/// 
+	/// Index `0` is the primary [`api`](Self::api) URL, and the rest are the
+	/// [`mirrors`](Self::mirrors), in order. If the index is out of range, the
+	/// primary is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `index` - The index of the API URL.
+	/// 
+	fn api_url(&self, index: usize) -> &Url {
+		once(&self.api).chain(&self.mirrors).nth(index).unwrap_or(&self.api)
+	}
+	
+	//		send_request														
+	/// Sends a single HTTP request.
+	/// 
+	/// This function performs the actual request for
+	/// [`failover_request()`](Self::failover_request()), to the specified API
+	/// URL, without any retries.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `base`     - The API URL to send the request to.
+	/// * `endpoint` - The endpoint to request, relative to the API URL.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpError`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::InvalidUrl`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// 
+	async fn send_request(&self, base: &Url, endpoint: &str) -> Result<(Url, Response), UpdaterError> {
+		//		Perform request													
+		let Ok(url)  = base.join(endpoint) else {
+			return Err(UpdaterError::InvalidUrl(base.clone(), endpoint.to_owned()));
+		};
+		let response = self.get(&url).await?;
+		//		Check status													
+		let status = response.status();
+		if !status.is_success() {
+			return Err(UpdaterError::HttpError(url, status));
+		}
+		Ok((url, response))
+	}
+	
+	//		get																	
+	/// Sends an HTTP `GET` request, along with the API token if there is one.
+	/// 
+	/// The API token is only sent if the URL is under the API URL or one of the
+	/// mirrors, so that it is not given away to any other host. This is decided
+	/// by origin and path segments, as explained for [`is_under()`].
+	/// 
+	/// Responses with a `401 Unauthorized` or `403 Forbidden` status are turned
+	/// into errors, as they apply to every request. Other statuses are left for
+	/// the caller to check.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `url` - The URL to request.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::Forbidden`]
+	/// * [`UpdaterError::HttpRequestFailed`]
+	/// * [`UpdaterError::InvalidToken`]
+	/// * [`UpdaterError::Unauthorized`]
+	/// 
+	async fn get(&self, url: &Url) -> Result<Response, UpdaterError> {
+		let mut headers = HeaderMap::new();
+		let trusted     = once(&self.api).chain(&self.mirrors).any(|base| is_under(url, base));
+		if let Some(token) = self.token.as_ref().filter(|_| trusted) {
+			let mut value = HeaderValue::from_str(&format!("Bearer {}", token.token().await?)).map_err(|_err|
+				UpdaterError::InvalidToken(s!("The token contains invalid characters"))
+			)?;
+			value.set_sensitive(true);
+			let _previous = headers.insert(AUTHORIZATION, value);
+		}
+		let response = self.client.get(url.clone()).headers(headers).send().await.map_err(|err|
+			UpdaterError::HttpRequestFailed(url.clone(), describe_error(&err))
+		)?;
+		match response.status() {
+			StatusCode::UNAUTHORIZED => Err(UpdaterError::Unauthorized(url.clone())),
+			StatusCode::FORBIDDEN    => Err(UpdaterError::Forbidden(url.clone())),
+			_                        => Ok(response),
+		}
+	}
+}
+
+//		UpdateSource															
+impl UpdateSource for HttpSource {
+	//		latest																
+	fn latest(&self) -> BoxFuture<'_, Result<SignedPayload, UpdaterError>> {
+		Box::pin(self.get_signed("latest"))
+	}
+	
+	//		version_hash														
+	fn version_hash<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<SignedPayload, UpdaterError>> {
+		Box::pin(async move { self.get_signed(&format!("hashes/{version}")).await })
+	}
+	
+	//		release																
+	fn release<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<ReleaseStream, UpdaterError>> {
+		Box::pin(self.get_release(version))
+	}
+	
+	//		release_from														
+	fn release_from<'a>(&'a self, _version: &'a Version, url: &'a Url) -> BoxFuture<'a, Result<ReleaseStream, UpdaterError>> {
+		Box::pin(self.get_release_from(url))
+	}
+	
+	//		events																
+	fn events(&self) -> BoxFuture<'_, Result<Option<EventStream>, UpdaterError>> {
+		Box::pin(self.get_events())
+	}
+}
+
+//		DirectorySource															
+/// An update source that reads files from a local directory.
+/// 
+/// This reads the same metadata and release files as are served by the API
+/// server, from a directory, such as a network share or a mounted volume. See
+/// the [module documentation](self) for the layout of the directory.
+/// 
+/// A directory cannot announce new releases, and so push updates are not
+/// available when using this source.
+/// 
+#[derive(Clone, Debug)]
+pub struct DirectorySource {
+	//		Private properties													
+	/// The directory to read the files from.
+	path: PathBuf,
+}
+
+//󰭅		DirectorySource															
+impl DirectorySource {
+	//		Constructors														
+	
+	//		new																	
+	/// Creates a new directory update source.
+	/// 
+	/// The directory is not checked until it is used.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `path` - The directory to read the files from.
+	/// 
+	#[must_use]
+	pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+		Self { path: path.into() }
+	}
+	
+	//		Private methods														
+	
+	//		read_signed															
+	/// Reads signed metadata from the directory.
+	/// 
+	/// The signature is read from the file of the same name with a `.sig`
+	/// extension. If there is no signature file, the signature is left empty.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The path of the metadata file, relative to the directory.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::UnableToReadFile`]
+	/// 
+	async fn read_signed(&self, name: &str) -> Result<SignedPayload, UpdaterError> {
+		let (path, url)    = self.locate(name).await?;
+		let body           = fs::read_to_string(&path).await.map_err(|err|
+			UpdaterError::UnableToReadFile(path.clone(), err.to_string())
+		)?;
+		let signature_path = self.path.join(format!("{name}.sig"));
+		let signature      = match fs::read_to_string(&signature_path).await {
+			Ok(signature)                                    => signature.trim().to_owned(),
+			Err(err) if err.kind() == IoErrorKind::NotFound => String::new(),
+			Err(err)                                         => {
+				return Err(UpdaterError::UnableToReadFile(signature_path, err.to_string()));
+			},
+		};
+		Ok(SignedPayload { url, body, signature })
+	}
+	
+	//		open_release														
+	/// Opens a release file in the directory.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the release file.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::UnableToReadFile`]
+	/// 
+	async fn open_release(&self, version: &Version) -> Result<ReleaseStream, UpdaterError> {
+		let (path, url) = self.locate(&format!("releases/{version}")).await?;
+		let read_error  = |err: IoError| UpdaterError::UnableToReadFile(path.clone(), err.to_string());
+		let file        = AsyncFile::open(&path).await.map_err(read_error)?;
+		let metadata    = file.metadata().await.map_err(read_error)?;
+		let length      = usize::try_from(metadata.len()).ok();
+		let stream      = ReaderStream::new(file).map(|chunk| chunk.map_err(|err| err.to_string())).boxed();
+		Ok(ReleaseStream { url, length, stream })
+	}
+	
+	//		locate																
+	/// Gets the path and URL of a file in the directory.
+	/// 
+	/// The URL is a `file://` URL for the full path of the file, which is used
+	/// to refer to it in the same way as a response from the API server.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `name` - The path of the file, relative to the directory.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`UpdaterError::UnableToReadFile`]
+	/// 
+	async fn locate(&self, name: &str) -> Result<(PathBuf, Url), UpdaterError> {
+		let path      = self.path.join(name);
+		let full_path = fs::canonicalize(&path).await.map_err(|err|
+			UpdaterError::UnableToReadFile(path.clone(), err.to_string())
+		)?;
+		let Ok(url)   = Url::from_file_path(&full_path) else {
+			return Err(UpdaterError::UnableToReadFile(path, s!("Not a valid file path")));
+		};
+		Ok((full_path, url))
+	}
+}
+
+//		UpdateSource															
+impl UpdateSource for DirectorySource {
+	//		latest																
+	fn latest(&self) -> BoxFuture<'_, Result<SignedPayload, UpdaterError>> {
+		Box::pin(self.read_signed("latest"))
+	}
+	
+	//		version_hash														
+	fn version_hash<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<SignedPayload, UpdaterError>> {
+		Box::pin(async move { self.read_signed(&format!("hashes/{version}")).await })
+	}
+	
+	//		release																
+	fn release<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<ReleaseStream, UpdaterError>> {
+		Box::pin(self.open_release(version))
+	}
+}
+
+
+
+//		Traits																											
+
+//§		UpdateSource															
+/// A source of updates.
+/// 
+/// The updater uses an update source to obtain the metadata for the latest
+/// version, the metadata for a specific version, and the release files. The
+/// metadata is the same JSON payload as returned by the corresponding API
+/// endpoint, and is verified by the updater using the signature, so sources do
+/// not need to be trusted.
+/// 
+/// Errors should be reported using the most appropriate [`UpdaterError`]
+/// variant. Those that [are retryable](UpdaterError::is_retryable()) will be
+/// retried by the updater according to its [`RetryPolicy`](super::RetryPolicy).
+/// 
+pub trait UpdateSource: Debug + Send + Sync {
+	//		latest																
+	/// Gets the metadata for the latest version.
+	/// 
+	/// This is the payload returned by the `latest` endpoint, which contains
+	/// the latest version, along with its signature.
+	/// 
+	/// # Errors
+	/// 
+	/// Any [`UpdaterError`] that describes why the metadata could not be
+	/// obtained.
+	/// 
+	fn latest(&self) -> BoxFuture<'_, Result<SignedPayload, UpdaterError>>;
+	
+	//		version_hash														
+	/// Gets the metadata for a version.
+	/// 
+	/// This is the payload returned by the `hashes/{version}` endpoint, which
+	/// contains the version and the SHA256 hash of its release file, along with
+	/// its signature.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version to get the metadata for.
+	/// 
+	/// # Errors
+	/// 
+	/// Any [`UpdaterError`] that describes why the metadata could not be
+	/// obtained.
+	/// 
+	fn version_hash<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<SignedPayload, UpdaterError>>;
+	
+	//		release																
+	/// Opens the release file for a version.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version to get the release file for.
+	/// 
+	/// # Errors
+	/// 
+	/// Any [`UpdaterError`] that describes why the release file could not be
+	/// opened.
+	/// 
+	fn release<'a>(&'a self, version: &'a Version) -> BoxFuture<'a, Result<ReleaseStream, UpdaterError>>;
+	
+	//		release_from														
+	/// Opens the release file for a version from a download URL.
+	/// 
+	/// This is used when the metadata for the version gives a URL to download
+	/// the release file from, such as a signed URL on a CDN. Sources that do
+	/// not download over HTTP ignore the URL, and open the release file as
+	/// usual using [`release()`](Self::release()), which is the default.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version to get the release file for.
+	/// * `_url`    - The URL given in the metadata for the version.
+	/// 
+	/// # Errors
+	/// 
+	/// Any [`UpdaterError`] that describes why the release file could not be
+	/// opened.
+	/// 
+	fn release_from<'a>(&'a self, version: &'a Version, _url: &'a Url) -> BoxFuture<'a, Result<ReleaseStream, UpdaterError>> {
+		self.release(version)
+	}
+	
+	//		events																
+	/// Opens a stream of events announcing new releases.
+	/// 
+	/// This is used for push updates, and returns the events in the same format
+	/// as the `events` endpoint. Sources that cannot announce new releases
+	/// return [`None`], which is the default.
+	/// 
+	/// # Errors
+	/// 
+	/// Any [`UpdaterError`] that describes why the events could not be
+	/// obtained.
+	/// 
+	fn events(&self) -> BoxFuture<'_, Result<Option<EventStream>, UpdaterError>> {
+		Box::pin(async { Ok(None) })
+	}
+}
+
+
+
+//		Functions																										
+
+//		get_header																
+/// Gets a header from an HTTP response.
+/// 
+/// This function gets a header from an HTTP response, and converts it to the
+/// specified type.
+/// 
+/// # Parameters
+/// 
+/// * `response` - The HTTP response to get the header from.
+/// * `header`   - The header to get from the response.
+/// 
+fn get_header<K, T>(response: &Response, header: K) -> T
+where
+	K: AsHeaderName,
+	T: Default + FromStr
+{
+	response.headers()
+		.get(header)
+		.and_then(|h| h.to_str().ok())
+		.and_then(|s| T::from_str(s).ok())
+		.unwrap_or_default()
+}
+
+//		describe_error															
+/// Describes an error, along with the errors that caused it.
+/// 
+/// The HTTP client does not include the cause of an error in its message, and
+/// so this is needed to explain, for instance, why a redirect was refused.
+/// 
+/// # Parameters
+/// 
+/// * `err` - The error to describe.
+/// 
+fn describe_error(err: &dyn Error) -> String {
+	let mut description = err.to_string();
+	let mut cause       = err.source();
+	while let Some(source) = cause {
+		description.push_str(": ");
+		description.push_str(&source.to_string());
+		cause = source.source();
+	}
+	description
+}
+
+//		get_content_length														
+/// Gets the length of the body of an HTTP response, if it is given.
+/// 
+/// Unlike [`get_header()`], a missing or invalid `Content-Length` header gives
+/// [`None`] rather than zero, as the length is not always known in advance.
+/// 
+/// # Parameters
+/// 
+/// * `response` - The HTTP response to get the length of.
+/// 
+fn get_content_length(response: &Response) -> Option<usize> {
+	response.headers()
+		.get(CONTENT_LENGTH)
+		.and_then(|h| h.to_str().ok())
+		.and_then(|s| s.parse().ok())
+}
+
+//		is_under																
+/// Works out whether a URL is under a base URL.
+/// 
+/// The URL must have the same origin as the base, i.e. the same scheme, host,
+/// and port, and its path segments must start with those of the base. The
+/// base path is treated as a directory whether or not it has a trailing slash,
+/// so `/api` covers `/api/latest` but not `/apis/latest`.
+/// 
+/// # Parameters
+/// 
+/// * `url`  - The URL to check.
+/// * `base` - The base URL to check against.
+/// 
+fn is_under(url: &Url, base: &Url) -> bool {
+	let segments = |target: &Url| -> Vec<String> {
+		target.path_segments()
+			.map(|segments| segments.filter(|segment| !segment.is_empty()).map(ToOwned::to_owned).collect())
+			.unwrap_or_default()
+	};
+	url.origin() == base.origin() && segments(url).starts_with(&segments(base))
+}
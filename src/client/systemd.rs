@@ -0,0 +1,133 @@
+//! Integration with the systemd service manager.
+//! 
+//! When an application is run as a systemd service of `Type=notify`, it can
+//! report its state to systemd by sending messages over the socket given in the
+//! `NOTIFY_SOCKET` environment variable. This is the protocol implemented by
+//! the `sd_notify()` function in `libsystemd`, which is provided here without
+//! needing any extra dependencies.
+//! 
+//! The updater uses this to report that the application is reloading or
+//! stopping when it restarts, if enabled in the [`Config`](super::Config).
+//! Applications can also use [`notify()`] directly, for instance to report
+//! that they are ready once they have started up.
+//! 
+
+
+
+//		Modules																											
+
+#[cfg(test)]
+#[path = "../tests/client/systemd.rs"]
+mod tests;
+
+
+
+//		Packages																										
+
+use std::{
+	env::var_os,
+	ffi::OsStr,
+	io::Result as IoResult,
+	os::unix::{
+		ffi::OsStrExt as _,
+		net::{SocketAddr, UnixDatagram},
+	},
+};
+
+#[cfg(target_os = "linux")]
+use std::os::linux::net::SocketAddrExt as _;
+
+#[cfg(not(target_os = "linux"))]
+use std::io::{Error as IoError, ErrorKind};
+
+
+
+//		Functions																										
+
+//		notify																	
+/// Sends a state notification to systemd.
+/// 
+/// The state is made up of one or more newline-separated assignments, such as
+/// `READY=1`, `RELOADING=1`, `STOPPING=1`, or `STATUS=...`. See the
+/// `sd_notify(3)` manual page for the full list.
+/// 
+/// If the application is not running under systemd, or the service is not
+/// configured to receive notifications, then the `NOTIFY_SOCKET` environment
+/// variable will not be set, and nothing will be sent. In this situation
+/// `false` is returned, rather than an error, as it is not a problem.
+/// 
+/// # Parameters
+/// 
+/// * `state` - The state to report.
+/// 
+/// # Errors
+/// 
+/// Returns an I/O error if the notification socket cannot be written to.
+/// 
+pub fn notify(state: &str) -> IoResult<bool> {
+	let Some(socket_path) = var_os("NOTIFY_SOCKET") else {
+		return Ok(false);
+	};
+	send_to(&socket_path, state)?;
+	Ok(true)
+}
+
+//		send_to																	
+/// Sends a state notification to a specific socket.
+/// 
+/// Socket paths starting with `@` refer to the abstract namespace, as used by
+/// systemd in some configurations. This is only available on Linux.
+/// 
+/// # Parameters
+/// 
+/// * `socket_path` - The path of the socket to send to.
+/// * `state`       - The state to report.
+/// 
+/// # Errors
+/// 
+/// Returns an I/O error if the socket cannot be written to.
+/// 
+fn send_to(socket_path: &OsStr, state: &str) -> IoResult<()> {
+	let socket  = UnixDatagram::unbound()?;
+	let address = match socket_path.as_bytes().strip_prefix(b"@") {
+		Some(name) => abstract_address(name)?,
+		None       => SocketAddr::from_pathname(socket_path)?,
+	};
+	let _sent   = socket.send_to_addr(state.as_bytes(), &address)?;
+	Ok(())
+}
+
+//		abstract_address														
+/// Creates an address in the abstract socket namespace.
+/// 
+/// # Parameters
+/// 
+/// * `name` - The name of the socket, without the leading `@`.
+/// 
+/// # Errors
+/// 
+/// Returns an I/O error if the name is too long.
+/// 
+#[cfg(target_os = "linux")]
+fn abstract_address(name: &[u8]) -> IoResult<SocketAddr> {
+	SocketAddr::from_abstract_name(name)
+}
+
+//		abstract_address														
+/// Creates an address in the abstract socket namespace.
+/// 
+/// The abstract namespace is specific to Linux, and so an error is always
+/// returned on other platforms.
+/// 
+/// # Parameters
+/// 
+/// * `name` - The name of the socket, without the leading `@`.
+/// 
+/// # Errors
+/// 
+/// Returns an I/O error of kind [`ErrorKind::Unsupported`].
+/// 
+#[cfg(not(target_os = "linux"))]
+fn abstract_address(_name: &[u8]) -> IoResult<SocketAddr> {
+	Err(IoError::new(ErrorKind::Unsupported, "Abstract socket addresses are only supported on Linux"))
+}
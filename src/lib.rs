@@ -42,13 +42,11 @@
 
 pub mod server;
 pub mod client;
+pub mod keys;
+pub mod bundle;
 
 mod responses;
 
-#[cfg(test)]
-#[path = "tests/common.rs"]
-mod common;
-
 #[cfg(test)]
 #[path = "tests/mocks.rs"]
 mod mocks;
@@ -13,6 +13,7 @@ mod tests;
 use rubedo::crypto::Sha256Hash;
 use semver::Version;
 use serde::{Deserialize, Serialize};
+use url::Url;
 
 
 
@@ -28,6 +29,23 @@ pub(crate) struct LatestVersionResponse {
 	pub version: Version,
 }
 
+//		SignedEvent																
+/// The data of an event sent by the `events` endpoint.
+/// 
+/// Events have no headers, so the signature is sent alongside the payload,
+/// which is kept as a string so that it can be verified exactly as it was
+/// signed.
+#[expect(clippy::redundant_pub_crate, reason = "Internal use only")]
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub(crate) struct SignedEvent {
+	//		Crate-accessible properties											
+	/// The JSON payload of the event.
+	pub payload:   String,
+	
+	/// The signature of the payload, in hexadecimal.
+	pub signature: String,
+}
+
 //		VersionHashResponse														
 /// The application hash and version returned by the `hashes/:version` endpoint.
 #[expect(clippy::redundant_pub_crate, reason = "Internal use only")]
@@ -39,6 +57,11 @@ pub(crate) struct VersionHashResponse {
 	
 	/// The SHA256 hash of the application binary for this version.
 	pub hash:    Sha256Hash,
+	
+	/// Where to download the application binary from, if not from the
+	/// `releases/:version` endpoint. This is usually a time-limited signed URL.
+	#[serde(default, skip_serializing_if = "Option::is_none")]
+	pub url:     Option<Url>,
 }
 
 
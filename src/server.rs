@@ -48,6 +48,53 @@
 //! maximum compatibility with all potential uses. Base64 would only offer a
 //! minor saving in comparison.
 //! 
+//! # Release events
+//! 
+//! Clients usually find out about new releases by polling the `latest`
+//! endpoint, which means that an urgent release can take as long as the
+//! clients' check interval to reach them. To avoid this, the [`Axum`] handlers
+//! include an events endpoint, which uses [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html)
+//! to tell connected clients about the latest version as soon as it changes.
+//! New releases can be made available while the server is running using
+//! [`Core::add_version()`], and withdrawn using [`Core::remove_version()`].
+//! 
+//! Each event contains the same payload as the `latest` endpoint, plus a
+//! signature for it, as there are no per-event headers to put it in.
+//! 
+//! # Offline bundles
+//! 
+//! For clients that cannot reach the server, a release can be exported as a
+//! signed bundle using [`Core::create_bundle()`], and then installed using
+//! [`Updater::install_bundle()`](crate::client::Updater::install_bundle()).
+//! See the [`bundle`](crate::bundle) module for details.
+//! 
+//! # Static export
+//! 
+//! For simple deployments, running a server just to provide the endpoints may
+//! be more than is needed. Instead, [`Core::export()`] writes the same files
+//! into a directory tree, with the signatures in detached `.sig` files, which
+//! can then be hosted by any static web server or file share.
+//! 
+//! # Authentication
+//! 
+//! The update endpoints can be limited to known clients by giving a list of
+//! API tokens in the [`Config`], each with an [`Entitlement`] that determines
+//! which applications, release channels, and versions it may see. The
+//! [`Axum`] handlers then require one of the tokens as a bearer token, and
+//! [`Core::authenticate()`] can be used to do the same from other handlers.
+//! 
+//! # Signed download URLs
+//! 
+//! The release files can be served from elsewhere, such as a CDN, by giving
+//! [`SignedUrls`] settings in the [`Config`]. The metadata for each version
+//! then includes a short-lived download URL, signed using HMAC or Ed25519, and
+//! the [`get_release_file()`](Axum::get_release_file()) handler can be told to
+//! reject requests that are not made using a valid one.
+//! 
+//! Alternatively, the handler can redirect requests for the release files to
+//! an external location, by giving a [`release_redirect`](Config::release_redirect)
+//! URL in the [`Config`]. The hashes are still served and signed by the server.
+//! 
 //! # Streaming
 //! 
 //! The behaviour implemented in the provided [`Axum`] handlers is that large
@@ -67,16 +114,36 @@ mod tests;
 
 //		Packages																										
 
-use crate::responses::{LatestVersionResponse, VersionHashResponse};
+use crate::{
+	bundle::{BundleError, write_bundle},
+	responses::{LatestVersionResponse, SignedEvent, VersionHashResponse},
+};
 use axum::{
 	Extension,
 	Json,
 	body::{Body, Bytes},
-	extract::Path,
-	http::{StatusCode, header::CONTENT_LENGTH, header::CONTENT_TYPE},
-	response::{IntoResponse, Response},
+	extract::{Path, Query},
+	http::{
+		HeaderMap,
+		StatusCode,
+		header::{AUTHORIZATION, CONTENT_LENGTH, CONTENT_TYPE, WWW_AUTHENTICATE},
+	},
+	response::{
+		IntoResponse,
+		Redirect,
+		Response,
+		sse::{Event, KeepAlive, Sse},
+	},
 };
-use ed25519_dalek::Signer as _;
+use core::{
+	convert::Infallible,
+	fmt::{Debug, self},
+	time::Duration,
+};
+use ed25519_dalek::{Signature, Signer as _};
+use futures_util::{StreamExt as _, stream};
+use hmac::{Hmac, Mac as _};
+use parking_lot::RwLock;
 use rubedo::{
 	crypto::{Sha256Hash, SigningKey},
 	http::ResponseExt as _,
@@ -84,25 +151,95 @@ use rubedo::{
 	sugar::s,
 };
 use semver::Version;
+use serde::Serialize;
+use sha2::Sha256;
 use std::{
-	collections::HashMap,
-	fs::File,
+	collections::{HashMap, HashSet},
+	fs::{File, self},
 	io::ErrorKind as IoErrorKind,
-	path::PathBuf,
+	path::{Path as FilePath, PathBuf},
 	sync::Arc,
+	time::{SystemTime, UNIX_EPOCH},
 };
 use thiserror::Error as ThisError;
 use tokio::{
 	fs::File as AsyncFile,
 	io::{AsyncReadExt as _, BufReader},
+	sync::watch::{Receiver as Watcher, Sender as WatchSender},
 };
 use tokio_util::io::ReaderStream;
 use tracing::error;
+use url::Url;
 
 
 
 //		Enums																											
 
+//		AuthError																
+/// Errors that can occur when authenticating requests.
+/// 
+/// These only occur when [`tokens`](Config::tokens) or
+/// [`signed_urls`](Config::signed_urls) are configured.
+/// 
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[non_exhaustive]
+pub enum AuthError {
+	/// The signed download URL has expired.
+	#[error("The download URL has expired")]
+	ExpiredUrl,
+	
+	/// The token is valid, but is not entitled to the application or release
+	/// that was requested.
+	#[error("The API token is not entitled to this release")]
+	Forbidden,
+	
+	/// The signature of the download URL is missing, or does not match the
+	/// rest of the URL, meaning that it has been tampered with.
+	#[error("The download URL is not validly signed")]
+	InvalidUrlSignature,
+	
+	/// No token was given, or the token given is not known.
+	#[error("A valid API token is required")]
+	Unauthorized,
+}
+
+//		IntoResponse															
+impl IntoResponse for AuthError {
+	//		into_response														
+	/// Converts the error into a response.
+	/// 
+	/// An [`Unauthorized`](Self::Unauthorized) error results in a
+	/// `401 Unauthorized` status, with a `WWW-Authenticate` header asking for a
+	/// bearer token, and all other errors result in a `403 Forbidden` status.
+	/// 
+	fn into_response(self) -> Response {
+		match self {
+			Self::ExpiredUrl          |
+			Self::Forbidden           |
+			Self::InvalidUrlSignature => (StatusCode::FORBIDDEN, self.to_string()).into_response(),
+			Self::Unauthorized        => (
+				StatusCode::UNAUTHORIZED,
+				[(WWW_AUTHENTICATE, "Bearer")],
+				self.to_string(),
+			).into_response(),
+		}
+	}
+}
+
+//		ExportError																
+/// Errors that can occur when exporting static update files.
+#[derive(Clone, Debug, Eq, PartialEq, ThisError)]
+#[non_exhaustive]
+pub enum ExportError {
+	/// A release file could not be copied into the export directory.
+	#[error("Unable to copy release file {0:?}: {1}")]
+	UnableToCopyRelease(PathBuf, String),
+	
+	/// A file or directory could not be written in the export directory.
+	#[error("Unable to write {0:?}: {1}")]
+	UnableToWrite(PathBuf, String),
+}
+
 //		ReleaseError															
 /// Errors that can occur in relation to releases.
 #[derive(Clone, Debug, Eq, PartialEq, ThisError)]
@@ -121,6 +258,32 @@ pub enum ReleaseError {
 	Unreadable(Version, IoErrorKind, String),
 }
 
+//		UrlSigning																
+/// How signed download URLs are signed.
+#[derive(Clone)]
+#[non_exhaustive]
+pub enum UrlSigning {
+	/// HMAC-SHA256, using the specified secret key. The key needs to be shared
+	/// with anything else that verifies the URLs, such as a CDN.
+	Hmac(Vec<u8>),
+	
+	/// Ed25519, using the server's private [`key`](Config::key). The URLs can
+	/// then be verified by anything that has the server's public key, without
+	/// needing to share a secret.
+	Ed25519,
+}
+
+//		Debug																	
+impl Debug for UrlSigning {
+	//		fmt																	
+	fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+		match *self {
+			Self::Hmac(_) => f.debug_tuple("Hmac").finish_non_exhaustive(),
+			Self::Ed25519 => write!(f, "Ed25519"),
+		}
+	}
+}
+
 
 
 //		Structs																											
@@ -170,6 +333,38 @@ pub enum ReleaseError {
 /// large files, where the memory usage could become a problem and the raw speed
 /// of each download becomes a secondary concern.
 /// 
+/// # Authentication
+/// 
+/// By default, the update endpoints can be used by anyone who can reach them.
+/// If [`tokens`](Self::tokens) are given, each request made to the [`Axum`]
+/// handlers must include one of them as a bearer token in the `Authorization`
+/// header, and only sees the releases that the token's [`Entitlement`] allows.
+/// Requests without a known token receive a `401 Unauthorized` status, and
+/// requests for something the token is not entitled to receive a
+/// `403 Forbidden` status.
+/// 
+/// # Signed download URLs
+/// 
+/// To serve release files from somewhere else, such as a CDN, without making
+/// them public, [`signed_urls`](Self::signed_urls) can be given. The metadata
+/// for each version then includes a download URL under the configured base,
+/// which expires after a short time, and is signed so that it cannot be
+/// altered. Clients download the release file from that URL instead of from
+/// the [`get_release_file()`](Axum::get_release_file()) handler. If the base
+/// points back at the server, or the CDN fetches the files from it, the
+/// handler can be told to only serve requests made using a valid signed URL.
+/// See [`SignedUrls`] for details.
+/// 
+/// # Redirects
+/// 
+/// Alternatively, the release files can be kept in external storage, such as
+/// a CDN or an object store, by giving a [`release_redirect`](Self::release_redirect)
+/// URL. The [`get_release_file()`](Axum::get_release_file()) handler then
+/// responds to each request that is allowed with a `307 Temporary Redirect` to
+/// the release file at that location, instead of serving it. The hashes are
+/// still given by the server, and are still signed, so clients can check that
+/// the file they are redirected to has not been altered.
+/// 
 #[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -206,7 +401,149 @@ pub struct Config {
 	/// version numbers against the SHA256 hashes of the binary release files.
 	/// The hashes are required so that the server can verify the integrity of
 	/// the files before serving them to clients.
-	pub versions:         HashMap<Version, Sha256Hash>
+	pub versions:         HashMap<Version, Sha256Hash>,
+	
+	/// The API tokens that are allowed to use the update endpoints, mapped
+	/// against what each one is entitled to see. This is optional, and if it
+	/// is [`None`], no authentication is required.
+	pub tokens:           Option<HashMap<String, Entitlement>>,
+	
+	/// The settings for issuing signed download URLs for the release files.
+	/// This is optional, and if it is [`None`], clients download the release
+	/// files from the `releases/{version}` endpoint.
+	pub signed_urls:      Option<SignedUrls>,
+	
+	/// The URL of the external location to redirect requests for the release
+	/// files to. The release files are expected to be found under this URL
+	/// with the same names as in the [`releases`](Self::releases) directory,
+	/// and so it should end with a `/`, such as `https://cdn.example.com/releases/`.
+	/// If this is given, the release files are not read from the releases
+	/// directory, and so do not need to be present there. This is optional,
+	/// and if it is [`None`], the release files are served by the server.
+	pub release_redirect: Option<Url>,
+}
+
+//		Entitlement																
+/// What an API token is entitled to see.
+/// 
+/// Each restriction is optional, and the default is an entitlement that can
+/// see everything.
+/// 
+/// # Channels
+/// 
+/// The channel of a release is taken from the pre-release part of its version
+/// number, being the first identifier of it, so that `2.0.0-beta.1` is in the
+/// `beta` channel. Versions without a pre-release part, such as `2.0.0`, are
+/// in the `stable` channel. See [`channel()`](Self::channel()).
+/// 
+#[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Entitlement {
+	//		Public properties													
+	/// The names of the applications that the token may see, matched against
+	/// the [`appname`](Config::appname). If this is [`None`], all applications
+	/// may be seen.
+	pub apps:        Option<HashSet<String>>,
+	
+	/// The release channels that the token may see. If this is [`None`], all
+	/// channels may be seen.
+	pub channels:    Option<HashSet<String>>,
+	
+	/// The highest version that the token may see. If this is [`None`], there
+	/// is no limit.
+	pub max_version: Option<Version>,
+}
+
+//󰭅		Entitlement																
+impl Entitlement {
+	//		Public methods														
+	
+	//		allows_app															
+	/// Whether the entitlement allows an application to be seen.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `appname` - The name of the application.
+	/// 
+	#[must_use]
+	pub fn allows_app(&self, appname: &str) -> bool {
+		self.apps.as_ref().is_none_or(|apps| apps.contains(appname))
+	}
+	
+	//		allows_version														
+	/// Whether the entitlement allows a version to be seen.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application.
+	/// 
+	#[must_use]
+	pub fn allows_version(&self, version: &Version) -> bool {
+		self.max_version.as_ref().is_none_or(|max| version <= max)
+		&& self.channels.as_ref().is_none_or(|channels| channels.contains(Self::channel(version)))
+	}
+	
+	//		channel																
+	/// The release channel that a version is in.
+	/// 
+	/// This is the first identifier of the pre-release part of the version
+	/// number, or `stable` if there is no pre-release part.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application.
+	/// 
+	#[must_use]
+	pub fn channel(version: &Version) -> &str {
+		version.pre.as_str().split('.').next().filter(|name| !name.is_empty()).unwrap_or("stable")
+	}
+	
+	//		is_unrestricted														
+	/// Whether the entitlement allows every version to be seen.
+	const fn is_unrestricted(&self) -> bool {
+		self.channels.is_none() && self.max_version.is_none()
+	}
+}
+
+//		SignedUrls																
+/// The settings for issuing signed download URLs.
+/// 
+/// Each URL is formed by adding the version number to the [`base`](Self::base)
+/// URL, along with `expires` and `signature` query parameters. The expiry time
+/// is given in seconds since the Unix epoch, and the signature is in
+/// hexadecimal, and is made over the following string:
+/// 
+/// ```text
+/// {appname}/{version}/{expires}
+/// ```
+/// 
+/// For example, `https://cdn.example.com/releases/1.2.3?expires=1700000000&signature=...`
+/// is signed over `myapp/1.2.3/1700000000`. This allows the URLs to be checked
+/// by a CDN or other service that has the secret or public key, as well as by
+/// the [`get_release_file()`](Axum::get_release_file()) handler.
+/// 
+#[expect(clippy::exhaustive_structs, reason = "Provided for configuration")]
+#[derive(Clone, Debug)]
+pub struct SignedUrls {
+	//		Public properties													
+	/// The URL to add the version number to, to form each download URL. This
+	/// should end with a `/`, such as `https://cdn.example.com/releases/`.
+	pub base:     Url,
+	
+	/// How long each download URL remains valid for. This only needs to be
+	/// long enough for the client to start the download.
+	pub lifetime: Duration,
+	
+	/// How the download URLs are signed.
+	pub signing:  UrlSigning,
+	
+	/// Whether the [`get_release_file()`](Axum::get_release_file()) handler
+	/// should only serve release files to requests made using a valid signed
+	/// URL. This should be enabled if the [`base`](Self::base) URL points back
+	/// at the server, or if the CDN fetches the files from it and passes the
+	/// query parameters on. API tokens are not checked for these requests, as
+	/// the URL was only issued to a client that was entitled to it.
+	pub verify:   bool,
 }
 
 //		Core																	
@@ -217,16 +554,31 @@ pub struct Config {
 /// the [`Axum`] struct, which contains ready-made handlers for use with the
 /// [Axum](https://crates.io/crates/axum) web framework.
 /// 
+/// Clones share the same version list, so a version added using one of them
+/// is seen by all of them.
+/// 
 #[derive(Clone, Debug)]
 pub struct Core {
-	//		Private properties													
+	//		Private properties													
+	/// A count of the changes made to the version list. This is held in a
+	/// watch channel so that event streams are woken by every change, and can
+	/// then work out whether the latest version that they are entitled to see
+	/// has changed, even if the overall latest version has not.
+	changes:  WatchSender<u64>,
+	
 	/// The configuration for the server.
-	config: Config,
+	config:   Config,
 	
 	/// The latest version of the application. This is determined by examining
-	/// the version list supplied, and finding the highest number. It is then
-	/// cached here for efficiency.
-	latest: Version,
+	/// the version list, and finding the highest number. It is then cached
+	/// here for efficiency, in a watch channel, so that interested parties can
+	/// be told when it changes.
+	latest:   WatchSender<Version>,
+	
+	/// The available versions of the application. These start out as the ones
+	/// given in the configuration, and can be changed while the server is
+	/// running.
+	versions: Arc<RwLock<HashMap<Version, Sha256Hash>>>,
 }
 
 //󰭅		Core																	
@@ -242,7 +594,9 @@ impl Core {
 	/// 
 	/// This function will check the release files for the versions specified in
 	/// the list, and will return an error if any of the files are missing,
-	/// unreadable, or fail the SHA256 hash check.
+	/// unreadable, or fail the SHA256 hash check. This check is skipped if a
+	/// [`release_redirect`](Config::release_redirect) is configured, as the
+	/// release files are then held elsewhere.
 	/// 
 	/// # Parameters
 	/// 
@@ -257,44 +611,222 @@ impl Core {
 	pub fn new(config: Config) -> Result<Self, ReleaseError> {
 		#[expect(clippy::iter_over_hash_type, reason = "Order doesn't matter here")]
 		for (version, hash) in &config.versions {
-			let path = config.releases.join(format!("{}-{}", config.appname, version));
-			if !path.exists() || !path.is_file() {
-				return Err(ReleaseError::Missing(version.clone(), path));
-			}
-			let file_hash: Sha256Hash = File::hash(&path).map_err(|err|
-				ReleaseError::Unreadable(version.clone(), err.kind(), err.to_string())
-			)?;
-			if file_hash != *hash {
-				return Err(ReleaseError::Invalid(version.clone(), path));
-			}
+			Self::verify_release(&config, version, hash)?;
 		}
-		let latest = config.versions.keys().max().unwrap_or(&Version::new(0, 0, 0)).clone();
+		let latest   = config.versions.keys().max().unwrap_or(&Version::new(0, 0, 0)).clone();
+		let versions = Arc::new(RwLock::new(config.versions.clone()));
 		Ok(Self {
+			changes: WatchSender::new(0),
 			config,
-			latest,
+			latest:  WatchSender::new(latest),
+			versions,
 		})
 	}
 	
+	//		Public methods														
+	
+	//		authenticate														
+	/// Works out what a request is entitled to see.
+	/// 
+	/// If no [`tokens`](Config::tokens) are configured, everything is allowed,
+	/// and the default, unrestricted [`Entitlement`] is returned. Otherwise,
+	/// the token must be one of those configured, and its entitlement must
+	/// allow the application.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `token` - The API token given with the request, if any.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`AuthError::Forbidden`]
+	/// * [`AuthError::Unauthorized`]
+	/// 
+	pub fn authenticate(&self, token: Option<&str>) -> Result<Entitlement, AuthError> {
+		let Some(ref tokens) = self.config.tokens else {
+			return Ok(Entitlement::default());
+		};
+		let entitlement = token.and_then(|name| tokens.get(name)).ok_or(AuthError::Unauthorized)?;
+		if !entitlement.allows_app(&self.config.appname) {
+			return Err(AuthError::Forbidden);
+		}
+		Ok(entitlement.clone())
+	}
+	
+	//		add_version															
+	/// Adds a version of the application.
+	/// 
+	/// This function makes a new release available while the server is
+	/// running. The release file is checked in the same way as when the server
+	/// is created, unless a [`release_redirect`](Config::release_redirect) is
+	/// configured, and if the version is newer than the current latest version,
+	/// subscribers are told about it. If the version already exists, its hash
+	/// is replaced.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application to add.
+	/// * `hash`    - The SHA256 hash of the release file for the version.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`ReleaseError::Invalid`]
+	/// * [`ReleaseError::Missing`]
+	/// * [`ReleaseError::Unreadable`]
+	/// 
+	pub fn add_version(&self, version: Version, hash: Sha256Hash) -> Result<(), ReleaseError> {
+		Self::verify_release(&self.config, &version, &hash)?;
+		let _previous = self.versions.write().insert(version, hash);
+		self.refresh_latest();
+		Ok(())
+	}
+	
+	//		remove_version														
+	/// Removes a version of the application.
+	/// 
+	/// This function withdraws a release while the server is running. If it
+	/// was the latest version, the next highest version becomes the latest, and
+	/// subscribers are told about it. The hash of the removed version is
+	/// returned, or [`None`] if it did not exist.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application to remove.
+	/// 
+	#[expect(clippy::must_use_candidate, reason = "The hash is not always needed")]
+	pub fn remove_version(&self, version: &Version) -> Option<Sha256Hash> {
+		let removed = self.versions.write().remove(version);
+		self.refresh_latest();
+		removed
+	}
+	
+	//		create_bundle														
+	/// Creates an update bundle for a version of the application.
+	/// 
+	/// This function writes a bundle containing the release file for the
+	/// specified version, along with its metadata, signed using the server's
+	/// private key. The bundle can then be installed by clients that cannot
+	/// reach the server. See the [`bundle`](crate::bundle) module for details.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application to bundle.
+	/// * `path`    - The path to write the bundle to.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`BundleError::UnableToReadRelease`]
+	/// * [`BundleError::UnableToWriteBundle`]
+	/// * [`BundleError::UnknownVersion`]
+	/// 
+	pub fn create_bundle(&self, version: &Version, path: &FilePath) -> Result<(), BundleError> {
+		let Some(hash) = self.versions().get(version).copied() else {
+			return Err(BundleError::UnknownVersion(version.clone()));
+		};
+		let release    = self.config.releases.join(format!("{}-{}", self.config.appname, version));
+		write_bundle(&self.config.key, version, hash, &release, path)
+	}
+	
+	//		export																
+	/// Exports the available versions of the application as static files.
+	/// 
+	/// This function writes the same metadata and release files as are served
+	/// by the [`Axum`] handlers into a directory, so that they can be hosted by
+	/// any static web server or file share, without needing a live server. The
+	/// metadata is signed using the server's private key, with each signature
+	/// written to a detached file alongside it, as static hosting cannot add
+	/// the `X-Signature` header. The layout is as follows:
+	/// 
+	///   - `latest` — The latest version, as served by
+	///     [`get_latest_version()`](Axum::get_latest_version()).
+	///   - `hashes/{version}` — The hash of each release file, as served by
+	///     [`get_hash_for_version()`](Axum::get_hash_for_version()).
+	///   - `releases/{version}` — Each release file, as served by
+	///     [`get_release_file()`](Axum::get_release_file()).
+	///   - `{name}.sig` — The signature of each metadata file, in hexadecimal.
+	/// 
+	/// The release files and their hashes are written before the `latest` file,
+	/// so that a directory that is being read at the same time never refers to
+	/// a release that is not there yet. Existing files are overwritten, but
+	/// files for versions that are no longer available are not removed.
+	/// 
+	/// The directory can be read by the client using an
+	/// [`HttpSource`](crate::client::source::HttpSource) pointed at a web
+	/// server that hosts it, or a
+	/// [`DirectorySource`](crate::client::source::DirectorySource).
+	/// 
+	/// # Parameters
+	/// 
+	/// * `dir` - The directory to write the files to. It will be created if it
+	///           does not exist.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`ExportError::UnableToCopyRelease`]
+	/// * [`ExportError::UnableToWrite`]
+	/// 
+	pub fn export(&self, dir: &FilePath) -> Result<(), ExportError> {
+		let hashes   = dir.join("hashes");
+		let releases = dir.join("releases");
+		for path in [&hashes, &releases] {
+			fs::create_dir_all(path).map_err(|err| ExportError::UnableToWrite(path.clone(), err.to_string()))?;
+		}
+		#[expect(clippy::iter_over_hash_type, reason = "Order doesn't matter here")]
+		for (version, hash) in self.versions() {
+			let release = self.config.releases.join(format!("{}-{}", self.config.appname, version));
+			let _copied = fs::copy(&release, releases.join(version.to_string())).map_err(|err|
+				ExportError::UnableToCopyRelease(release.clone(), err.to_string())
+			)?;
+			self.write_signed(dir, &format!("hashes/{version}"), &VersionHashResponse { version, hash, url: None })?;
+		}
+		self.write_signed(dir, "latest", &LatestVersionResponse { version: self.latest_version(&Entitlement::default()) })
+	}
+	
 	//		latest_version														
 	/// The latest version of the application.
 	/// 
 	/// This function returns the latest version of the application, as per the
-	/// configured version list.
+	/// current version list, out of the versions that the entitlement allows.
+	/// If it does not allow any of them, `0.0.0` is returned.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `entitlement` - What the caller is entitled to see. The default
+	///                   [`Entitlement`] allows every version.
+	/// 
+	#[must_use]
+	pub fn latest_version(&self, entitlement: &Entitlement) -> Version {
+		if entitlement.is_unrestricted() {
+			return self.latest.borrow().clone();
+		}
+		self.versions.read().keys()
+			.filter(|version| entitlement.allows_version(version))
+			.max()
+			.cloned()
+			.unwrap_or_else(|| Version::new(0, 0, 0))
+	}
+	
+	//		subscribe															
+	/// Subscribes to changes to the latest version.
+	/// 
+	/// This function returns a receiver that always holds the latest version of
+	/// the application, and that can be used to wait for it to change.
 	/// 
 	#[must_use]
-	pub fn latest_version(&self) -> Version {
-		self.latest.clone()
+	pub fn subscribe(&self) -> Watcher<Version> {
+		self.latest.subscribe()
 	}
 	
 	//		versions															
 	/// The available versions of the application.
 	/// 
 	/// This function returns the available versions of the application, as
-	/// specified in the configuration.
+	/// specified in the configuration, plus or minus any that have been added
+	/// or removed since.
 	/// 
 	#[must_use]
 	pub fn versions(&self) -> HashMap<Version, Sha256Hash> {
-		self.config.versions.clone()
+		self.versions.read().clone()
 	}
 	
 	//		release_file														
@@ -320,6 +852,210 @@ impl Core {
 			.get(version)
 			.map(|_hash| self.config.releases.join(format!("{}-{}", self.config.appname, version)))
 	}
+	
+	//		release_location													
+	/// The external location of the release file for a version.
+	/// 
+	/// This function returns the URL that requests for the release file are
+	/// redirected to, if a [`release_redirect`](Config::release_redirect) is
+	/// configured and the version exists, and [`None`] otherwise.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application to get the location for.
+	/// 
+	#[must_use]
+	pub fn release_location(&self, version: &Version) -> Option<Url> {
+		let base = self.config.release_redirect.as_ref()?;
+		self.versions.read().contains_key(version)
+			.then(|| base.join(&format!("{}-{}", self.config.appname, version)).ok())
+			.flatten()
+	}
+	
+	//		download_url														
+	/// The signed URL to download the release file for a version from.
+	/// 
+	/// This function returns a URL that expires after the configured lifetime,
+	/// as described in [`SignedUrls`]. If [`signed_urls`](Config::signed_urls)
+	/// are not configured, [`None`] is returned, and the release file should
+	/// be downloaded from the `releases/{version}` endpoint as usual.
+	/// 
+	/// No check is made that the version exists.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version` - The version of the application to download.
+	/// 
+	#[must_use]
+	pub fn download_url(&self, version: &Version) -> Option<Url> {
+		let settings = self.config.signed_urls.as_ref()?;
+		let expires  = Self::unix_time().saturating_add(settings.lifetime.as_secs());
+		let mut url  = settings.base.join(&version.to_string()).ok()?;
+		let _query   = url.query_pairs_mut()
+			.append_pair("expires",   &expires.to_string())
+			.append_pair("signature", &self.sign_download(settings, version, expires))
+		;
+		Some(url)
+	}
+	
+	//		verify_download														
+	/// Checks a signed download URL.
+	/// 
+	/// This function checks the parts of a URL issued by
+	/// [`download_url()`](Self::download_url()), to make sure that it has not
+	/// been tampered with, and has not expired.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `version`   - The version of the application being downloaded.
+	/// * `expires`   - The expiry time given in the URL, in seconds since the
+	///                 Unix epoch.
+	/// * `signature` - The signature given in the URL, in hexadecimal.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`AuthError::ExpiredUrl`]
+	/// * [`AuthError::InvalidUrlSignature`]
+	/// 
+	pub fn verify_download(&self, version: &Version, expires: u64, signature: &str) -> Result<(), AuthError> {
+		let Some(ref settings) = self.config.signed_urls else {
+			return Err(AuthError::InvalidUrlSignature);
+		};
+		let message = format!("{}/{version}/{expires}", self.config.appname);
+		let bytes   = hex::decode(signature).map_err(|_err| AuthError::InvalidUrlSignature)?;
+		let valid   = match settings.signing {
+			UrlSigning::Hmac(ref secret) => Self::hmac(secret, &message).verify_slice(&bytes).is_ok(),
+			UrlSigning::Ed25519          => <[u8; 64]>::try_from(bytes.as_slice()).is_ok_and(|array|
+				self.config.key.verifying_key().verify_strict(message.as_bytes(), &Signature::from_bytes(&array)).is_ok()
+			),
+		};
+		if !valid {
+			return Err(AuthError::InvalidUrlSignature);
+		}
+		if Self::unix_time() > expires {
+			return Err(AuthError::ExpiredUrl);
+		}
+		Ok(())
+	}
+	
+	//		Private methods														
+	
+	//		refresh_latest														
+	/// Works out the latest version again after the version list has changed.
+	/// 
+	/// Subscribers are only told about the latest version if it is different
+	/// from before. Event streams are told about every change, as a version
+	/// below the overall latest one may still be the latest for some tokens.
+	/// 
+	fn refresh_latest(&self) {
+		self.changes.send_modify(|count| *count = count.wrapping_add(1));
+		let latest = self.versions.read().keys().max().cloned().unwrap_or_else(|| Version::new(0, 0, 0));
+		let _changed = self.latest.send_if_modified(|current| {
+			if *current == latest {
+				return false;
+			}
+			*current = latest;
+			true
+		});
+	}
+	
+	//		hmac																
+	/// Creates an HMAC-SHA256 instance for a signed download URL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `secret`  - The secret key.
+	/// * `message` - The message to sign or verify.
+	/// 
+	#[expect(clippy::unwrap_used,        reason = "Infallible")]
+	fn hmac(secret: &[u8], message: &str) -> Hmac<Sha256> {
+		let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+		mac.update(message.as_bytes());
+		mac
+	}
+	
+	//		sign_download														
+	/// Signs a download URL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `settings` - The signed URL settings.
+	/// * `version`  - The version of the application being downloaded.
+	/// * `expires`  - The expiry time, in seconds since the Unix epoch.
+	/// 
+	fn sign_download(&self, settings: &SignedUrls, version: &Version, expires: u64) -> String {
+		let message = format!("{}/{version}/{expires}", self.config.appname);
+		match settings.signing {
+			UrlSigning::Hmac(ref secret) => hex::encode(Self::hmac(secret, &message).finalize().into_bytes()),
+			UrlSigning::Ed25519          => self.config.key.sign(message.as_bytes()).to_string(),
+		}
+	}
+	
+	//		unix_time															
+	/// The current time, in seconds since the Unix epoch.
+	fn unix_time() -> u64 {
+		SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |since| since.as_secs())
+	}
+	
+	//		write_signed														
+	/// Writes a metadata file and its detached signature.
+	/// 
+	/// The payload is serialised in the same way as by the [`Axum`] handlers,
+	/// and the signature is written to a file of the same name with a `.sig`
+	/// extension.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `dir`     - The directory to write the files to.
+	/// * `name`    - The name of the metadata file, relative to the directory.
+	/// * `payload` - The payload to write.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`ExportError::UnableToWrite`]
+	/// 
+	#[expect(clippy::unwrap_in_result, reason = "Infallible")]
+	#[expect(clippy::unwrap_used,      reason = "Infallible")]
+	fn write_signed<T: Serialize>(&self, dir: &FilePath, name: &str, payload: &T) -> Result<(), ExportError> {
+		let body      = serde_json::to_vec(payload).unwrap();
+		let signature = self.config.key.sign(&body).to_string();
+		let path      = dir.join(name);
+		let sig_path  = dir.join(format!("{name}.sig"));
+		fs::write(&path, &body).map_err(|err| ExportError::UnableToWrite(path.clone(), err.to_string()))?;
+		fs::write(&sig_path, signature).map_err(|err| ExportError::UnableToWrite(sig_path, err.to_string()))
+	}
+	
+	//		verify_release														
+	/// Checks the release file for a version of the application.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `config`  - The configuration for the server.
+	/// * `version` - The version of the application to check.
+	/// * `hash`    - The expected SHA256 hash of the release file.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`ReleaseError::Invalid`]
+	/// * [`ReleaseError::Missing`]
+	/// * [`ReleaseError::Unreadable`]
+	/// 
+	fn verify_release(config: &Config, version: &Version, hash: &Sha256Hash) -> Result<(), ReleaseError> {
+		if config.release_redirect.is_some() {
+			return Ok(());
+		}
+		let path = config.releases.join(format!("{}-{}", config.appname, version));
+		if !path.exists() || !path.is_file() {
+			return Err(ReleaseError::Missing(version.clone(), path));
+		}
+		let file_hash: Sha256Hash = File::hash(&path).map_err(|err|
+			ReleaseError::Unreadable(version.clone(), err.kind(), err.to_string())
+		)?;
+		if file_hash != *hash {
+			return Err(ReleaseError::Invalid(version.clone(), path));
+		}
+		Ok(())
+	}
 }
 
 //		Axum																	
@@ -337,6 +1073,9 @@ impl Core {
 ///      router as an extension, to be extracted by the handlers.
 ///   2. The handlers are static methods, and stateless, and obtain their state
 ///      by extracting the [`Core`] instance from the request extensions.
+///   3. If [`tokens`](Config::tokens) are configured, the handlers check the
+///      bearer token in the `Authorization` header of each request, using
+///      [`Core::authenticate()`].
 /// 
 /// It is not intended that this struct should be instantiated, and so the
 /// ability to do so is not provided.
@@ -348,6 +1087,7 @@ impl Core {
 /// let core   = Arc::new(Core::new(config));
 /// let app    = Router::new()
 ///     .route("/api/latest",            get(Axum::get_latest_version))
+///     .route("/api/events",            get(Axum::get_events))
 ///     .route("/api/hashes/{version}",   get(Axum::get_hash_for_version))
 ///     .route("/api/releases/{version}", get(Axum::get_release_file))
 ///     .layer(Extension(core))
@@ -372,14 +1112,74 @@ impl Axum {
 	/// # Parameters
 	/// 
 	/// * `core`    - The core server instance.
+	/// * `headers` - The request headers, containing any API token.
+	/// 
+	/// # Errors
+	/// 
+	///   - A `401 Unauthorized` status will be returned if an API token is
+	///     required, and a known one is not given.
+	///   - A `403 Forbidden` status will be returned if the API token is not
+	///     entitled to the application.
 	/// 
 	#[expect(clippy::unused_async, reason = "Consistent and future-proof")]
 	pub async fn get_latest_version(
 		Extension(core): Extension<Arc<Core>>,
+		headers:         HeaderMap,
+	) -> impl IntoResponse {
+		let entitlement = core.authenticate(Self::bearer_token(&headers))?;
+		Ok::<_, AuthError>(Self::sign_response(&core.config.key, Json(LatestVersionResponse {
+			version: core.latest_version(&entitlement),
+		}).into_response()))
+	}
+	
+	//		get_events															
+	/// Events announcing the latest version of the application.
+	/// 
+	/// This handler returns a stream of [Server-Sent Events](https://html.spec.whatwg.org/multipage/server-sent-events.html),
+	/// which stays open. An event of type `release` is sent straight away with
+	/// the current latest version, and then again each time the latest version
+	/// changes. The data of each event is a JSON object containing the same
+	/// payload as the [`get_latest_version()`](Self::get_latest_version())
+	/// handler, and its signature. Keep-alive comments are sent periodically,
+	/// so that idle connections are not closed by proxies.
+	/// 
+	/// The latest version is the latest one that the API token is entitled to
+	/// see, and so changes to versions that it cannot see are not announced.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `core`    - The core server instance.
+	/// * `headers` - The request headers, containing any API token.
+	/// 
+	/// # Errors
+	/// 
+	///   - A `401 Unauthorized` status will be returned if an API token is
+	///     required, and a known one is not given.
+	///   - A `403 Forbidden` status will be returned if the API token is not
+	///     entitled to the application.
+	/// 
+	#[expect(clippy::unused_async, reason = "Consistent and future-proof")]
+	pub async fn get_events(
+		Extension(core): Extension<Arc<Core>>,
+		headers:         HeaderMap,
 	) -> impl IntoResponse {
-		Self::sign_response(&core.config.key, Json(LatestVersionResponse {
-			version: core.latest_version(),
-		}).into_response())
+		let entitlement = core.authenticate(Self::bearer_token(&headers))?;
+		let key         = core.config.key.clone();
+		let state       = (core.changes.subscribe(), None, core, entitlement);
+		let stream      = stream::unfold(state, |(mut watcher, mut last, instance, allowed)| async move {
+			loop {
+				if last.is_some() {
+					watcher.changed().await.ok()?;
+				}
+				watcher.mark_unchanged();
+				let version = instance.latest_version(&allowed);
+				if last.as_ref() != Some(&version) {
+					last = Some(version.clone());
+					return Some((version, (watcher, last, instance, allowed)));
+				}
+			}
+		}).map(move |version| Ok::<_, Infallible>(Self::sign_event(&key, version)));
+		Ok::<_, AuthError>(Sse::new(stream).keep_alive(KeepAlive::default()))
 	}
 	
 	//		get_hash_for_version												
@@ -391,26 +1191,36 @@ impl Axum {
 	/// # Parameters
 	/// 
 	/// * `core`    - The core server instance.
+	/// * `headers` - The request headers, containing any API token.
 	/// * `version` - The version of the application to retrieve the hash for.
 	/// 
 	/// # Errors
 	/// 
 	///   - A `400 Bad Request` status will be returned if the version format is
 	///     invalid.
+	///   - A `401 Unauthorized` status will be returned if an API token is
+	///     required, and a known one is not given.
+	///   - A `403 Forbidden` status will be returned if the API token is not
+	///     entitled to the specified version.
 	///   - A `404 Not Found` status will be returned if the specified version
 	///     does not exist.
 	/// 
 	#[expect(clippy::unused_async, reason = "Consistent and future-proof")]
 	pub async fn get_hash_for_version(
 		Extension(core): Extension<Arc<Core>>,
+		headers:         HeaderMap,
 		Path(version):   Path<Version>,
 	) -> impl IntoResponse {
+		if let Err(err) = Self::authorize(&core, &headers, &version) {
+			return Err(err.into_response());
+		}
 		match core.versions().get(&version) {
 			Some(hash) => Ok(Self::sign_response(&core.config.key, Json(VersionHashResponse {
+				url:     core.download_url(&version),
 				version,
 				hash:    *hash,
 			}).into_response())),
-			None       => Err((StatusCode::NOT_FOUND, format!("Version {version} not found"))),
+			None       => Err((StatusCode::NOT_FOUND, format!("Version {version} not found")).into_response()),
 		}
 	}
 	
@@ -421,16 +1231,34 @@ impl Axum {
 	/// application, as per the configured version list. It will stream the file
 	/// if it is large.
 	/// 
+	/// If [`signed_urls`](Config::signed_urls) are configured with
+	/// [`verify`](SignedUrls::verify) enabled, the request must have been made
+	/// using a valid signed URL, and the API token is not checked.
+	/// 
+	/// If a [`release_redirect`](Config::release_redirect) is configured, a
+	/// `307 Temporary Redirect` status is returned instead of the file, giving
+	/// the external location of the file. A temporary redirect is used so that
+	/// clients do not remember it, and ask the server each time, so that the
+	/// token is still checked, and the location can be changed.
+	/// 
 	/// # Parameters
 	/// 
 	/// * `core`    - The core server instance.
+	/// * `headers` - The request headers, containing any API token.
 	/// * `version` - The version of the application to retrieve the release
 	///               file for.
+	/// * `query`   - The query parameters, containing the expiry time and
+	///               signature of a signed URL.
 	/// 
 	/// # Errors
 	/// 
 	///   - A `400 Bad Request` status will be returned if the version format is
 	///     invalid.
+	///   - A `401 Unauthorized` status will be returned if an API token is
+	///     required, and a known one is not given.
+	///   - A `403 Forbidden` status will be returned if the API token is not
+	///     entitled to the specified version, or if a signed URL is required,
+	///     and the one used has expired or is not validly signed.
 	///   - A `404 Not Found` status will be returned if the specified version
 	///     does not exist.
 	///   - A `500 Internal Server Error` status will be returned if the file
@@ -442,27 +1270,43 @@ impl Axum {
 	#[expect(clippy::missing_panics_doc, reason = "Infallible")]
 	pub async fn get_release_file(
 		Extension(core): Extension<Arc<Core>>,
+		headers:         HeaderMap,
 		Path(version):   Path<Version>,
+		Query(query):    Query<HashMap<String, String>>,
 	) -> impl IntoResponse {
+		let access = if core.config.signed_urls.as_ref().is_some_and(|settings| settings.verify) {
+			Self::verify_signed_url(&core, &query, &version)
+		} else {
+			Self::authorize(&core, &headers, &version)
+		};
+		if let Err(err) = access {
+			return Err(err.into_response());
+		}
+		if core.config.release_redirect.is_some() {
+			let Some(location) = core.release_location(&version) else {
+				return Err((StatusCode::NOT_FOUND, format!("Version {version} not found")).into_response());
+			};
+			return Ok(Redirect::temporary(location.as_str()).into_response());
+		}
 		let Some(path) = core.release_file(&version) else {
-			return Err((StatusCode::NOT_FOUND, format!("Version {version} not found")));
+			return Err((StatusCode::NOT_FOUND, format!("Version {version} not found")).into_response());
 		};
 		if !path.exists() || !path.is_file() {
 			error!("Release file missing: {path:?}");
-			return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Release file missing")));
+			return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Release file missing")).into_response());
 		}
 		let mut file  = match AsyncFile::open(&path).await {
 			Ok(file) => file,
 			Err(err) => {
 				error!("Cannot open release file: {path:?}, error: {err}");
-				return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot open release file")));
+				return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot open release file")).into_response());
 			},
 		};
 		let metadata = match file.metadata().await {
 			Ok(metadata) => metadata,
 			Err(err)     => {
 				error!("Cannot read release file metadata: {path:?}, error: {err}");
-				return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot read release file metadata")));
+				return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot read release file metadata")).into_response());
 			},
 		};
 		let body = if metadata.len() > core.config.stream_threshold.saturating_mul(1024) {
@@ -475,7 +1319,7 @@ impl Axum {
 				Ok(_)    => (),
 				Err(err) => {
 					error!("Cannot read release file: {path:?}, error: {err}");
-					return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot read release file")));
+					return Err((StatusCode::INTERNAL_SERVER_ERROR, s!("Cannot read release file")).into_response());
 				},
 			}
 			Body::from(contents)
@@ -529,6 +1373,87 @@ impl Axum {
 		signed_response.headers_mut().extend(response.headers().clone());
 		signed_response.into_response()
 	}
+	
+	//		sign_event															
+	/// Creates a signed event announcing a version.
+	/// 
+	/// Server-Sent Events have no headers, so the signature cannot be sent in
+	/// the same way as for [`sign_response()`](Self::sign_response()). Instead,
+	/// the payload is serialised to a string, and sent alongside its signature
+	/// in the event data.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `key`     - The server's private key.
+	/// * `version` - The version to announce.
+	/// 
+	#[expect(clippy::missing_panics_doc, reason = "Infallible")]
+	#[expect(clippy::unwrap_used,        reason = "Infallible")]
+	pub fn sign_event(key: &SigningKey, version: Version) -> Event {
+		let payload   = serde_json::to_string(&LatestVersionResponse { version }).unwrap();
+		let signature = key.sign(payload.as_bytes()).to_string();
+		Event::default()
+			.event("release")
+			.data(serde_json::to_string(&SignedEvent { payload, signature }).unwrap())
+	}
+	
+	//		Private methods														
+	
+	//		authorize															
+	/// Checks that a request is entitled to a version of the application.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `core`    - The core server instance.
+	/// * `headers` - The request headers, containing any API token.
+	/// * `version` - The version of the application being requested.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`AuthError::Forbidden`]
+	/// * [`AuthError::Unauthorized`]
+	/// 
+	fn authorize(core: &Core, headers: &HeaderMap, version: &Version) -> Result<(), AuthError> {
+		if core.authenticate(Self::bearer_token(headers))?.allows_version(version) {
+			Ok(())
+		} else {
+			Err(AuthError::Forbidden)
+		}
+	}
+	
+	//		verify_signed_url													
+	/// Checks that a request was made using a valid signed download URL.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `core`    - The core server instance.
+	/// * `query`   - The query parameters of the request.
+	/// * `version` - The version of the application being requested.
+	/// 
+	/// # Errors
+	/// 
+	/// * [`AuthError::ExpiredUrl`]
+	/// * [`AuthError::InvalidUrlSignature`]
+	/// 
+	fn verify_signed_url(core: &Core, query: &HashMap<String, String>, version: &Version) -> Result<(), AuthError> {
+		let expires   = query.get("expires").and_then(|expires| expires.parse().ok()).ok_or(AuthError::InvalidUrlSignature)?;
+		let signature = query.get("signature").ok_or(AuthError::InvalidUrlSignature)?;
+		core.verify_download(version, expires, signature)
+	}
+	
+	//		bearer_token														
+	/// Gets the bearer token from the `Authorization` header, if there is one.
+	/// 
+	/// # Parameters
+	/// 
+	/// * `headers` - The request headers.
+	/// 
+	fn bearer_token(headers: &HeaderMap) -> Option<&str> {
+		headers.get(AUTHORIZATION)?.to_str().ok()?
+			.split_once(' ')
+			.filter(|parts| parts.0.eq_ignore_ascii_case("bearer"))
+			.map(|parts| parts.1.trim())
+	}
 }
 
 
@@ -0,0 +1,46 @@
+//		Packages																										
+
+use super::*;
+use crate::keys::generate_keypair;
+use claims::assert_err_eq;
+use rubedo::sugar::s;
+use ed25519_dalek::Signature;
+use sha2::{Sha256, Digest as _};
+use std::fs;
+use tempfile::tempdir;
+
+
+
+//		Tests																											
+
+//		write_bundle															
+#[test]
+fn write_bundle() {
+	let key       = generate_keypair().0;
+	let dir       = tempdir().unwrap();
+	let release   = dir.path().join("release");
+	let bundle    = dir.path().join("test.bundle");
+	let hash      = Sha256::digest(b"\x00release\ncontents\xFF").into();
+	fs::write(&release, b"\x00release\ncontents\xFF").unwrap();
+	super::write_bundle(&key, &Version::new(1, 2, 3), hash, &release, &bundle).unwrap();
+	let data      = fs::read(&bundle).unwrap();
+	let mut parts = data.splitn(4, |&byte| byte == b'\n');
+	assert_eq!(parts.next().unwrap(), BUNDLE_FORMAT.as_bytes());
+	let payload   = parts.next().unwrap();
+	let decoded   = serde_json::from_slice::<VersionHashResponse>(payload).unwrap();
+	assert_eq!(decoded.version, Version::new(1, 2, 3));
+	assert_eq!(decoded.hash,    hash);
+	let signature = Signature::from_slice(&hex::decode(parts.next().unwrap()).unwrap()).unwrap();
+	assert!(key.verifying_key().verify_strict(payload, &signature).is_ok());
+	assert_eq!(parts.next().unwrap(), b"\x00release\ncontents\xFF");
+}
+#[test]
+fn write_bundle__err_missing_release() {
+	let key     = generate_keypair().0;
+	let dir     = tempdir().unwrap();
+	let release = dir.path().join("release");
+	let bundle  = dir.path().join("test.bundle");
+	let err     = super::write_bundle(&key, &Version::new(1, 2, 3), Sha256Hash::default(), &release, &bundle);
+	assert_err_eq!(err, BundleError::UnableToReadRelease(release, s!("No such file or directory (os error 2)")));
+	assert!(!bundle.exists());
+}
@@ -3,35 +3,75 @@
 //		Packages																										
 
 use super::*;
-use crate::common::utils::*;
+use crate::{
+	bundle::write_bundle,
+	keys::generate_keypair,
+};
+use crate::client::maintenance::{MaintenanceScope, MaintenanceWindow, TimeOfDay};
+use crate::client::source::DirectorySource;
 use crate::mocks::{
+	MockConsent,
 	MockSubscriber,
 	Subscriber as _,
-	reqwest::{create_mock_binary_response, create_mock_response},
+	reqwest::{ResponseSignature, create_mock_binary_response, create_mock_response},
 	std_env::MOCK_EXE,
+	std_process::{ProcessCall, take_calls},
 };
 use assert_json_diff::assert_json_eq;
-use claims::{assert_err_eq, assert_ok, assert_none, assert_some};
-use futures_util::future::FutureExt as _;
+use claims::{assert_err, assert_err_eq, assert_ok, assert_ok_eq, assert_none, assert_some, assert_some_eq};
+use ed25519_dalek::Signer as _;
+use futures_util::{StreamExt as _, future::FutureExt as _};
 use parking_lot::ReentrantMutexGuard;
 use reqwest::StatusCode;
-use rubedo::std::ByteSized as _;
+use rubedo::{
+	crypto::SigningKey,
+	std::ByteSized as _,
+};
 use serde_json::{Value as JsonValue, json};
-use core::cell::RefCell;
-use sham::reqwest::{MockClient, create_mock_client, create_mock_response as create_sham_response};
+use core::{
+	cell::RefCell,
+	iter::repeat_with,
+	ops::Range,
+	slice,
+};
+use sham::reqwest::{
+	MockClient,
+	MockError,
+	create_mock_client,
+};
 use std::{
 	fs::{File, self},
 	io::Write as _,
-	sync::LazyLock,
+	net::TcpListener,
+	sync::{Barrier, LazyLock},
+	time::UNIX_EPOCH,
 };
 use tokio::{
 	fs as async_fs,
+	runtime::Handle,
+	task::spawn_blocking,
 	time::sleep,
 };
 use tempfile::{TempDir, tempdir};
 
 
 
+//		Constants																										
+
+const TEST_CA_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBjTCCATOgAwIBAgIULII92JS9RZ5SILouotK/LYm5GrwwCgYIKoZIzj0EAwIw
+GzEZMBcGA1UEAwwQUGF0Y2hpZnkgVGVzdCBDQTAgFw0yNjEwMTgxNjQ1MDdaGA8y
+MTI2MDkyNDE2NDUwN1owGzEZMBcGA1UEAwwQUGF0Y2hpZnkgVGVzdCBDQTBZMBMG
+ByqGSM49AgEGCCqGSM49AwEHA0IABHSBeEMQA9kFajmp5sTkHj6RxvyDgHEAnNr+
+eiH0IGxHbPqxsTJLlo47Krj54Onvsa/yg4zCk7ojHjq8Roa+H2qjUzBRMB0GA1Ud
+DgQWBBTUSnOFn/CSfOmT8DBgeNWxUBg0dzAfBgNVHSMEGDAWgBTUSnOFn/CSfOmT
+8DBgeNWxUBg0dzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0gAMEUCIFPe
+jucOa8Vl6Vfqkf/m4UC6Lropqc+4IDyhuOI8Kcx4AiEAoXspc0zyBqRM4lDJjnuD
+0agRLtUBCCaBkq7fJCrH7A8=
+-----END CERTIFICATE-----\n";
+
+
+
 //		Statics																											
 
 static EMPTY_PUBLIC_KEY: LazyLock<VerifyingKey> = LazyLock::new(|| VerifyingKey::from_bytes([0; 32]));
@@ -44,8 +84,9 @@ static EMPTY_PUBLIC_KEY: LazyLock<VerifyingKey> = LazyLock::new(|| VerifyingKey:
 /// This function sets up a safe `Updater` instance for testing.
 /// 
 /// The `Updater` instance is created by bypassing `Updater::new()`, so that the
-/// real checks are not triggered. Additionally, it is created with a mock
-/// `Client` instance, so that no actual network requests will be made.
+/// real checks are not triggered. Additionally, it is created with an
+/// `HttpSource` that uses a mock `Client` instance, so that no actual network
+/// requests will be made.
 /// 
 fn setup_safe_updater(
 	version:     Version,
@@ -61,17 +102,50 @@ fn setup_safe_updater(
 	Updater {
 		actions:     AtomicUsize::new(0),
 		broadcast:   tx,
+		bundles:     Mutex::new(HashSet::new()),
 		config:      Config {
 			version,
 			api:     api.parse().unwrap(),
+			mirrors: vec![],
+			source:  None,
+			http:    HttpSettings::default(),
+			token:   None,
 			key,
 			check_on_startup: false,
 			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy {
+				max_retries: 0,
+				..RetryPolicy::default()
+			},
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		},
+		declined:    RwLock::new(HashSet::new()),
+		deadline:    Mutex::new(None),
+		events:      Mutex::new(vec![]),
 		exe_path:    MOCK_EXE.lock().borrow().as_ref().map_or_else(PathBuf::new, Clone::clone),
-		http_client: mock_client,
+		listeners:   Mutex::new(vec![]),
+		next_action: AtomicU64::new(1),
 		queue:       sender,
+		staged:      Mutex::new(None),
+		shutdown:    Mutex::new(None),
+		source:      Arc::new(HttpSource::with_client(mock_client, api.parse().unwrap(), vec![], Duration::from_secs(300))),
 		status:      RwLock::new(Status::Idle),
+		subscribed:  AtomicBool::new(false),
+		tracked:     Mutex::new(BTreeMap::new()),
+		watch:       WatchSender::new(Status::Idle),
+		this:        Weak::new(),
 	}
 }
 
@@ -94,11 +168,408 @@ fn setup_files<'lock>() -> (
 	(lock, temp_dir, exe_path, old_path, new_path)
 }
 
+//		create_update_client													
+/// This function creates a mock client that responds for an update process.
+/// 
+/// The available responses are for the latest version check, the metadata for
+/// the version, and the release file download, in that order, for version
+/// 2.3.4. The range
+/// specifies which of these steps the mock client should expect, so that tests
+/// can start and stop the process wherever they need to.
+/// 
+fn create_update_client(steps: Range<usize>) -> (VerifyingKey, MockClient) {
+	let private_key                  = generate_keypair().0;
+	let url1                         = "https://api.example.com/api/latest";
+	let url2                         = "https://api.example.com/api/hashes/2.3.4";
+	let url3                         = "https://api.example.com/api/releases/2.3.4";
+	let payload                      = b"Test payload";
+	let json1                        = json!({
+		"version": s!("2.3.4"),
+	}).to_string();
+	let json2                        = json!({
+		"version": s!("2.3.4"),
+		"hash":    hex::encode(Sha256::digest(payload)),
+	}).to_string();
+	let (mock_response1, public_key) = create_mock_response(
+		url1,
+		StatusCode::OK,
+		Some("application/json"),
+		Some(json1.len()),
+		Ok(&json1),
+		&ResponseSignature::GenerateUsing(private_key.clone()),
+	);
+	let (mock_response2, _public_key) = create_mock_response(
+		url2,
+		StatusCode::OK,
+		Some("application/json"),
+		Some(json2.len()),
+		Ok(&json2),
+		&ResponseSignature::GenerateUsing(private_key),
+	);
+	let mock_response3 = create_mock_binary_response(
+		url3,
+		StatusCode::OK,
+		Some("application/octet-stream"),
+		Some(payload.len()),
+		Ok(payload),
+	);
+	let responses = vec![
+		(url1, Ok(mock_response1)),
+		(url2, Ok(mock_response2)),
+		(url3, Ok(mock_response3)),
+	];
+	(public_key, create_mock_client(responses.into_iter().skip(steps.start).take(steps.len()).collect()))
+}
+
+
+
+//		create_bundle															
+/// This function creates a signed update bundle for testing.
+/// 
+/// The bundle contains the specified payload as the release file, and is
+/// written to the specified directory. The public key that can verify it is
+/// returned along with its path.
+/// 
+fn create_bundle(dir: &Path, name: &str, version: &str, payload: &[u8]) -> (VerifyingKey, PathBuf) {
+	let private_key = generate_keypair().0;
+	let release     = dir.join(format!("{name}.release"));
+	let bundle      = dir.join(name);
+	fs::write(&release, payload).unwrap();
+	write_bundle(&private_key, &version.parse().unwrap(), Sha256::digest(payload).into(), &release, &bundle).unwrap();
+	fs::remove_file(&release).unwrap();
+	(private_key.verifying_key(), bundle)
+}
+
+//		create_release_event													
+/// This function creates the data for a signed release event.
+fn create_release_event(key: &SigningKey, version: &str) -> String {
+	let payload   = json!({
+		"version": version,
+	}).to_string();
+	let signature = key.sign(payload.as_bytes()).to_string();
+	json!({
+		"payload":   payload,
+		"signature": signature,
+	}).to_string()
+}
+
+//		create_schedule															
+/// This function creates a maintenance schedule for testing.
+/// 
+/// If the schedule should be open, it has a window that lasts all day.
+/// Otherwise, it has a window that opens in several hours' time, so that it is
+/// guaranteed to be closed for the duration of the test.
+/// 
+fn create_schedule(open: bool, scope: MaintenanceScope) -> MaintenanceSchedule {
+	let hour         = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs().div_euclid(3_600);
+	let start        = u8::try_from((hour + 6).rem_euclid(24)).unwrap();
+	let end          = u8::try_from((hour + 7).rem_euclid(24)).unwrap();
+	let window       = if open {
+		MaintenanceWindow::daily(TimeOfDay::default(), TimeOfDay::default())
+	} else {
+		MaintenanceWindow::daily(TimeOfDay::new(start, 0).unwrap(), TimeOfDay::new(end, 0).unwrap())
+	};
+	let mut schedule = MaintenanceSchedule::new(vec![window], 0);
+	schedule.scope   = scope;
+	schedule
+}
+
 
 
 //		Tests																											
 
 //		Updater																	
+#[cfg(test)]
+mod critical_action {
+	use super::*;
+	
+	//		display																
+	#[test]
+	fn display() {
+		let named   = CriticalAction { id: 3, name: Some(s!("Import job")), started: Instant::now() };
+		let unnamed = CriticalAction { id: 4, name: None,                   started: Instant::now() };
+		assert_eq!(named.to_string(),   "Import job (#3, running for 0s)");
+		assert_eq!(unnamed.to_string(), "Unnamed action (#4, running for 0s)");
+	}
+}
+
+#[cfg(test)]
+mod http_settings {
+	use super::*;
+	
+	//		client_builder														
+	#[test]
+	fn client_builder() {
+		let settings = HttpSettings {
+			connect_timeout:   Some(Duration::from_secs(5)),
+			read_timeout:      Some(Duration::from_secs(30)),
+			proxy:             Some("http://proxy.example.com:3128".parse().unwrap()),
+			no_proxy:          Some(s!("localhost,.internal.example.com")),
+			root_certificates: vec![TEST_CA_CERTIFICATE.as_bytes().to_vec()],
+			user_agent:        Some(s!("test-app/1.0.0")),
+			headers:           vec![
+				(s!("X-Api-Key"), s!("secret")),
+				(s!("X-Tenant"),  s!("one")),
+				(s!("X-Tenant"),  s!("two")),
+			],
+			max_redirects:     Some(5),
+		};
+		let debug    = format!("{:?}", settings.client_builder().unwrap());
+		assert!(debug.contains("connect_timeout: 5s"));
+		assert!(debug.contains("proxy.example.com:3128"));
+		assert!(debug.contains(r#""user-agent": "test-app/1.0.0""#));
+		assert!(debug.contains(r#""x-api-key": "secret""#));
+		assert!(debug.contains(r#""x-tenant": "two""#));
+		assert!(!debug.contains(r#""x-tenant": "one""#));
+	}
+	#[test]
+	fn client_builder__default() {
+		let debug = format!("{:?}", HttpSettings::default().client_builder().unwrap());
+		assert!(!debug.contains("connect_timeout"));
+		assert!(!debug.contains("proxies"));
+		assert!(!debug.contains("user-agent"));
+	}
+	#[test]
+	fn client_builder__err_invalid_certificate() {
+		let settings = HttpSettings {
+			root_certificates: vec![b"Not a certificate".to_vec()],
+			..HttpSettings::default()
+		};
+		let err      = settings.client_builder().unwrap_err();
+		assert!(matches!(err, UpdaterError::InvalidHttpSettings(ref msg) if msg.starts_with("Root certificate: ")));
+	}
+	#[test]
+	fn client_builder__err_invalid_header_name() {
+		let settings = HttpSettings {
+			headers: vec![(s!("X Api Key"), s!("secret"))],
+			..HttpSettings::default()
+		};
+		let err      = settings.client_builder().unwrap_err();
+		assert_eq!(err,             UpdaterError::InvalidHttpSettings(s!(r#"Header name "X Api Key""#)));
+		assert_eq!(err.to_string(), r#"Invalid HTTP settings: Header name "X Api Key""#);
+	}
+	#[test]
+	fn client_builder__err_invalid_header_value() {
+		let settings = HttpSettings {
+			headers: vec![(s!("X-Api-Key"), s!("secret\n"))],
+			..HttpSettings::default()
+		};
+		assert_err_eq!(settings.client_builder(), UpdaterError::InvalidHttpSettings(s!(r#"Header value for "X-Api-Key""#)));
+	}
+	#[test]
+	fn client_builder__err_invalid_user_agent() {
+		let settings = HttpSettings {
+			user_agent: Some(s!("test-app\n")),
+			..HttpSettings::default()
+		};
+		assert_err_eq!(settings.client_builder(), UpdaterError::InvalidHttpSettings(s!("User agent")));
+	}
+	
+	//		build_client														
+	#[test]
+	fn build_client__err_invalid_settings() {
+		let settings = HttpSettings {
+			headers: vec![(s!(""), s!("secret"))],
+			..HttpSettings::default()
+		};
+		assert_err_eq!(settings.build_client(), UpdaterError::InvalidHttpSettings(s!(r#"Header name """#)));
+	}
+}
+
+#[cfg(test)]
+mod progress {
+	use super::*;
+	
+	//		measure																
+	#[test]
+	fn measure() {
+		assert_eq!(Progress::measure(0,     1_000, Duration::from_secs(1)), Progress::new(0,     1_000, None));
+		assert_eq!(Progress::measure(250,   1_000, Duration::from_secs(1)), Progress::new(250,   1_000, Some(Duration::from_secs(3))));
+		assert_eq!(Progress::measure(1_000, 1_000, Duration::from_secs(4)), Progress::new(1_000, 1_000, Some(Duration::ZERO)));
+		assert_eq!(Progress::measure(250,   0,     Duration::from_secs(1)), Progress::new(250,   0,     None));
+	}
+	
+	//		percent																
+	#[test]
+	fn percent() {
+		assert_eq!(Progress::new(0,     1_000, None).percent(), 0);
+		assert_eq!(Progress::new(505,   1_000, None).percent(), 50);
+		assert_eq!(Progress::new(1_000, 1_000, None).percent(), 100);
+		assert_eq!(Progress::new(2_000, 1_000, None).percent(), 100);
+		assert_eq!(Progress::new(0,     0,     None).percent(), 0);
+	}
+	
+	//		to_string															
+	#[test]
+	fn to_string() {
+		assert_eq!(Progress::new(250, 1_000, None).to_string(),                         "25%, 250 of 1000 bytes");
+		assert_eq!(Progress::new(250, 1_000, Some(Duration::from_secs(3))).to_string(), "25%, 250 of 1000 bytes, 3s remaining");
+		assert_eq!(Progress::new(250, 0,     None).to_string(),                         "250 bytes");
+	}
+}
+
+#[cfg(test)]
+mod progress_policy {
+	use super::*;
+	
+	//		is_due																
+	#[test]
+	fn is_due__on_percent_change() {
+		let policy = ProgressPolicy::OnPercentChange;
+		let last   = Progress::new(100, 1_000, None);
+		assert!(!policy.is_due(&last, Duration::from_secs(5), &Progress::new(109,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::ZERO,         &Progress::new(110,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::ZERO,         &Progress::new(1_000, 1_000, None)));
+	}
+	#[test]
+	fn is_due__unknown_total() {
+		let last = Progress::new(100, 0, None);
+		assert!(!ProgressPolicy::OnPercentChange.is_due(&last, Duration::from_secs(5), &Progress::new(1_000, 0, None)));
+		assert!( ProgressPolicy::Bytes(250)     .is_due(&last, Duration::ZERO,         &Progress::new(350,   0, None)));
+	}
+	#[test]
+	fn is_due__interval() {
+		let policy = ProgressPolicy::Interval(Duration::from_millis(500));
+		let last   = Progress::new(100, 1_000, None);
+		assert!(!policy.is_due(&last, Duration::from_millis(499), &Progress::new(900,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::from_millis(500), &Progress::new(101,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::ZERO,             &Progress::new(1_000, 1_000, None)));
+	}
+	#[test]
+	fn is_due__bytes() {
+		let policy = ProgressPolicy::Bytes(250);
+		let last   = Progress::new(100, 1_000, None);
+		assert!(!policy.is_due(&last, Duration::from_secs(5), &Progress::new(349,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::ZERO,         &Progress::new(350,   1_000, None)));
+		assert!( policy.is_due(&last, Duration::ZERO,         &Progress::new(1_000, 1_000, None)));
+	}
+}
+
+#[cfg(test)]
+mod retry_policy {
+	use super::*;
+	
+	//		default																
+	#[test]
+	fn default() {
+		let policy = RetryPolicy::default();
+		assert_eq!(policy.max_retries,       3);
+		assert_eq!(policy.base_delay,        Duration::from_secs(1));
+		assert_eq!(policy.max_delay,         Duration::from_secs(60));
+		assert_eq!(policy.jitter,            Duration::from_millis(500));
+		assert_eq!(policy.fallback_interval, None);
+		assert_eq!(policy.primary_retry,     Duration::from_secs(300));
+	}
+	
+	//		delay																
+	#[test]
+	fn delay() {
+		let policy = RetryPolicy {
+			jitter: Duration::ZERO,
+			..RetryPolicy::default()
+		};
+		assert_eq!(policy.delay(0),   Duration::from_secs(1));
+		assert_eq!(policy.delay(1),   Duration::from_secs(2));
+		assert_eq!(policy.delay(5),   Duration::from_secs(32));
+		assert_eq!(policy.delay(6),   Duration::from_secs(60));
+		assert_eq!(policy.delay(100), Duration::from_secs(60));
+	}
+	#[test]
+	fn delay__jitter() {
+		let policy = RetryPolicy::default();
+		for _ in 0..100 {
+			let delay = policy.delay(2);
+			assert!(delay >= Duration::from_secs(4));
+			assert!(delay <= Duration::from_millis(4_500));
+		}
+	}
+}
+
+#[cfg(test)]
+mod token_source {
+	use super::*;
+	
+	//		token																
+	#[tokio::test]
+	async fn token__static() {
+		assert_ok_eq!(TokenSource::Static(s!("secret")).token().await, s!("secret"));
+	}
+	#[tokio::test]
+	async fn token__file() {
+		let dir  = tempdir().unwrap();
+		let path = dir.path().join("token");
+		fs::write(&path, "secret\n").unwrap();
+		assert_ok_eq!(TokenSource::File(path).token().await, s!("secret"));
+	}
+	#[tokio::test]
+	async fn token__err_missing_file() {
+		let dir  = tempdir().unwrap();
+		let path = dir.path().join("token");
+		let err  = TokenSource::File(path.clone()).token().await;
+		assert_err_eq!(err, UpdaterError::InvalidToken(format!("File {}: No such file or directory (os error 2)", path.display())));
+	}
+	#[tokio::test]
+	async fn token__err_missing_env() {
+		let err = TokenSource::Env(s!("PATCHIFY_TEST_MISSING_TOKEN")).token().await;
+		assert_err_eq!(err, UpdaterError::InvalidToken(s!("Environment variable PATCHIFY_TEST_MISSING_TOKEN: environment variable not found")));
+	}
+	#[tokio::test]
+	async fn token__err_empty() {
+		let err = TokenSource::Static(String::new()).token().await;
+		assert_err_eq!(err, UpdaterError::InvalidToken(s!("The token is empty")));
+	}
+	
+	//		fmt																	
+	#[test]
+	fn fmt() {
+		assert_eq!(format!("{:?}", TokenSource::Static(s!("secret"))),     "Static(..)");
+		assert_eq!(format!("{:?}", TokenSource::Env(s!("API_TOKEN"))),     r#"Env("API_TOKEN")"#);
+		assert_eq!(format!("{:?}", TokenSource::File(PathBuf::from("t"))), r#"File("t")"#);
+	}
+}
+
+#[cfg(test)]
+mod updater_error {
+	use super::*;
+	
+	//		is_retryable														
+	#[test]
+	fn is_retryable() {
+		let url = Url::parse("https://api.example.com/api/latest").unwrap();
+		assert!( UpdaterError::HttpError(url.clone(), StatusCode::INTERNAL_SERVER_ERROR).is_retryable());
+		assert!( UpdaterError::HttpError(url.clone(), StatusCode::SERVICE_UNAVAILABLE).is_retryable());
+		assert!( UpdaterError::HttpError(url.clone(), StatusCode::TOO_MANY_REQUESTS).is_retryable());
+		assert!( UpdaterError::HttpRequestFailed(url.clone(), s!("Connection refused")).is_retryable());
+		assert!( UpdaterError::MissingData(url.clone(), 10, 20).is_retryable());
+		assert!(!UpdaterError::HttpError(url.clone(), StatusCode::NOT_FOUND).is_retryable());
+		assert!(!UpdaterError::FailedHashVerification(Version::new(2, 3, 4)).is_retryable());
+		assert!(!UpdaterError::FailedSignatureVerification(url.clone()).is_retryable());
+		assert!(!UpdaterError::Forbidden(url.clone()).is_retryable());
+		assert!(!UpdaterError::InvalidBundle(PathBuf::from("test.bundle")).is_retryable());
+		assert!(!UpdaterError::InvalidHttpSettings(s!("User agent")).is_retryable());
+		assert!(!UpdaterError::InvalidSignature(url.clone(), s!("invalid")).is_retryable());
+		assert!(!UpdaterError::InvalidToken(s!("The token is empty")).is_retryable());
+		assert!(!UpdaterError::MissingSignature(url.clone()).is_retryable());
+		assert!(!UpdaterError::Unauthorized(url).is_retryable());
+		assert!(!UpdaterError::UnableToReadFile(PathBuf::from("latest"), s!("Permission denied")).is_retryable());
+		assert!(!UpdaterError::UpdateAlreadyUnderway(Status::Checking).is_retryable());
+	}
+	
+	//		warrants_failover													
+	#[test]
+	fn warrants_failover() {
+		let url = Url::parse("https://api.example.com/api/latest").unwrap();
+		assert!( UpdaterError::HttpError(url.clone(), StatusCode::INTERNAL_SERVER_ERROR).warrants_failover());
+		assert!( UpdaterError::HttpError(url.clone(), StatusCode::BAD_GATEWAY).warrants_failover());
+		assert!( UpdaterError::HttpRequestFailed(url.clone(), s!("Connection refused")).warrants_failover());
+		assert!(!UpdaterError::HttpError(url.clone(), StatusCode::NOT_FOUND).warrants_failover());
+		assert!(!UpdaterError::HttpError(url.clone(), StatusCode::TOO_MANY_REQUESTS).warrants_failover());
+		assert!(!UpdaterError::Unauthorized(url.clone()).warrants_failover());
+		assert!(!UpdaterError::FailedSignatureVerification(url.clone()).warrants_failover());
+		assert!(!UpdaterError::InvalidUrl(url, s!("latest")).warrants_failover());
+	}
+}
+
 #[cfg(test)]
 mod updater_construction {
 	use super::*;
@@ -113,20 +584,119 @@ mod updater_construction {
 		let updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              "https://api.example.com".parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              *EMPTY_PUBLIC_KEY,
 			check_on_startup: false,
 			check_interval:   Some(Duration::from_secs(60 * 60)),
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		assert_eq!(updater.actions.load(order),     0);
 		assert_eq!(updater.config.version,          Version::new(1, 0, 0));
 		assert_eq!(updater.config.api,              "https://api.example.com".parse().unwrap());
+		assert!(updater.config.mirrors.is_empty());
 		assert_eq!(updater.config.key,              *EMPTY_PUBLIC_KEY);
 		assert_eq!(updater.config.check_on_startup, false);
 		assert_eq!(updater.config.check_interval,   Some(Duration::from_secs(60 * 60)));
+		assert_eq!(updater.config.push_updates,     false);
+		assert_eq!(updater.config.update_policy,    UpdatePolicy::Automatic);
+		assert_eq!(updater.config.retry_policy,     RetryPolicy::default());
+		assert_eq!(updater.config.failure_cooldown, Duration::from_secs(60));
+		assert_eq!(updater.config.shutdown_timeout, Duration::from_secs(30));
+		assert_eq!(updater.config.progress_policy,  ProgressPolicy::OnPercentChange);
+		assert!(matches!(updater.config.restart_strategy, RestartStrategy::Exec));
+		assert_eq!(updater.config.systemd_notify,   false);
+		assert_none!(updater.config.consent.as_ref());
+		assert!(updater.declined.read().is_empty());
+		assert!(!updater.subscribed.load(order));
+		assert_none!(updater.config.source.as_ref());
+		assert_eq!(updater.config.http,             HttpSettings::default());
+		assert_none!(updater.shutdown.lock().as_ref());
+		assert!(updater.listeners.lock().is_empty());
+		assert!(Arc::ptr_eq(&updater.this.upgrade().unwrap(), &updater));
 		assert_eq!(updater.exe_path,                *MOCK_EXE.lock().borrow().as_ref().unwrap());
 		assert_eq!(*updater.status.read(),          Status::Idle);
 	}
 	#[tokio::test]
+	async fn new__custom_source() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let source: Arc<dyn UpdateSource> = Arc::new(DirectorySource::new("/srv/updates"));
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              "https://api.example.com".parse().unwrap(),
+			mirrors:          vec![],
+			source:           Some(Arc::clone(&source)),
+			http:             HttpSettings::default(),
+			token:            None,
+			key:              *EMPTY_PUBLIC_KEY,
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert!(Arc::ptr_eq(&updater.source, &source));
+	}
+	#[tokio::test]
+	async fn new__err_invalid_http_settings() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let err = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              "https://api.example.com".parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings {
+				user_agent: Some(s!("test-app\n")),
+				..HttpSettings::default()
+			},
+			token:            None,
+			key:              *EMPTY_PUBLIC_KEY,
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap_err();
+		assert_eq!(err, UpdaterError::InvalidHttpSettings(s!("User agent")));
+	}
+	#[tokio::test]
 	async fn new__err_unable_to_obtain_current_exe_path() {
 		//	No test for this at present, as it is difficult to simulate a failure.
 		//	It's also quite unlikely to occur.
@@ -137,24 +707,67 @@ mod updater_construction {
 mod updater_public {
 	use super::*;
 	
-	//		register_action														
+	//		check_for_updates													
 	#[tokio::test]
-	async fn register_action() {
-		let order   = Ordering::SeqCst;
+	async fn check_for_updates__complete_successful_process() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, _, _, _)  = setup_files();
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(payload)),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(payload.len()),
+			Ok(payload),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			*EMPTY_PUBLIC_KEY,
-			MockClient::new(),
+			public_key,
+			mock_client,
 		);
-		assert_eq!(updater.actions.load(order), 0);
-		assert_eq!(updater.register_action(),   Some(1));
-		assert_eq!(updater.actions.load(order), 1);
-		assert_eq!(updater.register_action(),   Some(2));
-		assert_eq!(updater.actions.load(order), 2);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::PendingRestart(version.clone()));
+		assert_eq!(updater.status(), Status::Restarting(version.clone()));
 	}
 	#[tokio::test]
-	async fn register_action__when_updating() {
+	async fn check_for_updates__update_check_already_underway() {
+		//	The lock needs to be maintained for the duration of the test. We call
+		//	setup_files() to ensure that the mock executable path is set.
+		let (_lock, _temp_dir, _, _, _) = setup_files();
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
@@ -162,54 +775,1228 @@ mod updater_public {
 			MockClient::new(),
 		);
 		updater.set_status(Status::Checking);
-		assert_some!(updater.register_action());
-		updater.set_status(Status::Downloading(Version::new(1, 0, 0), 25));
-		assert_some!(updater.register_action());
-		updater.set_status(Status::Installing(Version::new(1, 0, 0)));
-		assert_some!(updater.register_action());
-		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0)));
-		assert_none!(updater.register_action());
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::Checking)));
+		assert_eq!(updater.status(), Status::Checking);
+		updater.set_status(Status::Downloading(Version::new(1, 0, 0), Progress::new(80, 100, None)));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::Downloading(Version::new(1, 0, 0), Progress::new(80, 100, None)))));
+		assert_eq!(updater.status(), Status::Downloading(Version::new(1, 0, 0), Progress::new(80, 100, None)));
+		updater.set_status(Status::Installing(Version::new(1, 0, 0), Progress::default()));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::Installing(Version::new(1, 0, 0), Progress::default()))));
+		assert_eq!(updater.status(), Status::Installing(Version::new(1, 0, 0), Progress::default()));
+		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0), 1, None));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::PendingRestart(Version::new(1, 0, 0), 1, None))));
+		assert_eq!(updater.status(), Status::PendingRestart(Version::new(1, 0, 0), 1, None));
 		updater.set_status(Status::Restarting(Version::new(1, 0, 0)));
-		assert_none!(updater.register_action());
-		updater.set_status(Status::Idle);
-		assert_some!(updater.register_action());
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::Restarting(Version::new(1, 0, 0)))));
+		assert_eq!(updater.status(), Status::Restarting(Version::new(1, 0, 0)));
 	}
 	#[tokio::test]
-	async fn register_action__overflow() {
-		let order   = Ordering::SeqCst;
+	async fn check_for_updates__no_update_available() {
+		let url                         = "https://api.example.com/api/latest";
+		let json                        = json!({
+			"version": s!("1.0.0"),
+		}).to_string();
+		let (mock_response, public_key) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let mock_client = create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]);
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			*EMPTY_PUBLIC_KEY,
-			MockClient::new(),
+			public_key,
+			mock_client,
 		);
-		let _ = updater.actions.fetch_add(usize::MAX - 1, order);
-		assert_eq!(updater.actions.load(order), usize::MAX - 1);
-		assert_eq!(updater.register_action(),   Some(usize::MAX));
-		assert_eq!(updater.actions.load(order), usize::MAX);
-		assert_eq!(updater.register_action(),   None);
-		assert_eq!(updater.actions.load(order), usize::MAX);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpToDate(Version::new(1, 0, 0)));
+		assert_eq!(updater.status(), Status::Idle);
 	}
-	
-	//		deregister_action													
 	#[tokio::test]
-	async fn deregister_action() {
-		let order   = Ordering::SeqCst;
-		let updater = setup_safe_updater(
-			Version::new(1, 0, 0),
-			"https://api.example.com/api/",
-			*EMPTY_PUBLIC_KEY,
-			MockClient::new(),
-		);
-		assert_eq!(updater.actions.load(order), 0);
-		assert_eq!(updater.register_action(),   Some(1));
-		assert_eq!(updater.register_action(),   Some(2));
-		assert_eq!(updater.register_action(),   Some(3));
-		assert_eq!(updater.actions.load(order), 3);
-		assert_eq!(updater.deregister_action(), Some(2));
-		assert_eq!(updater.deregister_action(), Some(1));
-		assert_eq!(updater.actions.load(order), 1);
-		assert_eq!(updater.deregister_action(), Some(0));
+	async fn check_for_updates__download_failed() {
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(payload)),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			//	Intentionally-incorrect content type, to make the process fail
+			Some("text/plain"),
+			Some(payload.len()),
+			Ok(payload),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UnexpectedContentType(
+			url2.parse().unwrap(),
+			s!("text/plain"),
+			s!("application/octet-stream"),
+		)));
+		assert_eq!(updater.status(), Status::Failed(version.clone(), Box::new(UpdaterError::UnexpectedContentType(
+			url2.parse().unwrap(),
+			s!("text/plain"),
+			s!("application/octet-stream"),
+		))));
+	}
+	#[tokio::test]
+	async fn check_for_updates__download_partial() {
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(payload)),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			//	Intentionally-incorrect content length, to make the process fail
+			Some(payload.len() * 2),
+			Ok(payload),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::MissingData(
+			url2.parse().unwrap(),
+			payload.len(),
+			payload.len() * 2,
+		)));
+		assert_eq!(updater.status(), Status::Failed(version.clone(), Box::new(UpdaterError::MissingData(
+			url2.parse().unwrap(),
+			payload.len(),
+			payload.len() * 2,
+		))));
+	}
+	#[tokio::test]
+	async fn check_for_updates__download_full() {
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			//	Intentionally-incorrect hash, to make the process fail
+			"hash":    hex::encode(Sha256::digest("Some other payload")),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(payload.len()),
+			Ok(payload),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::FailedHashVerification(version.clone())));
+		assert_eq!(updater.status(), Status::Failed(version.clone(), Box::new(UpdaterError::FailedHashVerification(version.clone()))));
+	}
+	#[tokio::test]
+	async fn check_for_updates__failure_cooldown() {
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/hashes/2.3.4";
+		let json                         = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url2, Err(MockError::default())),
+		]);
+		let updater     = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				public_key,
+				mock_client,
+			);
+			updater.config.failure_cooldown = Duration::from_millis(10);
+			updater.this                    = Weak::clone(this);
+			updater
+		});
+		let err = UpdaterError::HttpRequestFailed(url2.parse().unwrap(), s!("Mocked Reqwest error"));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(err.clone()));
+		assert_eq!(updater.status(),                  Status::Failed(Version::new(2, 3, 4), Box::new(err)));
+		//	The cool-down happens in the background, so wait for it to finish
+		while updater.status() != Status::Idle {
+			sleep(Duration::from_millis(10)).await;
+		}
+		assert_eq!(updater.status(),                  Status::Idle);
+	}
+	#[tokio::test]
+	async fn check_for_updates__install_failed() {
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(payload)),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(payload.len()),
+			Ok(payload),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		assert_eq!(updater.status(), Status::Idle);
+		assert!(matches!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UnableToRenameCurrentExe(_, _))));
+		//	We haven't set up the test files, so the installation will fail, which
+		//	is what we want here, so that we can check the status is correct
+		assert!(matches!(
+			updater.status(),
+			Status::Failed(ref failed, ref err) if *failed == version && matches!(**err, UpdaterError::UnableToRenameCurrentExe(_, _))
+		));
+	}
+	#[tokio::test]
+	async fn check_for_updates__restart_blocked() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, _, _, _)  = setup_files();
+		let version                      = Version::new(2, 3, 4);
+		let private_key                  = generate_keypair().0;
+		let url1                         = "https://api.example.com/api/latest";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let url3                         = "https://api.example.com/api/hashes/2.3.4";
+		let payload                      = b"Test payload";
+		let json1                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let json2                        = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(payload)),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json1.len()),
+			Ok(&json1),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(payload.len()),
+			Ok(payload),
+		);
+		let (mock_response3, _public_key) = create_mock_response(
+			url3,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json2.len()),
+			Ok(&json2),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mock_client = create_mock_client(vec![
+			(url1, Ok(mock_response1)),
+			(url3, Ok(mock_response3)),
+			(url2, Ok(mock_response2)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		assert_eq!(updater.status(),          Status::Idle);
+		assert_eq!(updater.register_action(), Some(1));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::PendingRestart(version.clone()));
+		//	We've registered a critical action, so the installation will be blocked,
+		//	which is what we want here, so that we can check the status is correct
+		assert_eq!(updater.status(),          Status::PendingRestart(version.clone(), 1, None));
+	}
+	#[tokio::test]
+	async fn check_for_updates__restart_failed() {
+		//	No test for this at present, as it is difficult to simulate a failure
+	}
+	#[tokio::test]
+	async fn check_for_updates__notify_only() {
+		let (public_key, mock_client) = create_update_client(0..1);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_none!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn check_for_updates__notify_only_newer_release() {
+		let private_key = generate_keypair().0;
+		let url         = "https://api.example.com/api/latest";
+		let responses   = ["2.3.4", "3.0.0", "3.0.0"].into_iter().map(|version| {
+			let json = json!({
+				"version": version,
+			}).to_string();
+			(url, Ok(create_mock_response(
+				url,
+				StatusCode::OK,
+				Some("application/json"),
+				Some(json.len()),
+				Ok(&json),
+				&ResponseSignature::GenerateUsing(private_key.clone()),
+			).0))
+		}).collect();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			private_key.verifying_key(),
+			create_mock_client(responses),
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpdateAvailable(Version::new(3, 0, 0)));
+		assert_eq!(updater.status(),                  Status::UpdateAvailable(Version::new(3, 0, 0)));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpdateAvailable(Version::new(3, 0, 0)));
+		assert_eq!(updater.status(),                  Status::UpdateAvailable(Version::new(3, 0, 0)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__install_without_restart_held() {
+		let private_key = generate_keypair().0;
+		let url         = "https://api.example.com/api/latest";
+		let json        = json!({
+			"version": s!("3.0.0"),
+		}).to_string();
+		let (mock_response, public_key) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::GenerateUsing(private_key),
+		);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		updater.config.update_policy = UpdatePolicy::InstallWithoutRestart;
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		//	The installed update is kept, so that the original can be restored
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::PendingRestart(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Installed(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__download_only_held() {
+		let (public_key, mock_client) = create_update_client(0..1);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Downloaded(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__download_only() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Downloaded(Version::new(2, 3, 4)));
+		let staged_path = updater.staged.lock().as_ref().map(|staged| staged.1.clone()).unwrap();
+		assert_eq!(fs::read(staged_path).unwrap(), b"Test payload");
+	}
+	#[tokio::test]
+	async fn check_for_updates__install_without_restart() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, _, _) = setup_files();
+		let (public_key, mock_client)          = create_update_client(0..3);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::InstallWithoutRestart;
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::PendingRestart(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(fs::read(&exe_path).unwrap(),      b"Test payload");
+	}
+	#[tokio::test]
+	async fn check_for_updates__maintenance_window_open() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, _, _) = setup_files();
+		let (public_key, mock_client)          = create_update_client(0..3);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::InstallWithoutRestart;
+		updater.config.maintenance   = Some(create_schedule(true, MaintenanceScope::InstallAndRestart));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::PendingRestart(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(fs::read(&exe_path).unwrap(),      b"Test payload");
+	}
+	#[tokio::test]
+	async fn check_for_updates__maintenance_window_closed() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let schedule                  = create_schedule(false, MaintenanceScope::InstallAndRestart);
+		let opening                   = schedule.next_opening(SystemTime::now()).unwrap();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.maintenance = Some(schedule);
+		//	The update is downloaded, but not installed
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::AwaitingWindow(Version::new(2, 3, 4), opening));
+		assert_eq!(updater.status(),                  Status::AwaitingWindow(Version::new(2, 3, 4), ConsentStep::Install, opening));
+		assert_some!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn check_for_updates__maintenance_window_closed_for_restart() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, _, _) = setup_files();
+		let (public_key, mock_client)          = create_update_client(0..3);
+		let schedule                           = create_schedule(false, MaintenanceScope::Restart);
+		let opening                            = schedule.next_opening(SystemTime::now()).unwrap();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.maintenance = Some(schedule);
+		//	The update is installed, but the application is not restarted
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::AwaitingWindow(Version::new(2, 3, 4), opening));
+		assert_eq!(updater.status(),                  Status::AwaitingWindow(Version::new(2, 3, 4), ConsentStep::Restart, opening));
+		assert_eq!(fs::read(&exe_path).unwrap(),      b"Test payload");
+		assert!(take_calls().is_empty());
+	}
+	#[tokio::test]
+	async fn check_for_updates__consent_approved() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let consent                   = Arc::new(MockConsent::new([ConsentDecision::Approve]));
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		updater.config.consent       = Some(Arc::<MockConsent>::clone(&consent));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(*consent.requests.lock(),          vec![(ConsentStep::Download, Version::new(2, 3, 4))]);
+	}
+	#[tokio::test]
+	async fn check_for_updates__consent_deferred() {
+		let (public_key, mock_client) = create_update_client(0..1);
+		let delay                     = Duration::from_secs(60 * 60);
+		let consent                   = Arc::new(MockConsent::new([ConsentDecision::Defer(delay)]));
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.consent = Some(Arc::<MockConsent>::clone(&consent));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Deferred(Version::new(2, 3, 4), delay));
+		assert_eq!(updater.status(),                  Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_none!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn check_for_updates__consent_deferred_then_resumed() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let delay                     = Duration::from_millis(10);
+		let consent                   = Arc::new(MockConsent::new([ConsentDecision::Defer(delay)]));
+		let updater                   = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				public_key,
+				mock_client,
+			);
+			updater.config.update_policy = UpdatePolicy::DownloadOnly;
+			updater.config.consent       = Some(Arc::<MockConsent>::clone(&consent));
+			updater.this                 = Weak::clone(this);
+			updater
+		});
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Deferred(Version::new(2, 3, 4), delay));
+		assert_eq!(updater.status(),                  Status::UpdateAvailable(Version::new(2, 3, 4)));
+		//	The deferred step happens in the background, so wait for it to finish
+		while updater.status() != Status::Downloaded(Version::new(2, 3, 4)) {
+			sleep(delay).await;
+		}
+		assert_eq!(*consent.requests.lock(), vec![
+			(ConsentStep::Download, Version::new(2, 3, 4)),
+			(ConsentStep::Download, Version::new(2, 3, 4)),
+		]);
+		assert_some!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn check_for_updates__consent_declined() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let consent                   = Arc::new(MockConsent::new([ConsentDecision::Approve, ConsentDecision::Decline]));
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.consent = Some(Arc::<MockConsent>::clone(&consent));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Declined(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Idle);
+		assert_eq!(*consent.requests.lock(),          vec![
+			(ConsentStep::Download, Version::new(2, 3, 4)),
+			(ConsentStep::Install,  Version::new(2, 3, 4)),
+		]);
+		assert_none!(updater.staged.lock().as_ref());
+		assert!(updater.declined.read().contains(&Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__declined_version() {
+		let (public_key, mock_client) = create_update_client(0..1);
+		let consent                   = Arc::new(MockConsent::default());
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.config.consent = Some(Arc::<MockConsent>::clone(&consent));
+		let _inserted          = updater.declined.write().insert(Version::new(2, 3, 4));
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Declined(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                  Status::Idle);
+		assert!(consent.requests.lock().is_empty());
+	}
+	
+	//		install_bundle														
+	#[tokio::test]
+	async fn install_bundle() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, temp_dir, exe_path, _, _) = setup_files();
+		let (public_key, bundle)              = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		updater.config.update_policy = UpdatePolicy::InstallWithoutRestart;
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::PendingRestart(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                      Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(fs::read(&exe_path).unwrap(),          b"Bundled payload");
+	}
+	#[tokio::test]
+	async fn install_bundle__download_only() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, bundle) = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),                      Status::Downloaded(Version::new(2, 3, 4)));
+		let staged_path = updater.staged.lock().as_ref().map(|staged| staged.1.clone()).unwrap();
+		assert_eq!(fs::read(staged_path).unwrap(),        b"Bundled payload");
+	}
+	#[tokio::test]
+	async fn install_bundle__up_to_date() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, bundle) = create_bundle(temp_dir.path(), "test.bundle", "1.0.0", b"Bundled payload");
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::UpToDate(Version::new(1, 0, 0)));
+		assert_eq!(updater.status(),                      Status::Idle);
+	}
+	#[tokio::test]
+	async fn install_bundle__err_missing() {
+		let temp_dir = tempdir().unwrap();
+		let bundle   = temp_dir.path().join("missing.bundle");
+		let updater  = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			MockClient::new(),
+		);
+		let err = UpdaterError::UnableToReadBundle(bundle.clone(), s!("No such file or directory (os error 2)"));
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Failed(err.clone()));
+		assert_eq!(updater.status(),                      Status::Idle);
+		assert_eq!(err.to_string(), format!("Unable to read update bundle {bundle:?}: No such file or directory (os error 2)"));
+	}
+	#[tokio::test]
+	async fn install_bundle__err_invalid_bundle() {
+		let temp_dir = tempdir().unwrap();
+		let bundle   = temp_dir.path().join("test.bundle");
+		fs::write(&bundle, b"Not a bundle").unwrap();
+		let updater  = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			MockClient::new(),
+		);
+		let err = UpdaterError::InvalidBundle(bundle.clone());
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Failed(err.clone()));
+		assert_eq!(updater.status(),                      Status::Idle);
+		assert_eq!(err.to_string(), format!("The update bundle {bundle:?} is not in the expected format"));
+	}
+	#[tokio::test]
+	async fn install_bundle__err_failed_signature() {
+		let temp_dir    = tempdir().unwrap();
+		let (_, bundle) = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let updater     = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			MockClient::new(),
+		);
+		let url = Url::from_file_path(fs::canonicalize(&bundle).unwrap()).unwrap();
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Failed(UpdaterError::FailedSignatureVerification(url)));
+		assert_eq!(updater.status(),                      Status::Idle);
+	}
+	#[tokio::test]
+	async fn install_bundle__err_failed_hash() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, bundle) = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let mut data             = fs::read(&bundle).unwrap();
+		*data.last_mut().unwrap() = b'!';
+		fs::write(&bundle, data).unwrap();
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		let version = Version::new(2, 3, 4);
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Failed(UpdaterError::FailedHashVerification(version.clone())));
+		assert_eq!(updater.status(), Status::Failed(version.clone(), Box::new(UpdaterError::FailedHashVerification(version))));
+		assert_none!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn install_bundle__err_already_underway() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, bundle) = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		updater.set_status(Status::Checking);
+		assert_eq!(updater.install_bundle(&bundle).await, CheckOutcome::Failed(UpdaterError::UpdateAlreadyUnderway(Status::Checking)));
+	}
+	
+	//		download															
+	#[tokio::test]
+	async fn download() {
+		let (public_key, mock_client) = create_update_client(1..3);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
+		);
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_ok!(updater.download().await);
+		assert_eq!(updater.status(), Status::Downloaded(Version::new(2, 3, 4)));
+		let staged_path = updater.staged.lock().as_ref().map(|staged| staged.1.clone()).unwrap();
+		assert_eq!(fs::read(staged_path).unwrap(), b"Test payload");
+	}
+	#[tokio::test]
+	async fn download__err_failed() {
+		let url1                         = "https://api.example.com/api/hashes/2.3.4";
+		let url2                         = "https://api.example.com/api/releases/2.3.4";
+		let json                         = json!({
+			"version": s!("2.3.4"),
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		}).to_string();
+		let (mock_response1, public_key) = create_mock_response(
+			url1,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let mock_response2 = create_mock_binary_response(
+			url2,
+			StatusCode::OK,
+			//	Intentionally-incorrect content type, to make the process fail
+			Some("text/plain"),
+			Some(12),
+			Ok(b"Test payload"),
+		);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![(url1, Ok(mock_response1)), (url2, Ok(mock_response2))]),
+		);
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_err_eq!(updater.download().await, UpdaterError::UnexpectedContentType(
+			url2.parse().unwrap(),
+			s!("text/plain"),
+			s!("application/octet-stream"),
+		));
+		assert_eq!(updater.status(), Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_none!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn download__err_invalid_status() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_err_eq!(updater.download().await, UpdaterError::InvalidStatus(Status::Idle));
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		assert_err_eq!(updater.download().await, UpdaterError::InvalidStatus(Status::Downloaded(Version::new(2, 3, 4))));
+		assert_eq!(updater.status(),             Status::Downloaded(Version::new(2, 3, 4)));
+	}
+	
+	//		install																
+	#[tokio::test]
+	async fn install() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, old_path, new_path) = setup_files();
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		*updater.staged.lock() = Some((tempdir().unwrap(), new_path));
+		assert_ok!(updater.install().await);
+		assert_eq!(updater.status(),             Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(fs::read(&exe_path).unwrap(), b"update contents");
+		assert_eq!(fs::read(&old_path).unwrap(), b"mock_exe contents");
+		assert_none!(updater.staged.lock().as_ref());
+	}
+	#[tokio::test]
+	async fn install__awaiting_window() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, _, new_path) = setup_files();
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.set_status(Status::AwaitingWindow(Version::new(2, 3, 4), ConsentStep::Install, SystemTime::now()));
+		*updater.staged.lock() = Some((tempdir().unwrap(), new_path));
+		assert_ok!(updater.install().await);
+		assert_eq!(updater.status(),             Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(fs::read(&exe_path).unwrap(), b"update contents");
+		//	Restarting cannot happen while installation is being held back
+		updater.set_status(Status::AwaitingWindow(Version::new(2, 3, 4), ConsentStep::Install, UNIX_EPOCH));
+		assert_err_eq!(updater.restart(), UpdaterError::InvalidStatus(Status::AwaitingWindow(Version::new(2, 3, 4), ConsentStep::Install, UNIX_EPOCH)));
+	}
+	#[tokio::test]
+	async fn install__err_invalid_status() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_err_eq!(updater.install().await, UpdaterError::InvalidStatus(Status::Idle));
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_err_eq!(updater.install().await, UpdaterError::InvalidStatus(Status::UpdateAvailable(Version::new(2, 3, 4))));
+	}
+	#[tokio::test]
+	async fn install__err_missing_download() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		assert_err_eq!(updater.install().await, UpdaterError::MissingDownload(Version::new(2, 3, 4)));
+		assert_eq!(updater.status(),            Status::UpdateAvailable(Version::new(2, 3, 4)));
+	}
+	
+	//		restart																
+	#[tokio::test]
+	async fn restart() {
+		//	The lock needs to be maintained for the duration of the test. We call
+		//	setup_files() to ensure that the mock executable path is set.
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		//	This will call FakeCommand::new(), which will return a wrapper around a
+		//	MockCommand that is already set up with the necessary expectations.
+		assert_ok!(updater.restart());
+		assert_eq!(updater.status(), Status::Restarting(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn restart__blocked() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_eq!(updater.register_action(), Some(1));
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		assert_ok!(updater.restart());
+		assert_eq!(updater.status(),          Status::PendingRestart(Version::new(2, 3, 4), 1, None));
+	}
+	#[tokio::test]
+	async fn restart__drain_force_restart() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.config.drain_policy = DrainPolicy {
+				report_interval: Duration::from_millis(10),
+				..DrainPolicy::new(Duration::from_millis(50), DrainAction::ForceRestart)
+			};
+			updater.this                = Weak::clone(this);
+			updater
+		});
+		let events  = updater.subscribe_events();
+		let _guard  = updater.guard_action(Some("Stuck job")).unwrap();
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		drop(take_calls());
+		assert_ok!(updater.restart());
+		assert_eq!(updater.status(), Status::PendingRestart(Version::new(2, 3, 4), 1, Some(Duration::from_millis(50))));
+		assert!(updater.wait_for_status(&Status::Restarting(Version::new(2, 3, 4)), Some(Duration::from_secs(5))).await);
+		assert!(take_calls().contains(&ProcessCall::Exit(1)));
+		//	The time remaining is reported as the deadline approaches
+		let reported = events.drain().filter_map(|status| match status {
+			Status::PendingRestart(_, _, remaining) => remaining,
+			Status::Idle                 |
+			Status::Checking             |
+			Status::UpdateAvailable(_)   |
+			Status::Downloading(_, _)    |
+			Status::Verifying(_)         |
+			Status::Downloaded(_)        |
+			Status::Installing(_, _)     |
+			Status::Installed(_)         |
+			Status::AwaitingWindow(_, _, _) |
+			Status::Restarting(_)        |
+			Status::RollingBack(_)       |
+			Status::Failed(_, _)         => None,
+		}).collect::<Vec<_>>();
+		assert!(reported.len() > 2);
+		assert!(reported.is_sorted_by(|earlier, later| earlier >= later));
+		assert_eq!(reported.last(), Some(&Duration::ZERO));
+	}
+	#[tokio::test]
+	async fn restart__drain_rollback() {
+		let (_lock, _temp_dir, exe_path, old_path, new_path) = setup_files();
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.config.drain_policy = DrainPolicy {
+				report_interval: Duration::from_millis(10),
+				..DrainPolicy::new(Duration::from_millis(50), DrainAction::Rollback)
+			};
+			updater.this                = Weak::clone(this);
+			updater
+		});
+		assert_ok!(updater.replace_executable(&Version::new(2, 3, 4), &new_path).await);
+		let _guard  = updater.guard_action(None).unwrap();
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		let events  = updater.subscribe_events();
+		assert_ok!(updater.restart());
+		let failed  = Status::Failed(Version::new(2, 3, 4), Box::new(UpdaterError::DrainTimedOut(Version::new(2, 3, 4), 1)));
+		assert!(updater.wait_for_status(&failed, Some(Duration::from_secs(5))).await);
+		assert!(!old_path.exists());
+		assert_eq!(fs::read_to_string(exe_path).unwrap(), "mock_exe contents");
+		//	The failure is only reported once the rollback has finished
+		let finished = events.drain().skip_while(|status| *status != Status::RollingBack(Version::new(2, 3, 4))).collect::<Vec<_>>();
+		assert_eq!(finished, vec![Status::RollingBack(Version::new(2, 3, 4)), failed.clone()]);
+		//	Critical actions can start again, and finishing the stuck one does not
+		//	trigger a restart
+		let _new_guard = updater.guard_action(None).unwrap();
+		assert_eq!(updater.status(), failed);
+	}
+	#[tokio::test]
+	async fn restart__drain_escalate() {
+		let escalated = Arc::new(Mutex::new(vec![]));
+		let recorder  = Arc::clone(&escalated);
+		let updater   = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.config.drain_policy = DrainPolicy {
+				report_interval: Duration::from_millis(10),
+				..DrainPolicy::new(Duration::from_millis(50), DrainAction::Escalate(Arc::new(move |version: &Version, actions: &[CriticalAction]| {
+					recorder.lock().push((version.clone(), actions.iter().map(|action| action.name.clone()).collect::<Vec<_>>()));
+				})))
+			};
+			updater.this                = Weak::clone(this);
+			updater
+		});
+		let guard     = updater.guard_action(Some("Stuck job")).unwrap();
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		assert_ok!(updater.restart());
+		let waiting   = Status::PendingRestart(Version::new(2, 3, 4), 1, Some(Duration::ZERO));
+		assert!(updater.wait_for_status(&waiting, Some(Duration::from_secs(5))).await);
+		for _ in 0..100 {
+			if !escalated.lock().is_empty() {
+				break;
+			}
+			sleep(Duration::from_millis(10)).await;
+		}
+		assert_eq!(*escalated.lock(), vec![(Version::new(2, 3, 4), vec![Some(s!("Stuck job"))])]);
+		//	The updater keeps waiting, and restarts once the action finishes
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		drop(guard);
+		assert_eq!(updater.status(), Status::Restarting(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn restart__err_invalid_status() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_err_eq!(updater.restart(), UpdaterError::InvalidStatus(Status::Idle));
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		assert_err_eq!(updater.restart(), UpdaterError::InvalidStatus(Status::Downloaded(Version::new(2, 3, 4))));
+	}
+	
+	//		register_action														
+	#[tokio::test]
+	async fn register_action() {
+		let order   = Ordering::SeqCst;
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_eq!(updater.actions.load(order), 0);
+		assert_eq!(updater.register_action(),   Some(1));
+		assert_eq!(updater.actions.load(order), 1);
+		assert_eq!(updater.register_action(),   Some(2));
+		assert_eq!(updater.actions.load(order), 2);
+	}
+	#[tokio::test]
+	async fn register_action__when_updating() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.set_status(Status::Checking);
+		assert_some!(updater.register_action());
+		updater.set_status(Status::UpdateAvailable(Version::new(1, 0, 0)));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::Downloading(Version::new(1, 0, 0), Progress::new(25, 100, None)));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::Downloaded(Version::new(1, 0, 0)));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::Installing(Version::new(1, 0, 0), Progress::default()));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::Installed(Version::new(1, 0, 0)));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::Failed(Version::new(1, 0, 0), Box::new(UpdaterError::MissingDownload(Version::new(1, 0, 0)))));
+		assert_some!(updater.register_action());
+		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0), 1, None));
+		assert_none!(updater.register_action());
+		updater.set_status(Status::Restarting(Version::new(1, 0, 0)));
+		assert_none!(updater.register_action());
+		updater.set_status(Status::Idle);
+		assert_some!(updater.register_action());
+	}
+	#[tokio::test]
+	async fn register_action__overflow() {
+		let order   = Ordering::SeqCst;
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let _ = updater.actions.fetch_add(usize::MAX - 1, order);
+		assert_eq!(updater.actions.load(order), usize::MAX - 1);
+		assert_eq!(updater.register_action(),   Some(usize::MAX));
+		assert_eq!(updater.actions.load(order), usize::MAX);
+		assert_eq!(updater.register_action(),   None);
+		assert_eq!(updater.actions.load(order), usize::MAX);
+	}
+	
+	//		guard_action														
+	#[tokio::test]
+	async fn guard_action() {
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.this = Weak::clone(this);
+			updater
+		});
+		let first   = updater.guard_action(Some("Import job")).unwrap();
+		let second  = updater.guard_action(None).unwrap();
+		assert_eq!(updater.actions.load(Ordering::SeqCst), 2);
+		let actions = updater.actions();
+		assert_eq!(actions.len(), 2);
+		assert_eq!(actions[0].id,   first.id());
+		assert_eq!(actions[0].name, Some(s!("Import job")));
+		assert_eq!(actions[1].id,   second.id());
+		assert_eq!(actions[1].name, None);
+		drop(first);
+		assert_eq!(updater.actions.load(Ordering::SeqCst), 1);
+		assert_eq!(updater.actions().iter().map(|action| action.id).collect::<Vec<_>>(), vec![second.id()]);
+		drop(second);
+		assert_eq!(updater.actions.load(Ordering::SeqCst), 0);
+		assert!(updater.actions().is_empty());
+	}
+	#[tokio::test]
+	async fn guard_action__early_return() {
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.this = Weak::clone(this);
+			updater
+		});
+		let job     = |fail: bool| -> Result<(), ()> {
+			let _guard = updater.guard_action(Some("Job")).unwrap();
+			if fail {
+				return Err(());
+			}
+			Ok(())
+		};
+		assert_err!(job(true));
+		assert_eq!(updater.actions.load(Ordering::SeqCst), 0);
+		assert!(updater.actions().is_empty());
+	}
+	#[tokio::test]
+	async fn guard_action__when_restart_pending() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.this = Weak::clone(this);
+			updater
+		});
+		let guard   = updater.guard_action(Some("Export job")).unwrap();
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		assert_ok!(updater.restart());
+		assert_eq!(updater.status(), Status::PendingRestart(Version::new(2, 3, 4), 1, None));
+		assert_none!(updater.guard_action(Some("New job")));
+		//	Dropping the last guard triggers the restart
+		drop(take_calls());
+		drop(guard);
+		assert_eq!(updater.status(), Status::Restarting(Version::new(2, 3, 4)));
+		assert!(take_calls().contains(&ProcessCall::Exit(1)));
+	}
+	
+	//		deregister_action													
+	#[tokio::test]
+	async fn deregister_action() {
+		let order   = Ordering::SeqCst;
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		assert_eq!(updater.actions.load(order), 0);
+		assert_eq!(updater.register_action(),   Some(1));
+		assert_eq!(updater.register_action(),   Some(2));
+		assert_eq!(updater.register_action(),   Some(3));
+		assert_eq!(updater.actions.load(order), 3);
+		assert_eq!(updater.deregister_action(), Some(2));
+		assert_eq!(updater.deregister_action(), Some(1));
+		assert_eq!(updater.actions.load(order), 1);
+		assert_eq!(updater.deregister_action(), Some(0));
 		assert_eq!(updater.actions.load(order), 0);
 	}
 	#[tokio::test]
@@ -223,11 +2010,11 @@ mod updater_public {
 		let _ = updater.actions.fetch_add(10, Ordering::SeqCst);
 		updater.set_status(Status::Checking);
 		assert_some!(updater.deregister_action());
-		updater.set_status(Status::Downloading(Version::new(1, 0, 0), 25));
+		updater.set_status(Status::Downloading(Version::new(1, 0, 0), Progress::new(25, 100, None)));
 		assert_some!(updater.deregister_action());
-		updater.set_status(Status::Installing(Version::new(1, 0, 0)));
+		updater.set_status(Status::Installing(Version::new(1, 0, 0), Progress::default()));
 		assert_some!(updater.deregister_action());
-		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0)));
+		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0), 1, None));
 		assert_some!(updater.deregister_action());
 		updater.set_status(Status::Restarting(Version::new(1, 0, 0)));
 		assert_some!(updater.deregister_action());
@@ -243,17 +2030,60 @@ mod updater_public {
 			MockClient::new(),
 		);
 		let _ = updater.actions.fetch_add(3, Ordering::SeqCst);
-		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0)));
+		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0), 3, None));
 		assert_eq!(updater.deregister_action(), Some(2));
-		assert_eq!(updater.status(),            Status::PendingRestart(Version::new(1, 0, 0)));
+		assert_eq!(updater.status(),            Status::PendingRestart(Version::new(1, 0, 0), 2, None));
 		assert_eq!(updater.deregister_action(), Some(1));
-		assert_eq!(updater.status(),            Status::PendingRestart(Version::new(1, 0, 0)));
+		assert_eq!(updater.status(),            Status::PendingRestart(Version::new(1, 0, 0), 1, None));
 		assert_eq!(updater.deregister_action(), Some(0));
 		assert_eq!(updater.status(),            Status::Restarting(Version::new(1, 0, 0)));
-		//	Due to the status change, the restart() method will now be called. This
+		//	Due to the status change, the relaunch() method will now be called. This
 		//	will call FakeCommand::new(), which will return a wrapper around a
 		//	MockCommand that is already set up with the necessary expectations.
 	}
+	#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+	async fn deregister_action__at_drain_deadline() {
+		let restarts    = Arc::new(AtomicUsize::new(0));
+		let recorder    = Arc::clone(&restarts);
+		let updater     = Arc::new({
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.config.drain_policy     = DrainPolicy::new(Duration::from_secs(60), DrainAction::ForceRestart);
+			updater.config.restart_strategy = RestartStrategy::Callback(Arc::new(move |_path: &Path| {
+				_ = recorder.fetch_add(1, Ordering::SeqCst);
+			}));
+			updater
+		});
+		for attempt in 1..=1_000 {
+			updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+			assert_eq!(updater.register_action(), Some(1));
+			assert_ok!(updater.restart());
+			//	The last critical action finishes just as the deadline passes
+			let barrier   = Arc::new(Barrier::new(2));
+			let finishing = {
+				let (this, gate) = (Arc::clone(&updater), Arc::clone(&barrier));
+				spawn_blocking(move || {
+					_ = gate.wait();
+					this.deregister_action()
+				})
+			};
+			let expiring  = {
+				let (this, gate) = (Arc::clone(&updater), Arc::clone(&barrier));
+				spawn_blocking(move || {
+					_ = gate.wait();
+					Handle::current().block_on(this.drain_expired(Version::new(2, 3, 4)));
+				})
+			};
+			assert_some_eq!(finishing.await.unwrap(), 0);
+			expiring.await.unwrap();
+			assert_eq!(updater.status(), Status::Restarting(Version::new(2, 3, 4)));
+			assert_eq!(restarts.load(Ordering::SeqCst), attempt);
+		}
+	}
 	#[tokio::test]
 	async fn deregister_action__underflow() {
 		let order   = Ordering::SeqCst;
@@ -272,6 +2102,85 @@ mod updater_public {
 		assert_eq!(updater.actions.load(order), 0);
 	}
 	
+	//		on_shutdown															
+	#[tokio::test]
+	async fn on_shutdown() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let calls   = Arc::new(AtomicUsize::new(0));
+		let counter = Arc::clone(&calls);
+		assert_none!(updater.shutdown.lock().as_ref());
+		updater.on_shutdown(move || async move {
+			let _previous = counter.fetch_add(1, Ordering::SeqCst);
+		});
+		let hook    = updater.shutdown.lock().take().unwrap();
+		(hook.0)().await;
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+	}
+	
+	//		register_listener													
+	#[tokio::test]
+	async fn register_listener() {
+		let updater  = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let http     = TcpListener::bind("127.0.0.1:0").unwrap();
+		let admin    = TcpListener::bind("127.0.0.1:0").unwrap();
+		assert_ok!(updater.register_listener("http",  &http));
+		assert_ok!(updater.register_listener("admin", &admin));
+		assert_ok!(updater.register_listener("http",  &http));
+		let names    = updater.listeners.lock().iter().map(|entry| entry.0.clone()).collect::<Vec<_>>();
+		assert_eq!(names, vec![s!("admin"), s!("http")]);
+		//	The registered sockets are duplicates, so the originals can still be
+		//	used independently
+		drop(http);
+		assert_eq!(updater.listeners.lock().len(), 2);
+	}
+	#[tokio::test]
+	async fn register_listener__err_invalid_name() {
+		let updater  = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+		assert_err_eq!(updater.register_listener("",          &listener), UpdaterError::InvalidListenerName(s!("")));
+		assert_err_eq!(updater.register_listener("http:8080", &listener), UpdaterError::InvalidListenerName(s!("http:8080")));
+		assert_err_eq!(updater.register_listener("http\n",    &listener), UpdaterError::InvalidListenerName(s!("http\n")));
+		assert!(updater.listeners.lock().is_empty());
+	}
+	
+	//		actions																
+	#[tokio::test]
+	async fn actions() {
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.this = Weak::clone(this);
+			updater
+		});
+		//	Actions registered without a guard are counted, but not listed
+		assert_eq!(updater.register_action(), Some(1));
+		assert!(updater.actions().is_empty());
+		let _guard  = updater.guard_action(Some("Report")).unwrap();
+		let actions = updater.actions();
+		assert_eq!(actions.len(),     1);
+		assert_eq!(actions[0].name,   Some(s!("Report")));
+		assert!(actions[0].elapsed() < Duration::from_secs(5));
+	}
+	
 	//		is_safe_to_update													
 	#[tokio::test]
 	async fn is_safe_to_update() {
@@ -328,8 +2237,8 @@ mod updater_public {
 		assert_eq!(updater.status(), Status::Idle);
 		updater.set_status(Status::Checking);
 		assert_eq!(updater.status(), Status::Checking);
-		updater.set_status(Status::Downloading(Version::new(1, 0, 0), 50));
-		assert_eq!(updater.status(), Status::Downloading(Version::new(1, 0, 0), 50));
+		updater.set_status(Status::Downloading(Version::new(1, 0, 0), Progress::new(50, 100, None)));
+		assert_eq!(updater.status(), Status::Downloading(Version::new(1, 0, 0), Progress::new(50, 100, None)));
 		updater.set_status(Status::Idle);
 		assert_eq!(updater.status(), Status::Idle);
 	}
@@ -351,7 +2260,6 @@ mod updater_public {
 		);
 		let (sender, receiver) = flume::unbounded();
 		let mut rx = updater.subscribe();
-		#[expect(clippy::pattern_type_mismatch, reason = "Tokio code")]
 		let thread = spawn(async move { select! {
 			Ok(status) = rx.recv()             => mock_subscriber.update(status),
 			_          = receiver.recv_async() => {},
@@ -377,7 +2285,6 @@ mod updater_public {
 		);
 		let (sender, receiver) = flume::unbounded();
 		let mut rx = updater.subscribe();
-		#[expect(clippy::pattern_type_mismatch, reason = "Tokio code")]
 		let thread = spawn(async move { select! {
 			Ok(status) = rx.recv()             => mock_subscriber.update(status),
 			_          = receiver.recv_async() => {}
@@ -386,385 +2293,352 @@ mod updater_public {
 		sender.send(()).unwrap();
 		assert!(async { thread.await.unwrap() }.catch_unwind().await.is_err());
 	}
-}
-
-#[cfg(test)]
-mod updater_private {
-	use sham::reqwest::MockError;
-	use crate::mocks::reqwest::ResponseSignature;
-	use super::*;
 	
-	//		check_for_updates													
+	//		subscribe_events													
 	#[tokio::test]
-	async fn check_for_updates__complete_successful_process() {
-		//	The lock and temp_dir need to be maintained for the duration of the test
-		let (_lock, _temp_dir, _, _, _)  = setup_files();
-		let version                      = Version::new(2, 3, 4);
-		let private_key                  = generate_new_private_key();
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let url3                         = "https://api.example.com/api/hashes/2.3.4";
-		let payload                      = b"Test payload";
-		let json1                        = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let json2                        = json!({
-			"version": s!("2.3.4"),
-			"hash":    hex::encode(Sha256::digest(payload)),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json1.len()),
-			Ok(&json1),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
-		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
-			StatusCode::OK,
-			Some("application/octet-stream"),
-			Some(payload.len()),
-			Ok(payload),
-		);
-		let (mock_response3, _public_key) = create_mock_response(
-			url3,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json2.len()),
-			Ok(&json2),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
+	async fn subscribe_events() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-			(url3, Ok(mock_response3)),
+		let rx1 = updater.subscribe_events();
+		let rx2 = updater.subscribe_events();
+		updater.set_status(Status::Checking);
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		drop(rx2);
+		assert_ok!(updater.set_status_if(|status| *status != Status::Idle, Status::Idle));
+		assert_eq!(rx1.drain().collect::<Vec<_>>(), vec![
+			Status::Checking,
+			Status::UpdateAvailable(Version::new(2, 3, 4)),
+			Status::Idle,
 		]);
-		let updater = setup_safe_updater(
+		assert_eq!(updater.events.lock().len(), 1);
+	}
+	#[tokio::test]
+	async fn subscribe_events__update_process() {
+		let (public_key, mock_client) = create_update_client(0..3);
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			public_key,
 			mock_client,
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Restarting(version.clone()));
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		let rx = updater.subscribe_events();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		let events = rx.drain().collect::<Vec<_>>();
+		assert_eq!(events.len(), 6);
+		assert_eq!(events[0],    Status::Checking);
+		assert_eq!(events[1],    Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_eq!(events[2],    Status::Downloading(Version::new(2, 3, 4), Progress::default()));
+		assert!(matches!(events[3], Status::Downloading(ref version, progress) if *version == Version::new(2, 3, 4) && progress.percent() == 100));
+		assert_eq!(events[4],    Status::Verifying(Version::new(2, 3, 4)));
+		assert_eq!(events[5],    Status::Downloaded(Version::new(2, 3, 4)));
 	}
+	
+	//		status_stream														
 	#[tokio::test]
-	async fn check_for_updates__update_check_already_underway() {
-		//	The lock needs to be maintained for the duration of the test. We call
-		//	setup_files() to ensure that the mock executable path is set.
-		let (_lock, _temp_dir, _, _, _) = setup_files();
+	async fn status_stream() {
+		let updater    = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let mut stream = updater.status_stream();
+		updater.set_status(Status::Checking);
+		updater.set_status(Status::Idle);
+		assert_eq!(stream.next().await, Some(Status::Checking));
+		assert_eq!(stream.next().await, Some(Status::Idle));
+	}
+	
+	//		watch																
+	#[tokio::test]
+	async fn watch() {
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			*EMPTY_PUBLIC_KEY,
 			MockClient::new(),
 		);
+		let mut rx  = updater.watch();
+		assert_eq!(*rx.borrow(), Status::Idle);
 		updater.set_status(Status::Checking);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Checking);
-		updater.set_status(Status::Downloading(Version::new(1, 0, 0), 80));
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Downloading(Version::new(1, 0, 0), 80));
-		updater.set_status(Status::Installing(Version::new(1, 0, 0)));
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Installing(Version::new(1, 0, 0)));
-		updater.set_status(Status::PendingRestart(Version::new(1, 0, 0)));
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::PendingRestart(Version::new(1, 0, 0)));
-		updater.set_status(Status::Restarting(Version::new(1, 0, 0)));
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Restarting(Version::new(1, 0, 0)));
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_ok!(rx.changed().await);
+		assert_eq!(*rx.borrow_and_update(), Status::UpdateAvailable(Version::new(2, 3, 4)));
+		assert_ok!(updater.set_status_if(|status| *status != Status::Idle, Status::Idle));
+		assert_ok!(rx.changed().await);
+		assert_eq!(*rx.borrow_and_update(), Status::Idle);
 	}
+	
+	//		wait_for															
 	#[tokio::test]
-	async fn check_for_updates__no_update_available() {
-		let url                         = "https://api.example.com/api/latest";
-		let json                        = json!({
-			"version": s!("1.0.0"),
-		}).to_string();
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
+	async fn wait_for() {
+		let updater = Arc::new(setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		));
+		assert_eq!(updater.wait_for(|status| *status == Status::Idle, None).await, Some(Status::Idle));
+		let setter  = Arc::clone(&updater);
+		drop(spawn(async move {
+			sleep(Duration::from_millis(10)).await;
+			setter.set_status(Status::Checking);
+			setter.set_status(Status::PendingRestart(Version::new(2, 3, 4), 2, None));
+		}));
+		assert_eq!(
+			updater.wait_for(|status| matches!(*status, Status::PendingRestart(_, _, _)), Some(Duration::from_secs(5))).await,
+			Some(Status::PendingRestart(Version::new(2, 3, 4), 2, None)),
 		);
-		let mock_client = create_mock_client(vec![
-			(url, Ok(mock_response)),
-		]);
+	}
+	#[tokio::test]
+	async fn wait_for__timeout() {
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
-			mock_client,
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Idle);
+		assert_none!(updater.wait_for(|status| *status == Status::Checking, Some(Duration::from_millis(10))).await);
 	}
+	
+	//		wait_for_status														
 	#[tokio::test]
-	async fn check_for_updates__download_failed() {
-		let version                      = Version::new(2, 3, 4);
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let payload                      = b"Test payload";
-		let json                         = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
-			StatusCode::OK,
-			//	Intentionally-incorrect content type, to make the process fail
-			Some("text/plain"),
-			Some(payload.len()),
-			Ok(payload),
+	async fn wait_for_status() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-		]);
+		assert!( updater.wait_for_status(&Status::Idle,     Some(Duration::from_millis(10))).await);
+		assert!(!updater.wait_for_status(&Status::Checking, Some(Duration::from_millis(10))).await);
+	}
+}
+
+#[cfg(test)]
+mod updater_private {
+	use super::*;
+	
+	//		set_status_if														
+	#[tokio::test]
+	async fn set_status_if() {
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
-			mock_client,
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Downloading(version.clone(), 0));
+		let mut rx  = updater.subscribe();
+		assert_ok!(updater.set_status_if(|status| *status == Status::Idle, Status::Checking));
+		assert_eq!(updater.status(),         Status::Checking);
+		assert_eq!(rx.recv().await.unwrap(), Status::Checking);
+		assert_err_eq!(updater.set_status_if(|status| *status == Status::Idle, Status::Checking), Status::Checking);
+		assert_eq!(updater.status(),         Status::Checking);
+		assert!(rx.try_recv().is_err());
 	}
-	#[tokio::test]
-	async fn check_for_updates__download_partial() {
-		let version                      = Version::new(2, 3, 4);
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let payload                      = b"Test payload";
-		let json                         = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
+	
+	//		falls_back_after													
+	#[test]
+	fn falls_back_after() {
+		let url         = Url::parse("https://api.example.com/api/latest").unwrap();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
-			StatusCode::OK,
-			Some("application/octet-stream"),
-			//	Intentionally-incorrect content length, to make the process fail
-			Some(payload.len() * 2),
-			Ok(payload),
+		updater.config.retry_policy.fallback_interval = Some(Duration::from_secs(600));
+		let retryable = CheckOutcome::Failed(UpdaterError::HttpRequestFailed(url.clone(), s!("Timed out")));
+		let permanent = CheckOutcome::Failed(UpdaterError::FailedSignatureVerification(url));
+		let success   = CheckOutcome::UpToDate(Version::new(1, 0, 0));
+		assert_eq!(updater.falls_back_after(&retryable, false), true);
+		assert_eq!(updater.falls_back_after(&permanent, false), false);
+		assert_eq!(updater.falls_back_after(&permanent, true),  true);
+		assert_eq!(updater.falls_back_after(&success,   true),  false);
+		updater.config.retry_policy.fallback_interval = None;
+		assert_eq!(updater.falls_back_after(&retryable, false), false);
+	}
+	
+	//		time_until_check													
+	#[test]
+	fn time_until_check() {
+		let now         = UNIX_EPOCH + Duration::from_secs(1_704_067_200);
+		let normal      = Duration::from_secs(60);
+		let fallback    = Duration::from_secs(600);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-		]);
-		let updater = setup_safe_updater(
+		assert_none!(updater.time_until_check(false, now));
+		updater.config.check_interval                 = Some(normal);
+		updater.config.retry_policy.fallback_interval = Some(fallback);
+		assert_some_eq!(updater.time_until_check(false, now), normal);
+		assert_some_eq!(updater.time_until_check(true,  now), fallback);
+		//	A schedule takes precedence over the interval, but not the fallback
+		updater.config.check_schedule                 = Some("daily at 02:30".parse().unwrap());
+		assert_some_eq!(updater.time_until_check(false, now), Duration::from_secs(9_000));
+		assert_some_eq!(updater.time_until_check(true,  now), fallback);
+		updater.config.check_schedule                 = Some("0 0 31 2 *".parse().unwrap());
+		assert_none!(updater.time_until_check(false, now));
+	}
+	#[test]
+	fn time_until_check__jitter() {
+		let now         = SystemTime::now();
+		let normal      = Duration::from_secs(60);
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
-			mock_client,
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Downloading(version.clone(), 50));
+		updater.config.check_interval = Some(normal);
+		updater.config.check_jitter   = Duration::from_secs(30);
+		let delays = repeat_with(|| updater.time_until_check(false, now).unwrap()).take(20).collect::<Vec<_>>();
+		assert!(delays.iter().all(|&delay| delay >= normal && delay <= normal + Duration::from_secs(30)));
+		assert!(delays.iter().any(|&delay| delay != delays[0]));
 	}
-	#[tokio::test]
-	async fn check_for_updates__download_full() {
-		let version                      = Version::new(2, 3, 4);
-		let private_key                  = generate_new_private_key();
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let url3                         = "https://api.example.com/api/hashes/2.3.4";
-		let payload                      = b"Test payload";
-		let json1                        = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let json2                        = json!({
-			"version": s!("2.3.4"),
-			//	Intentionally-incorrect hash, to make the process fail
-			"hash":    hex::encode(Sha256::digest("Some other payload")),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json1.len()),
-			Ok(&json1),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
+	
+	//		await_window														
+	#[test]
+	fn await_window() {
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
-			StatusCode::OK,
-			Some("application/octet-stream"),
-			Some(payload.len()),
-			Ok(payload),
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		assert_none!(updater.await_window(ConsentStep::Install, &Version::new(2, 3, 4)));
+		updater.config.maintenance = Some(create_schedule(true, MaintenanceScope::InstallAndRestart));
+		assert_none!(updater.await_window(ConsentStep::Install, &Version::new(2, 3, 4)));
+		updater.config.maintenance = Some(create_schedule(false, MaintenanceScope::Restart));
+		assert_none!(updater.await_window(ConsentStep::Download, &Version::new(2, 3, 4)));
+		assert_none!(updater.await_window(ConsentStep::Install,  &Version::new(2, 3, 4)));
+		assert_eq!(updater.status(), Status::Downloaded(Version::new(2, 3, 4)));
+	}
+	#[test]
+	fn await_window__never_opens() {
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let (mock_response3, _public_key) = create_mock_response(
-			url3,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json2.len()),
-			Ok(&json2),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
+		updater.config.maintenance = Some(MaintenanceSchedule::new(vec![], 0));
+		updater.set_status(Status::Installed(Version::new(2, 3, 4)));
+		assert_eq!(
+			updater.await_window(ConsentStep::Restart, &Version::new(2, 3, 4)),
+			Some(CheckOutcome::PendingRestart(Version::new(2, 3, 4))),
 		);
-		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-			(url3, Ok(mock_response3)),
-		]);
-		let updater = setup_safe_updater(
+		assert_eq!(updater.status(), Status::Installed(Version::new(2, 3, 4)));
+	}
+	
+	//		seek_consent														
+	#[tokio::test]
+	async fn seek_consent() {
+		let consent     = Arc::new(MockConsent::new([ConsentDecision::Approve]));
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
-			mock_client,
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		assert_eq!(updater.status(), Status::Downloading(version.clone(), 100));
+		assert_none!(updater.seek_consent(ConsentStep::Install, &Version::new(2, 3, 4)).await);
+		updater.config.consent = Some(Arc::<MockConsent>::clone(&consent));
+		assert_none!(updater.seek_consent(ConsentStep::Install, &Version::new(2, 3, 4)).await);
+		assert_eq!(*consent.requests.lock(), vec![(ConsentStep::Install, Version::new(2, 3, 4))]);
 	}
 	#[tokio::test]
-	async fn check_for_updates__install_failed() {
-		let version                      = Version::new(2, 3, 4);
-		let private_key                  = generate_new_private_key();
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let url3                         = "https://api.example.com/api/hashes/2.3.4";
-		let payload                      = b"Test payload";
-		let json1                        = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let json2                        = json!({
-			"version": s!("2.3.4"),
-			"hash":    hex::encode(Sha256::digest(payload)),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json1.len()),
-			Ok(&json1),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
+	async fn seek_consent__decline() {
+		let consent     = Arc::new(MockConsent::new([ConsentDecision::Decline]));
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
 		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
+		updater.config.consent = Some(Arc::<MockConsent>::clone(&consent));
+		updater.set_status(Status::Downloaded(Version::new(2, 3, 4)));
+		*updater.staged.lock() = Some((tempdir().unwrap(), PathBuf::from("update")));
+		assert_eq!(
+			updater.seek_consent(ConsentStep::Install, &Version::new(2, 3, 4)).await,
+			Some(CheckOutcome::Declined(Version::new(2, 3, 4))),
+		);
+		assert_eq!(updater.status(), Status::Idle);
+		assert_none!(updater.staged.lock().as_ref());
+		assert!(updater.declined.read().contains(&Version::new(2, 3, 4)));
+	}
+	
+	//		download_update														
+	#[tokio::test]
+	async fn download_update() {
+		let version       = Version::new(2, 3, 4);
+		let url           = "https://api.example.com/api/releases/2.3.4";
+		let payload       = b"Test payload";
+		let mock_response = create_mock_binary_response(
+			url,
 			StatusCode::OK,
 			Some("application/octet-stream"),
 			Some(payload.len()),
 			Ok(payload),
 		);
-		let (mock_response3, _public_key) = create_mock_response(
-			url3,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json2.len()),
-			Ok(&json2),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
-		);
 		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-			(url3, Ok(mock_response3)),
+			(url, Ok(mock_response)),
 		]);
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		assert_eq!(updater.status(), Status::Idle);
-		updater.check_for_updates().await;
-		//	We haven't set up the test files, so the installation will fail, which
-		//	is what we want here, so that we can check the status is correct
-		assert_eq!(updater.status(), Status::Installing(version.clone()));
+		let (_download_dir, update_path, file_hash) = updater.download_update(&version, None).await.unwrap();
+		let file_data                               = async_fs::read(update_path).await.unwrap();
+		assert_eq!(file_hash, Sha256Hash::from(Sha256::digest(payload)));
+		assert_eq!(file_hash, Sha256Hash::from(Sha256::digest(&file_data)));
+		assert_eq!(file_data, payload);
 	}
 	#[tokio::test]
-	async fn check_for_updates__restart_blocked() {
-		//	The lock and temp_dir need to be maintained for the duration of the test
-		let (_lock, _temp_dir, _, _, _)  = setup_files();
-		let version                      = Version::new(2, 3, 4);
-		let private_key                  = generate_new_private_key();
-		let url1                         = "https://api.example.com/api/latest";
-		let url2                         = "https://api.example.com/api/releases/2.3.4";
-		let url3                         = "https://api.example.com/api/hashes/2.3.4";
-		let payload                      = b"Test payload";
-		let json1                        = json!({
-			"version": s!("2.3.4"),
-		}).to_string();
-		let json2                        = json!({
-			"version": s!("2.3.4"),
-			"hash":    hex::encode(Sha256::digest(payload)),
-		}).to_string();
-		let (mock_response1, public_key) = create_mock_response(
-			url1,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json1.len()),
-			Ok(&json1),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
-		);
-		let mock_response2 = create_mock_binary_response(
-			url2,
+	async fn download_update__from_url() {
+		let version       = Version::new(2, 3, 4);
+		let url           = "https://cdn.example.com/releases/2.3.4?expires=1700000000&signature=abcd";
+		let payload       = b"Test payload";
+		let mock_response = create_mock_binary_response(
+			url,
 			StatusCode::OK,
 			Some("application/octet-stream"),
 			Some(payload.len()),
 			Ok(payload),
 		);
-		let (mock_response3, _public_key) = create_mock_response(
-			url3,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json2.len()),
-			Ok(&json2),
-			&ResponseSignature::GenerateUsing(private_key.clone()),
-		);
 		let mock_client = create_mock_client(vec![
-			(url1, Ok(mock_response1)),
-			(url2, Ok(mock_response2)),
-			(url3, Ok(mock_response3)),
+			(url, Ok(mock_response)),
 		]);
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		assert_eq!(updater.status(),          Status::Idle);
-		assert_eq!(updater.register_action(), Some(1));
-		updater.check_for_updates().await;
-		//	We've registered a critical action, so the installation will be blocked,
-		//	which is what we want here, so that we can check the status is correct
-		assert_eq!(updater.status(),          Status::PendingRestart(version.clone()));
-	}
-	#[tokio::test]
-	async fn check_for_updates__restart_failed() {
-		//	No test for this at present, as it is difficult to simulate a failure
+		let (_download_dir, update_path, file_hash) = updater.download_update(&version, Some(&url.parse().unwrap())).await.unwrap();
+		assert_eq!(file_hash, Sha256Hash::from(Sha256::digest(payload)));
+		assert_eq!(async_fs::read(update_path).await.unwrap(), payload);
 	}
-	
-	//		download_update														
 	#[tokio::test]
-	async fn download_update() {
+	async fn download_update__unknown_length() {
 		let version       = Version::new(2, 3, 4);
-		let url           = "https://api.example.com/api/releases/2.3.4";
+		let url           = "https://cdn.example.com/releases/2.3.4";
 		let payload       = b"Test payload";
 		let mock_response = create_mock_binary_response(
 			url,
 			StatusCode::OK,
 			Some("application/octet-stream"),
-			Some(payload.len()),
+			None,
 			Ok(payload),
 		);
 		let mock_client = create_mock_client(vec![
@@ -776,11 +2650,9 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		let (_download_dir, update_path, file_hash) = updater.download_update(&version).await.unwrap();
-		let file_data                               = async_fs::read(update_path).await.unwrap();
+		let (_download_dir, update_path, file_hash) = updater.download_update(&version, Some(&url.parse().unwrap())).await.unwrap();
 		assert_eq!(file_hash, Sha256Hash::from(Sha256::digest(payload)));
-		assert_eq!(file_hash, Sha256Hash::from(Sha256::digest(&file_data)));
-		assert_eq!(file_data, payload);
+		assert_eq!(async_fs::read(update_path).await.unwrap(), payload);
 	}
 	#[tokio::test]
 	async fn download_update__err_unable_to_create_download() {
@@ -817,9 +2689,33 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		let err = updater.download_update(&version).await.unwrap_err();
-		assert_eq!(err,             UpdaterError::UnexpectedContentType(url.parse().unwrap(), content_type.to_owned(), expected_content_type.clone()));
-		assert_eq!(err.to_string(), format!(r#"HTTP response from {url} had unexpected content type: "{content_type}", expected: "{expected_content_type}""#));
+		let err = updater.download_update(&version, None).await.unwrap_err();
+		assert_eq!(err,             UpdaterError::UnexpectedContentType(url.parse().unwrap(), content_type.to_owned(), expected_content_type.clone()));
+		assert_eq!(err.to_string(), format!(r#"HTTP response from {url} had unexpected content type: "{content_type}", expected: "{expected_content_type}""#));
+	}
+	#[tokio::test]
+	async fn download_update__err_interrupted() {
+		let version       = Version::new(2, 3, 4);
+		let url           = "https://api.example.com/api/releases/2.3.4";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			None,
+			Err(MockError::default()),
+		);
+		let mock_client = create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			mock_client,
+		);
+		let err = updater.download_update(&version, None).await.unwrap_err();
+		assert_eq!(err, UpdaterError::HttpRequestFailed(url.parse().unwrap(), s!("Mocked Reqwest error")));
+		assert!(err.is_retryable());
 	}
 	#[tokio::test]
 	async fn download_update__err_missing_data() {
@@ -845,7 +2741,7 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		let err = updater.download_update(&version).await.unwrap_err();
+		let err = updater.download_update(&version, None).await.unwrap_err();
 		assert_eq!(err,             UpdaterError::MissingData(url.parse().unwrap(), content_len, expected_content_len));
 		assert_eq!(err.to_string(), format!("HTTP response body from {url} is shorter than expected: {content_len} < {expected_content_len}"));
 	}
@@ -873,20 +2769,22 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		let err = updater.download_update(&version).await.unwrap_err();
+		let err = updater.download_update(&version, None).await.unwrap_err();
 		assert_eq!(err,             UpdaterError::TooMuchData(url.parse().unwrap(), content_len, expected_content_len));
 		assert_eq!(err.to_string(), format!("HTTP response body from {url} is longer than expected: {content_len} > {expected_content_len}"));
 	}
 	
-	//		verify_update														
+	//		get_release_info													
 	#[tokio::test]
-	async fn verify_update() {
+	async fn get_release_info() {
 		let version                     = Version::new(2, 3, 4);
 		let hash                        = Sha256::digest(b"Test payload");
 		let url                         = "https://api.example.com/api/hashes/2.3.4";
+		let download_url                = "https://cdn.example.com/releases/2.3.4?expires=1700000000&signature=abcd";
 		let json                        = json!({
 			"version": version.to_string(),
 			"hash":    hex::encode(hash),
+			"url":     download_url,
 		}).to_string();
 		let (mock_response, public_key) = create_mock_response(
 			url,
@@ -905,17 +2803,20 @@ mod updater_private {
 			public_key,
 			mock_client,
 		);
-		assert_ok!(updater.verify_update(&version, hash.as_slice().try_into().unwrap()).await);
+		let release = updater.get_release_info(&version).await.unwrap();
+		assert_eq!(release.version, version);
+		assert_eq!(release.hash,    Sha256Hash::from(hash));
+		assert_eq!(release.url,     Some(download_url.parse().unwrap()));
 	}
 	#[tokio::test]
-	async fn verify_update__err_failed_hash_verification() {
+	async fn get_release_info__err_invalid_payload() {
 		let version                     = Version::new(2, 3, 4);
+		let other_version               = Version::new(3, 3, 3);
 		let hash                        = Sha256::digest(b"Test payload");
-		let other_hash                  = Sha256::digest(b"Some other payload");
 		let url                         = "https://api.example.com/api/hashes/2.3.4";
 		let json                        = json!({
-			"version": version.to_string(),
-			"hash":    hex::encode(other_hash),
+			"version": other_version.to_string(),
+			"hash":    hex::encode(hash),
 		}).to_string();
 		let (mock_response, public_key) = create_mock_response(
 			url,
@@ -934,19 +2835,201 @@ mod updater_private {
 			public_key,
 			mock_client,
 		);
-		let err = updater.verify_update(&version, hash.as_slice().try_into().unwrap()).await;
+		let err = updater.get_release_info(&version).await;
+		assert_err_eq!(err.clone(), UpdaterError::InvalidPayload(url.parse().unwrap()));
+		assert_eq!(err.unwrap_err().to_string(), format!("Invalid payload received from {url}"));
+	}
+	
+	//		verify_update														
+	#[test]
+	fn verify_update() {
+		let hash    = Sha256Hash::from(Sha256::digest(b"Test payload"));
+		let release = VersionHashResponse { version: Version::new(2, 3, 4), hash, url: None };
+		assert_ok!(Updater::verify_update(&release, hash));
+	}
+	#[test]
+	fn verify_update__err_failed_hash_verification() {
+		let version = Version::new(2, 3, 4);
+		let hash    = Sha256Hash::from(Sha256::digest(b"Test payload"));
+		let release = VersionHashResponse {
+			version:  version.clone(),
+			hash:     Sha256::digest(b"Some other payload").into(),
+			url:      None,
+		};
+		let err = Updater::verify_update(&release, hash);
 		assert_err_eq!(err.clone(), UpdaterError::FailedHashVerification(version.clone()));
 		assert_eq!(err.unwrap_err().to_string(), format!("Failed hash verification for downloaded version {version}"));
 	}
+	
+	//		follow_events														
 	#[tokio::test]
-	async fn verify_update__err_invalid_payload() {
-		let version                     = Version::new(2, 3, 4);
-		let other_version               = Version::new(3, 3, 3);
-		let hash                        = Sha256::digest(b"Test payload");
-		let url                         = "https://api.example.com/api/hashes/2.3.4";
+	async fn follow_events() {
+		let private_key                 = generate_keypair().0;
+		let url1                        = "https://api.example.com/api/events";
+		let url2                        = "https://api.example.com/api/latest";
+		let body                        = format!(
+			":\n\nevent: release\ndata: {}\n\n",
+			create_release_event(&private_key, "2.3.4"),
+		);
 		let json                        = json!({
-			"version": other_version.to_string(),
-			"hash":    hex::encode(hash),
+			"version": s!("2.3.4"),
+		}).to_string();
+		let mock_response1              = create_mock_binary_response(
+			url1,
+			StatusCode::OK,
+			Some("text/event-stream"),
+			Some(body.len()),
+			Ok(body.as_bytes()),
+		);
+		let (mock_response2, public_key) = create_mock_response(
+			url2,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::GenerateUsing(private_key),
+		);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![
+				(url1, Ok(mock_response1)),
+				(url2, Ok(mock_response2)),
+			]),
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		assert_ok!(updater.follow_events().await);
+		assert!(updater.subscribed.load(Ordering::SeqCst));
+		assert_eq!(updater.status(), Status::UpdateAvailable(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn follow_events__crlf() {
+		let private_key                 = generate_keypair().0;
+		let url1                        = "https://api.example.com/api/events";
+		let url2                        = "https://api.example.com/api/latest";
+		let body                        = format!(
+			":\r\n\r\nevent: release\r\ndata: {}\r\n\r\n",
+			create_release_event(&private_key, "2.3.4"),
+		);
+		let json                        = json!({
+			"version": s!("2.3.4"),
+		}).to_string();
+		let mock_response1              = create_mock_binary_response(
+			url1,
+			StatusCode::OK,
+			Some("text/event-stream"),
+			Some(body.len()),
+			Ok(body.as_bytes()),
+		);
+		let (mock_response2, public_key) = create_mock_response(
+			url2,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::GenerateUsing(private_key),
+		);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![
+				(url1, Ok(mock_response1)),
+				(url2, Ok(mock_response2)),
+			]),
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		assert_ok!(updater.follow_events().await);
+		assert!(updater.subscribed.load(Ordering::SeqCst));
+		assert_eq!(updater.status(), Status::UpdateAvailable(Version::new(2, 3, 4)));
+	}
+	#[tokio::test]
+	async fn follow_events__drops_oversized_event() {
+		let private_key   = generate_keypair().0;
+		let url           = "https://api.example.com/api/events";
+		let body          = format!(
+			"data: {}\n\nevent: release\ndata: {}\n\n",
+			"x".repeat(MAX_EVENT_SIZE),
+			create_release_event(&private_key, "2.3.4"),
+		);
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("text/event-stream"),
+			Some(body.len()),
+			Ok(body.as_bytes()),
+		);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			private_key.verifying_key(),
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		assert_ok_eq!(updater.follow_events().await, true);
+		assert_eq!(updater.status(), Status::Idle);
+	}
+	#[tokio::test]
+	async fn follow_events__ignores_invalid_events() {
+		let url            = "https://api.example.com/api/events";
+		let body           = format!(
+			"event: release\ndata: {}\n\n",
+			create_release_event(&generate_keypair().0, "2.3.4"),
+		);
+		let mock_response  = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("text/event-stream"),
+			Some(body.len()),
+			Ok(body.as_bytes()),
+		);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		assert_ok!(updater.follow_events().await);
+		assert_eq!(updater.status(), Status::Idle);
+	}
+	#[tokio::test]
+	async fn follow_events__err_unexpected_content_type() {
+		let url           = "https://api.example.com/api/events";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(2),
+			Ok(b"{}"),
+		);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		let err = updater.follow_events().await;
+		assert_err_eq!(err, UpdaterError::UnexpectedContentType(
+			url.parse().unwrap(),
+			s!("application/json"),
+			s!("text/event-stream"),
+		));
+		assert!(!updater.subscribed.load(Ordering::SeqCst));
+	}
+	
+	//		handle_release_event												
+	#[tokio::test]
+	async fn handle_release_event() {
+		let private_key                 = generate_keypair().0;
+		let url                         = "https://api.example.com/api/latest";
+		let json                        = json!({
+			"version": s!("2.3.4"),
 		}).to_string();
 		let (mock_response, public_key) = create_mock_response(
 			url,
@@ -954,23 +3037,169 @@ mod updater_private {
 			Some("application/json"),
 			Some(json.len()),
 			Ok(&json),
-			&ResponseSignature::Generate,
+			&ResponseSignature::GenerateUsing(private_key.clone()),
 		);
-		let mock_client = create_mock_client(vec![
-			(url, Ok(mock_response)),
-		]);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		let outcome = updater.handle_release_event(
+			&"https://api.example.com/api/events".parse().unwrap(),
+			&create_release_event(&private_key, "2.3.4"),
+		).await;
+		assert_ok_eq!(outcome, Some(CheckOutcome::UpdateAvailable(Version::new(2, 3, 4))));
+	}
+	#[tokio::test]
+	async fn handle_release_event__not_newer() {
+		let private_key = generate_keypair().0;
+		let updater     = setup_safe_updater(
+			Version::new(2, 3, 4),
+			"https://api.example.com/api/",
+			private_key.verifying_key(),
+			MockClient::new(),
+		);
+		let outcome     = updater.handle_release_event(
+			&"https://api.example.com/api/events".parse().unwrap(),
+			&create_release_event(&private_key, "2.3.4"),
+		).await;
+		assert_ok_eq!(outcome, None);
+	}
+	#[tokio::test]
+	async fn handle_release_event__held() {
+		let private_key                 = generate_keypair().0;
+		let url                         = "https://api.example.com/api/latest";
+		let json                        = json!({
+			"version": s!("3.0.0"),
+		}).to_string();
+		let (mock_response, public_key) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::GenerateUsing(private_key.clone()),
+		);
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		updater.config.update_policy = UpdatePolicy::NotifyOnly;
+		updater.set_status(Status::UpdateAvailable(Version::new(2, 3, 4)));
+		let events_url = "https://api.example.com/api/events".parse().unwrap();
+		assert_ok_eq!(
+			updater.handle_release_event(&events_url, &create_release_event(&private_key, "2.3.4")).await,
+			None
+		);
+		assert_ok_eq!(
+			updater.handle_release_event(&events_url, &create_release_event(&private_key, "3.0.0")).await,
+			Some(CheckOutcome::UpdateAvailable(Version::new(3, 0, 0)))
+		);
+	}
+	#[tokio::test]
+	async fn handle_release_event__not_idle() {
+		let private_key = generate_keypair().0;
+		let updater     = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			private_key.verifying_key(),
+			MockClient::new(),
+		);
+		updater.set_status(Status::Checking);
+		let outcome     = updater.handle_release_event(
+			&"https://api.example.com/api/events".parse().unwrap(),
+			&create_release_event(&private_key, "2.3.4"),
+		).await;
+		assert_ok_eq!(outcome, None);
+	}
+	#[tokio::test]
+	async fn handle_release_event__err_failed_signature_verification() {
+		let url     = "https://api.example.com/api/events";
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			MockClient::new(),
+		);
+		let err     = updater.handle_release_event(
+			&url.parse().unwrap(),
+			&create_release_event(&generate_keypair().0, "2.3.4"),
+		).await;
+		assert_err_eq!(err, UpdaterError::FailedSignatureVerification(url.parse().unwrap()));
+	}
+	#[tokio::test]
+	async fn handle_release_event__err_invalid_payload() {
+		let url     = "https://api.example.com/api/events";
 		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let err     = updater.handle_release_event(&url.parse().unwrap(), "2.3.4").await;
+		assert_err_eq!(err, UpdaterError::InvalidPayload(url.parse().unwrap()));
+	}
+	
+	//		scan_bundles														
+	#[tokio::test]
+	async fn scan_bundles() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, bundle) = create_bundle(temp_dir.path(), "b.bundle", "2.3.4", b"Bundled payload");
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			public_key,
-			mock_client,
+			MockClient::new(),
 		);
-		let err = updater.verify_update(&version, hash.as_slice().try_into().unwrap()).await;
-		assert_err_eq!(err.clone(), UpdaterError::InvalidPayload(url.parse().unwrap()));
-		assert_eq!(err.unwrap_err().to_string(), format!("Invalid payload received from {url}"));
+		updater.config.bundle_dir    = Some(temp_dir.path().to_path_buf());
+		updater.config.update_policy = UpdatePolicy::DownloadOnly;
+		//	Files without the bundle extension are ignored, and bundles are taken
+		//	in order of name
+		fs::write(temp_dir.path().join("a.bundle.partial"), b"Incomplete").unwrap();
+		fs::write(temp_dir.path().join("c.bundle"),         b"Not a bundle").unwrap();
+		assert_some_eq!(updater.scan_bundles().await, CheckOutcome::Downloaded(Version::new(2, 3, 4)));
+		updater.set_status(Status::Idle);
+		let err = UpdaterError::InvalidBundle(temp_dir.path().join("c.bundle"));
+		assert_some_eq!(updater.scan_bundles().await, CheckOutcome::Failed(err));
+		//	Bundles that have already been dealt with are not installed again
+		assert_none!(updater.scan_bundles().await);
+		assert!(updater.bundles.lock().iter().any(|seen| seen.0 == bundle));
+	}
+	#[tokio::test]
+	async fn scan_bundles__not_idle() {
+		let temp_dir             = tempdir().unwrap();
+		let (public_key, _) = create_bundle(temp_dir.path(), "test.bundle", "2.3.4", b"Bundled payload");
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			public_key,
+			MockClient::new(),
+		);
+		updater.config.bundle_dir = Some(temp_dir.path().to_path_buf());
+		updater.set_status(Status::Checking);
+		assert_none!(updater.scan_bundles().await);
+		assert!(updater.bundles.lock().is_empty());
+	}
+	#[tokio::test]
+	async fn scan_bundles__no_dir() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			generate_keypair().0.verifying_key(),
+			MockClient::new(),
+		);
+		assert_none!(updater.scan_bundles().await);
 	}
 	
-	//		request																
+	//		request																
 	#[tokio::test]
 	async fn request() {
 		let url                         = "https://api.example.com/api/latest";
@@ -994,325 +3223,318 @@ mod updater_private {
 			public_key,
 			mock_client,
 		);
-		let (url2, response)   = updater.request("latest").await.unwrap();
-		let parsed  = serde_json::from_str::<JsonValue>(&response.text().await.unwrap()).unwrap();
+		let payload = updater.request(|source| source.latest()).await.unwrap();
+		let parsed  = serde_json::from_str::<JsonValue>(&payload.body).unwrap();
 		let crafted = json!({
 			"version": s!("3.3.3"),
 		});
-		assert_eq!(url2.as_str(), url);
+		assert_eq!(payload.url.as_str(), url);
 		assert_json_eq!(parsed, crafted);
 	}
 	#[tokio::test]
-	async fn request__err_http_error() {
+	async fn request__retry() {
 		let url                         = "https://api.example.com/api/latest";
-		let status                      = StatusCode::IM_A_TEAPOT;
 		let json                        = json!({
 			"version": s!("3.3.3"),
 		}).to_string();
 		let (mock_response, public_key) = create_mock_response(
 			url,
-			status,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let (mock_failure, _)           = create_mock_response(
+			url,
+			StatusCode::SERVICE_UNAVAILABLE,
 			Some("application/json"),
 			Some(json.len()),
 			Ok(&json),
 			&ResponseSignature::Generate,
 		);
 		let mock_client = create_mock_client(vec![
+			(url, Err(MockError::default())),
+			(url, Ok(mock_failure)),
 			(url, Ok(mock_response)),
 		]);
-		let updater = setup_safe_updater(
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			public_key,
 			mock_client,
 		);
-		let err = updater.request("latest").await;
-		assert_err_eq!(err.clone(), UpdaterError::HttpError(url.parse().unwrap(), status));
-		assert_eq!(err.unwrap_err().to_string(), format!("HTTP status code {status} received when calling {url}"));
+		updater.config.retry_policy = RetryPolicy {
+			max_retries: 2,
+			base_delay:  Duration::from_millis(1),
+			jitter:      Duration::ZERO,
+			..RetryPolicy::default()
+		};
+		let payload = updater.request(|source| source.latest()).await.unwrap();
+		assert_eq!(payload.url.as_str(), url);
 	}
 	#[tokio::test]
-	async fn request__err_http_request_failed() {
+	async fn request__err_retries_exhausted() {
 		let url         = "https://api.example.com/api/latest";
 		let err_msg     = "Mocked Reqwest error";
 		let mock_client = create_mock_client(vec![
 			(url, Err(MockError::default())),
+			(url, Err(MockError::default())),
 		]);
-		let updater = setup_safe_updater(
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			*EMPTY_PUBLIC_KEY,
 			mock_client,
 		);
-		let err = updater.request("latest").await;
-		assert_err_eq!(err.clone(), UpdaterError::HttpRequestFailed(url.parse().unwrap(), err_msg.to_owned()));
-		assert_eq!(err.unwrap_err().to_string(), format!("HTTP request to {url} failed: {err_msg}"));
+		updater.config.retry_policy = RetryPolicy {
+			max_retries: 1,
+			base_delay:  Duration::from_millis(1),
+			jitter:      Duration::ZERO,
+			..RetryPolicy::default()
+		};
+		assert_err_eq!(updater.request(|source| source.latest()).await, UpdaterError::HttpRequestFailed(url.parse().unwrap(), err_msg.to_owned()));
 	}
 	#[tokio::test]
-	async fn request__err_invalid_url() {
-		let base     = "https://api.example.com/api";
-		let endpoint = "https://[invalid]/../../../endpoint";
-		let updater  = setup_safe_updater(
+	async fn request__err_not_retried() {
+		let url                         = "https://api.example.com/api/latest";
+		let json                        = s!("{}");
+		let (mock_response, public_key) = create_mock_response(
+			url,
+			StatusCode::NOT_FOUND,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let mock_client = create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]);
+		let mut updater = setup_safe_updater(
 			Version::new(1, 0, 0),
-			base,
-			*EMPTY_PUBLIC_KEY,
-			MockClient::new(),
+			"https://api.example.com/api/",
+			public_key,
+			mock_client,
 		);
-		let err = updater.request(endpoint).await;
-		assert_err_eq!(err.clone(), UpdaterError::InvalidUrl(base.parse().unwrap(), endpoint.to_owned()));
-		assert_eq!(err.unwrap_err().to_string(), format!("Invalid URL specified: {base} plus {endpoint}"));
+		updater.config.retry_policy = RetryPolicy {
+			max_retries: 3,
+			base_delay:  Duration::from_millis(1),
+			..RetryPolicy::default()
+		};
+		assert_err_eq!(updater.request(|source| source.latest()).await, UpdaterError::HttpError(url.parse().unwrap(), StatusCode::NOT_FOUND));
 	}
-	
-	//		decode_and_verify													
 	#[tokio::test]
-	async fn decode_and_verify__latest_version() {
-		let version                     = Version::new(3, 3, 3);
+	async fn request__err_http_error() {
 		let url                         = "https://api.example.com/api/latest";
+		let status                      = StatusCode::IM_A_TEAPOT;
 		let json                        = json!({
-			"version": version.to_string(),
+			"version": s!("3.3.3"),
 		}).to_string();
 		let (mock_response, public_key) = create_mock_response(
 			url,
-			StatusCode::OK,
+			status,
 			Some("application/json"),
 			Some(json.len()),
 			Ok(&json),
 			&ResponseSignature::Generate,
 		);
+		let mock_client = create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]);
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			public_key,
-			MockClient::new(),
+			mock_client,
 		);
-		let response = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await.unwrap();
-		assert_eq!(response.version, version);
+		let err = updater.request(|source| source.latest()).await;
+		assert_err_eq!(err.clone(), UpdaterError::HttpError(url.parse().unwrap(), status));
+		assert_eq!(err.unwrap_err().to_string(), format!("HTTP status code {status} received when calling {url}"));
 	}
 	#[tokio::test]
-	async fn decode_and_verify__version_hash() {
-		let version                     = Version::new(3, 3, 3);
-		let hash                        = hex::encode(Sha256::digest(b"Test payload"));
-		let url                         = "https://api.example.com/api/hashes/3.3.3";
-		let json                        = json!({
+	async fn request__err_http_request_failed() {
+		let url         = "https://api.example.com/api/latest";
+		let err_msg     = "Mocked Reqwest error";
+		let mock_client = create_mock_client(vec![
+			(url, Err(MockError::default())),
+		]);
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			mock_client,
+		);
+		let err = updater.request(|source| source.latest()).await;
+		assert_err_eq!(err.clone(), UpdaterError::HttpRequestFailed(url.parse().unwrap(), err_msg.to_owned()));
+		assert_eq!(err.unwrap_err().to_string(), format!("HTTP request to {url} failed: {err_msg}"));
+	}
+	#[tokio::test]
+	async fn request__err_unable_to_read_file() {
+		let dir         = tempdir().unwrap();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.source  = Arc::new(DirectorySource::new(dir.path()));
+		let err         = updater.request(|source| source.latest()).await;
+		let path        = dir.path().join("latest");
+		assert_err_eq!(err.clone(), UpdaterError::UnableToReadFile(path.clone(), s!("No such file or directory (os error 2)")));
+		assert_eq!(err.unwrap_err().to_string(), format!("Unable to read file {path:?}: No such file or directory (os error 2)"));
+	}
+	
+	//		decode_and_verify													
+	#[test]
+	fn decode_and_verify__latest_version() {
+		let version     = Version::new(3, 3, 3);
+		let private_key = generate_keypair().0;
+		let json        = json!({
 			"version": version.to_string(),
-			"hash":    hash,
 		}).to_string();
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
+		let updater     = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			private_key.verifying_key(),
+			MockClient::new(),
 		);
-		let updater = setup_safe_updater(
+		let response    = updater.decode_and_verify::<LatestVersionResponse>(SignedPayload {
+			url:       "https://api.example.com/api/latest".parse().unwrap(),
+			signature: private_key.sign(json.as_bytes()).to_string(),
+			body:      json,
+		}).unwrap();
+		assert_eq!(response.version, version);
+	}
+	#[test]
+	fn decode_and_verify__version_hash() {
+		let version     = Version::new(3, 3, 3);
+		let hash        = hex::encode(Sha256::digest(b"Test payload"));
+		let private_key = generate_keypair().0;
+		let json        = json!({
+			"version": version.to_string(),
+			"hash":    hash,
+		}).to_string();
+		let updater     = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			private_key.verifying_key(),
 			MockClient::new(),
 		);
-		let response = updater.decode_and_verify::<VersionHashResponse>(url.parse().unwrap(), mock_response).await.unwrap();
+		let response    = updater.decode_and_verify::<VersionHashResponse>(SignedPayload {
+			url:       "https://api.example.com/api/hashes/3.3.3".parse().unwrap(),
+			signature: private_key.sign(json.as_bytes()).to_string(),
+			body:      json,
+		}).unwrap();
 		assert_eq!(response.version, version);
 		assert_eq!(response.hash,    Sha256Hash::from_hex(&hash).unwrap());
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_failed_signature_verification() {
-		let url                          = "https://api.example.com/api/latest";
-		let other_public_key             = generate_new_private_key().verifying_key();
-		let json                         = json!({
+	#[test]
+	fn decode_and_verify__err_failed_signature_verification() {
+		let url              = "https://api.example.com/api/latest";
+		let private_key      = generate_keypair().0;
+		let other_public_key = generate_keypair().0.verifying_key();
+		let json             = json!({
 			"version": s!("3.3.3"),
 		}).to_string();
-		let (mock_response, _public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
-		let updater = setup_safe_updater(
+		let updater          = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
 			other_public_key,
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
+		let err              = updater.decode_and_verify::<LatestVersionResponse>(SignedPayload {
+			url:       url.parse().unwrap(),
+			signature: private_key.sign(json.as_bytes()).to_string(),
+			body:      json,
+		});
 		assert_err_eq!(err.clone(), UpdaterError::FailedSignatureVerification(url.parse().unwrap()));
 		assert_eq!(err.unwrap_err().to_string(), format!("Failed signature verification for response from {url}"));
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_invalid_body() {
-		let url                         = "https://api.example.com/api/latest";
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			None,
-			Err(MockError::default()),
-			&ResponseSignature::Use(s!("dummy signature")),
-		);
-		let updater = setup_safe_updater(
-			Version::new(1, 0, 0),
-			"https://api.example.com/api/",
-			public_key,
-			MockClient::new(),
-		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
-		assert_err_eq!(err.clone(), UpdaterError::InvalidBody(url.parse().unwrap()));
-		assert_eq!(err.unwrap_err().to_string(), format!("Invalid HTTP body received from {url}"));
-	}
-	#[tokio::test]
-	async fn decode_and_verify__err_invalid_payload() {
-		let url                         = "https://api.example.com/api/latest";
-		let json                        = s!("{invalid json: 3.3.3");
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
-		let updater = setup_safe_updater(
+	#[test]
+	fn decode_and_verify__err_invalid_payload() {
+		let url         = "https://api.example.com/api/latest";
+		let private_key = generate_keypair().0;
+		let json        = s!("{invalid json: 3.3.3");
+		let updater     = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			private_key.verifying_key(),
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
+		let err         = updater.decode_and_verify::<LatestVersionResponse>(SignedPayload {
+			url:       url.parse().unwrap(),
+			signature: private_key.sign(json.as_bytes()).to_string(),
+			body:      json,
+		});
 		assert_err_eq!(err.clone(), UpdaterError::InvalidPayload(url.parse().unwrap()));
 		assert_eq!(err.unwrap_err().to_string(), format!("Invalid payload received from {url}"));
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_invalid_signature() {
-		let url                         = "https://api.example.com/api/latest";
-		let signature                   = s!("invalid signature");
-		let json                        = json!({
-			"version": s!("3.3.3"),
-		}).to_string();
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Use(signature.clone()),
-		);
-		let updater = setup_safe_updater(
+	#[test]
+	fn decode_and_verify__err_invalid_signature() {
+		let url       = "https://api.example.com/api/latest";
+		let signature = s!("invalid signature");
+		let updater   = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			generate_keypair().0.verifying_key(),
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
+		let err       = updater.decode_and_verify::<LatestVersionResponse>(SignedPayload {
+			url:       url.parse().unwrap(),
+			body:      json!({ "version": s!("3.3.3") }).to_string(),
+			signature: signature.clone(),
+		});
 		assert_err_eq!(err.clone(), UpdaterError::InvalidSignature(url.parse().unwrap(), signature.clone()));
 		assert_eq!(err.unwrap_err().to_string(), format!(r#"Invalid signature header "{signature}" received from {url}"#));
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_missing_signature() {
-		let url                         = "https://api.example.com/api/latest";
-		let json                        = json!({
-			"version": s!("3.3.3"),
-		}).to_string();
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some("application/json"),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Omit,
-		);
+	#[test]
+	fn decode_and_verify__err_missing_signature() {
+		let url     = "https://api.example.com/api/latest";
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			generate_keypair().0.verifying_key(),
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
+		let err     = updater.decode_and_verify::<LatestVersionResponse>(SignedPayload {
+			url:       url.parse().unwrap(),
+			body:      json!({ "version": s!("3.3.3") }).to_string(),
+			signature: String::new(),
+		});
 		assert_err_eq!(err.clone(), UpdaterError::MissingSignature(url.parse().unwrap()));
 		assert_eq!(err.unwrap_err().to_string(), format!("HTTP response from {url} does not contain a signature header"));
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_unexpected_content_type() {
-		let url                         = "https://api.example.com/api/latest";
-		let content_type                = "text/plain";
-		let expected_content_type       = s!("application/json");
-		let json                        = json!({
-			"version": s!("3.3.3"),
-		}).to_string();
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some(content_type),
-			Some(json.len()),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
-		let updater = setup_safe_updater(
-			Version::new(1, 0, 0),
-			"https://api.example.com/api/",
-			public_key,
-			MockClient::new(),
-		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
-		assert_err_eq!(err.clone(), UpdaterError::UnexpectedContentType(url.parse().unwrap(), content_type.to_owned(), expected_content_type.clone()));
-		assert_eq!(err.unwrap_err().to_string(), format!(r#"HTTP response from {url} had unexpected content type: "{content_type}", expected: "{expected_content_type}""#));
-	}
-	#[tokio::test]
-	async fn decode_and_verify__err_missing_data() {
-		let url                         = "https://api.example.com/api/latest";
-		let content_type                = "application/json";
-		let json                        = json!({
-			"version": s!("3.3.3"),
-		}).to_string();
-		let content_len                 = json.len();
-		let expected_content_len        = json.len() + 1;
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some(content_type),
-			Some(expected_content_len),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
-		let updater = setup_safe_updater(
+	
+	//		verify_signature													
+	#[test]
+	fn verify_signature() {
+		let private_key = generate_keypair().0;
+		let url         = "https://api.example.com/api/events".parse().unwrap();
+		let updater     = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			private_key.verifying_key(),
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
-		assert_err_eq!(err.clone(), UpdaterError::MissingData(url.parse().unwrap(), content_len, expected_content_len));
-		assert_eq!(err.unwrap_err().to_string(), format!("HTTP response body from {url} is shorter than expected: {content_len} < {expected_content_len}"));
+		let signature   = private_key.sign(b"Test payload").to_string();
+		assert_ok!(updater.verify_signature(&url, b"Test payload", signature.clone()));
+		assert_err_eq!(updater.verify_signature(&url, b"Other payload", signature), UpdaterError::FailedSignatureVerification(url.clone()));
 	}
-	#[tokio::test]
-	async fn decode_and_verify__err_too_much_data() {
-		let url                         = "https://api.example.com/api/latest";
-		let content_type                = "application/json";
-		let json                        = json!({
-			"version": s!("3.3.3"),
-		}).to_string();
-		let content_len                 = json.len();
-		let expected_content_len        = json.len() - 1;
-		let (mock_response, public_key) = create_mock_response(
-			url,
-			StatusCode::OK,
-			Some(content_type),
-			Some(expected_content_len),
-			Ok(&json),
-			&ResponseSignature::Generate,
-		);
+	#[test]
+	fn verify_signature__err_missing_and_invalid() {
+		let url     = "https://api.example.com/api/events".parse().unwrap();
 		let updater = setup_safe_updater(
 			Version::new(1, 0, 0),
 			"https://api.example.com/api/",
-			public_key,
+			*EMPTY_PUBLIC_KEY,
 			MockClient::new(),
 		);
-		let err = updater.decode_and_verify::<LatestVersionResponse>(url.parse().unwrap(), mock_response).await;
-		assert_err_eq!(err.clone(), UpdaterError::TooMuchData(url.parse().unwrap(), content_len, expected_content_len));
-		assert_eq!(err.unwrap_err().to_string(), format!("HTTP response body from {url} is longer than expected: {content_len} > {expected_content_len}"));
+		assert_err_eq!(updater.verify_signature(&url, b"Test payload", s!("")),     UpdaterError::MissingSignature(url.clone()));
+		assert_err_eq!(updater.verify_signature(&url, b"Test payload", s!("1234")), UpdaterError::InvalidSignature(url.clone(), s!("1234")));
+		assert_err_eq!(updater.verify_signature(&url, b"Test payload", s!("xyz")),  UpdaterError::InvalidSignature(url.clone(), s!("xyz")));
 	}
 	
-	//		replace_executable													
+	//		replace_executable													
 	#[tokio::test]
 	async fn replace_executable() {
 		//	The lock and temp_dir need to be maintained for the duration of the test
@@ -1323,7 +3545,7 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			MockClient::new(),
 		);
-		assert_ok!(updater.replace_executable(&new_path).await);
+		assert_ok!(updater.replace_executable(&Version::new(2, 3, 4), &new_path).await);
 		assert!(exe_path.exists());
 		assert!(old_path.exists());
 		assert!(!new_path.exists());
@@ -1347,7 +3569,7 @@ mod updater_private {
 			MockClient::new(),
 		);
 		fs::remove_file(&new_path).unwrap();
-		let err = updater.replace_executable(&new_path).await;
+		let err = updater.replace_executable(&Version::new(2, 3, 4), &new_path).await;
 		assert_err_eq!(err.clone(), UpdaterError::UnableToMoveNewExe(new_path.clone(), s!("No such file or directory (os error 2)")));
 		assert_eq!(err.unwrap_err().to_string(), format!(r"Unable to move the new executable {new_path:?}: No such file or directory (os error 2)"));
 		assert!(!exe_path.exists());
@@ -1365,7 +3587,7 @@ mod updater_private {
 			MockClient::new(),
 		);
 		fs::remove_file(&exe_path).unwrap();
-		let err = updater.replace_executable(&new_path).await;
+		let err = updater.replace_executable(&Version::new(2, 3, 4), &new_path).await;
 		assert_err_eq!(err.clone(), UpdaterError::UnableToRenameCurrentExe(exe_path.clone(), s!("No such file or directory (os error 2)")));
 		assert_eq!(err.unwrap_err().to_string(), format!(r"Unable to rename the current executable {exe_path:?}: No such file or directory (os error 2)"));
 		assert!(!exe_path.exists());
@@ -1378,9 +3600,129 @@ mod updater_private {
 		//	It's also quite unlikely to occur.
 	}
 	
-	//		restart																
+	//		copy_executable														
 	#[tokio::test]
-	async fn restart() {
+	async fn copy_executable() {
+		//	The lock and temp_dir need to be maintained for the duration of the test
+		let (_lock, _temp_dir, exe_path, _old_path, new_path) = setup_files();
+		let contents    = "update contents".repeat(10_000);
+		fs::write(&new_path, &contents).unwrap();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.progress_policy = ProgressPolicy::Bytes(64 * 1_024);
+		let events      = updater.subscribe_events();
+		assert_ok!(updater.copy_executable(&Version::new(2, 3, 4), &new_path, &exe_path).await);
+		assert_eq!(fs::read_to_string(&exe_path).unwrap(), contents);
+		assert!(new_path.exists());
+		assert_eq!(updater.status(), Status::Installing(Version::new(2, 3, 4), Progress::new(150_000, 150_000, Some(Duration::ZERO))));
+		//	The progress is reported as the file is copied
+		let reported: Vec<_> = events.drain().filter_map(|status|
+			if let Status::Installing(_, progress) = status { Some(progress.done) } else { None }
+		).collect();
+		assert_eq!(reported, vec![64 * 1_024, 128 * 1_024, 150_000]);
+	}
+	
+	//		drain_expired														
+	#[tokio::test]
+	async fn drain_expired__rollback_after_restarting() {
+		let (_lock, _temp_dir, exe_path, old_path, new_path) = setup_files();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.drain_policy = DrainPolicy::new(Duration::from_millis(50), DrainAction::Rollback);
+		assert_ok!(updater.replace_executable(&Version::new(2, 3, 4), &new_path).await);
+		//	The last critical action has finished, and the restart has begun
+		updater.set_status(Status::Restarting(Version::new(2, 3, 4)));
+		updater.drain_expired(Version::new(2, 3, 4)).await;
+		assert_eq!(updater.status(), Status::Restarting(Version::new(2, 3, 4)));
+		assert!(old_path.exists());
+		assert_eq!(fs::read_to_string(exe_path).unwrap(), "update contents");
+	}
+	
+	//		shut_down															
+	#[tokio::test]
+	async fn shut_down() {
+		//	The lock needs to be maintained for the duration of the test. We call
+		//	setup_files() to ensure that the mock executable path is set.
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let updater = Arc::new_cyclic(|this| {
+			let mut updater = setup_safe_updater(
+				Version::new(1, 0, 0),
+				"https://api.example.com/api/",
+				*EMPTY_PUBLIC_KEY,
+				MockClient::new(),
+			);
+			updater.this = Weak::clone(this);
+			updater
+		});
+		let calls   = Arc::new(AtomicUsize::new(0));
+		let counter = Arc::clone(&calls);
+		updater.on_shutdown(move || async move {
+			let _previous = counter.fetch_add(1, Ordering::SeqCst);
+		});
+		//	The hook is run in the background, before relaunching
+		updater.shut_down();
+		for _ in 0..100 {
+			if calls.load(Ordering::SeqCst) > 0 {
+				break;
+			}
+			sleep(Duration::from_millis(10)).await;
+		}
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+		assert_none!(updater.shutdown.lock().as_ref());
+	}
+	
+	//		run_shutdown_hook													
+	#[tokio::test]
+	async fn run_shutdown_hook() {
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let calls   = Arc::new(AtomicUsize::new(0));
+		let counter = Arc::clone(&calls);
+		updater.on_shutdown(move || async move {
+			sleep(Duration::from_millis(10)).await;
+			let _previous = counter.fetch_add(1, Ordering::SeqCst);
+		});
+		let hook    = updater.shutdown.lock().take().unwrap();
+		updater.run_shutdown_hook(hook).await;
+		assert_eq!(calls.load(Ordering::SeqCst), 1);
+	}
+	#[tokio::test]
+	async fn run_shutdown_hook__timeout() {
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.shutdown_timeout = Duration::from_millis(10);
+		let calls       = Arc::new(AtomicUsize::new(0));
+		let counter     = Arc::clone(&calls);
+		updater.on_shutdown(move || async move {
+			sleep(Duration::from_secs(10)).await;
+			let _previous = counter.fetch_add(1, Ordering::SeqCst);
+		});
+		let hook        = updater.shutdown.lock().take().unwrap();
+		let start       = Instant::now();
+		updater.run_shutdown_hook(hook).await;
+		assert!(start.elapsed() < Duration::from_secs(5));
+		assert_eq!(calls.load(Ordering::SeqCst), 0);
+	}
+	
+	//		relaunch															
+	#[tokio::test]
+	async fn relaunch__exec() {
 		//	The lock needs to be maintained for the duration of the test. We call
 		//	setup_files() to ensure that the mock executable path is set.
 		let (_lock, _temp_dir, _, _, _) = setup_files();
@@ -1390,46 +3732,189 @@ mod updater_private {
 			*EMPTY_PUBLIC_KEY,
 			MockClient::new(),
 		);
-		//	The code being tested will call FakeCommand::new(), which will return a
-		//	wrapper around a MockCommand that is already set up with the necessary
-		//	expectations.
-		updater.restart();
+		//	The code being tested will call FakeCommand::new(), which records the
+		//	calls made instead of replacing the process.
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![
+			ProcessCall::Exec(updater.exe_path.clone(), args().skip(1).collect()),
+			ProcessCall::Exit(1),
+		]);
+	}
+	#[tokio::test]
+	async fn relaunch__spawn_and_exit() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.restart_strategy = RestartStrategy::SpawnAndExit;
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![
+			ProcessCall::Spawn(updater.exe_path.clone(), args().skip(1).collect()),
+			ProcessCall::Exit(0),
+		]);
+	}
+	#[tokio::test]
+	async fn relaunch__exec_with_listeners() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let http    = TcpListener::bind("127.0.0.1:0").unwrap();
+		let admin   = TcpListener::bind("127.0.0.1:0").unwrap();
+		assert_ok!(updater.register_listener("http",  &http));
+		assert_ok!(updater.register_listener("admin", &admin));
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![
+			ProcessCall::MapFds(vec![3, 4]),
+			ProcessCall::Env(s!("LISTEN_FDS"),     Some(s!("2"))),
+			ProcessCall::Env(s!("LISTEN_FDNAMES"), Some(s!("http:admin"))),
+			ProcessCall::Env(s!("LISTEN_PID"),     Some(process_id().to_string())),
+			ProcessCall::Exec(updater.exe_path.clone(), args().skip(1).collect()),
+			ProcessCall::Exit(1),
+		]);
+	}
+	#[tokio::test]
+	async fn relaunch__spawn_and_exit_with_listeners() {
+		let (_lock, _temp_dir, _, _, _) = setup_files();
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.restart_strategy = RestartStrategy::SpawnAndExit;
+		let http        = TcpListener::bind("127.0.0.1:0").unwrap();
+		assert_ok!(updater.register_listener("http", &http));
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![
+			ProcessCall::MapFds(vec![3]),
+			ProcessCall::Env(s!("LISTEN_FDS"),     Some(s!("1"))),
+			ProcessCall::Env(s!("LISTEN_FDNAMES"), Some(s!("http"))),
+			ProcessCall::Env(s!("LISTEN_PID"),     None),
+			ProcessCall::Spawn(updater.exe_path.clone(), args().skip(1).collect()),
+			ProcessCall::Exit(0),
+		]);
+	}
+	#[tokio::test]
+	async fn relaunch__exit() {
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.restart_strategy = RestartStrategy::Exit(75);
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![ProcessCall::Exit(75)]);
+	}
+	#[tokio::test]
+	async fn relaunch__callback() {
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		let called      = Arc::new(Mutex::new(None));
+		let recorder    = Arc::clone(&called);
+		updater.config.restart_strategy = RestartStrategy::Callback(Arc::new(move |path: &Path| {
+			*recorder.lock() = Some(path.to_path_buf());
+		}));
+		updater.exe_path = PathBuf::from("/opt/app/bin/app");
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(*called.lock(), Some(PathBuf::from("/opt/app/bin/app")));
+		assert!(take_calls().is_empty());
+	}
+	
+	//		notify_systemd														
+	#[tokio::test]
+	async fn notify_systemd() {
+		//	The notification socket is not set when running tests, so this checks
+		//	that the restart is not affected when there is nothing to notify.
+		let mut updater = setup_safe_updater(
+			Version::new(1, 0, 0),
+			"https://api.example.com/api/",
+			*EMPTY_PUBLIC_KEY,
+			MockClient::new(),
+		);
+		updater.config.restart_strategy = RestartStrategy::Exit(0);
+		updater.config.systemd_notify   = true;
+		drop(take_calls());
+		updater.relaunch();
+		assert_eq!(take_calls(), vec![ProcessCall::Exit(0)]);
 	}
 }
 
 //		Functions																
 #[cfg(test)]
 mod functions {
-	use std::collections::HashMap;
 	use super::*;
 	
-	//		get_header															
+	//		check_redirect														
 	#[test]
-	fn get_header__string() {
-		let mock_response = create_sham_response(
-			"http://127.0.0.1",
-			StatusCode::OK,
-			Some("text/plain"),
-			Some("Test body".len()),
-			HashMap::<String, String>::new(),
-			Ok("Test body".as_ref()),
-		);
-		let content_type: String = get_header(&mock_response, CONTENT_TYPE);
-		assert_eq!(content_type, s!("text/plain"));
+	fn check_redirect__allowed() {
+		let api: Url = "https://api.example.com/api/releases/2.3.4".parse().unwrap();
+		let cdn: Url = "https://cdn.example.com/releases/test-2.3.4".parse().unwrap();
+		let old: Url = "http://api.example.com/api/releases/2.3.4".parse().unwrap();
+		assert_ok!(check_redirect(&cdn, slice::from_ref(&api),             10));
+		assert_ok!(check_redirect(&cdn, &[api, cdn.clone()],                2));
+		assert_ok!(check_redirect(&"http://cdn.example.com/test".parse().unwrap(), &[old], 10));
 	}
 	#[test]
-	fn get_header__integer() {
-		let mock_response = create_sham_response(
-			"http://127.0.0.1",
-			StatusCode::OK,
-			Some("text/plain"),
-			Some(1234),
-			HashMap::<String, String>::new(),
-			Ok("Test body".as_ref()),
-		);
-		let content_length: usize = get_header(&mock_response, CONTENT_LENGTH);
-		assert_eq!(content_length, 1234);
+	fn check_redirect__err_too_many() {
+		let api: Url = "https://api.example.com/api/releases/2.3.4".parse().unwrap();
+		let cdn: Url = "https://cdn.example.com/releases/test-2.3.4".parse().unwrap();
+		assert_err_eq!(check_redirect(&cdn, slice::from_ref(&api),  0), format!("Too many redirects, stopped at {cdn}"));
+		assert_err_eq!(check_redirect(&cdn, &[api.clone(), api],    1), format!("Too many redirects, stopped at {cdn}"));
+	}
+	#[test]
+	fn check_redirect__err_downgrade() {
+		let api: Url = "https://api.example.com/api/releases/2.3.4".parse().unwrap();
+		let cdn: Url = "http://cdn.example.com/releases/test-2.3.4".parse().unwrap();
+		assert_err_eq!(check_redirect(&cdn, &[api], 10), format!("Refused to follow a redirect from HTTPS to plain HTTP: {cdn}"));
+	}
+	
+	//		find_event_end														
+	#[test]
+	fn find_event_end() {
+		assert_some_eq!(super::find_event_end(b"data: a\n\ndata: b\n\n"),   9);
+		assert_some_eq!(super::find_event_end(b"data: a\r\n\r\ndata: b"),   11);
+		assert_some_eq!(super::find_event_end(b"data: a\r\rdata: b\n\n"),   9);
+		assert_some_eq!(super::find_event_end(b"data: a\n\ndata: b\r\r"),   9);
+	}
+	#[test]
+	fn find_event_end__incomplete() {
+		assert_none!(super::find_event_end(b""));
+		assert_none!(super::find_event_end(b"data: a\n"));
+		assert_none!(super::find_event_end(b"data: a\r\n\r"));
+	}
+	
+	//		parse_event															
+	#[test]
+	fn parse_event__release() {
+		assert_some_eq!(parse_event("event: release\ndata: {}\n\n"),          s!("{}"));
+		assert_some_eq!(parse_event("id: 1\nevent:release\ndata:{}\n"),        s!("{}"));
+		assert_some_eq!(parse_event("event: release\ndata: a\ndata: b\n\n"),  s!("a\nb"));
+		assert_some_eq!(parse_event("event: release\r\ndata: {}\r\n\r\n"),  s!("{}"));
+		assert_some_eq!(parse_event("event: release\rdata: a\rdata: b\r\r"),  s!("a\nb"));
+	}
+	#[test]
+	fn parse_event__ignored() {
+		assert_none!(parse_event(":\n\n"));
+		assert_none!(parse_event("data: {}\n\n"));
+		assert_none!(parse_event("event: other\ndata: {}\n\n"));
+		assert_none!(parse_event("event: release\n\n"));
 	}
 }
-
-
@@ -0,0 +1,126 @@
+//		Packages																										
+
+use super::*;
+use claims::assert_ok;
+use tokio::{
+	io::{AsyncReadExt as _, DuplexStream, duplex},
+	sync::broadcast,
+};
+
+
+
+//		Common																											
+
+//		setup_terminal_consent													
+/// This function sets up a `TerminalConsent` instance for testing.
+/// 
+/// The user's answers are written to the returned input stream, and whatever
+/// the instance writes can be read from the returned output stream.
+/// 
+fn setup_terminal_consent(answers: &str) -> (TerminalConsent, DuplexStream) {
+	let (mut user_in, input)    = duplex(1024);
+	let (output, user_out)      = duplex(1024);
+	let text                    = answers.to_owned();
+	drop(spawn(async move {
+		user_in.write_all(text.as_bytes()).await.unwrap();
+	}));
+	(TerminalConsent::with_io(Duration::from_secs(60), input, output), user_out)
+}
+
+//		read_output																
+/// This function reads everything written so far to the output stream.
+async fn read_output(mut stream: DuplexStream) -> String {
+	let mut buffer = vec![0; 1024];
+	let len        = stream.read(&mut buffer).await.unwrap();
+	String::from_utf8(buffer[..len].to_vec()).unwrap()
+}
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod terminal_consent {
+	use super::*;
+	
+	//		request																
+	#[tokio::test]
+	async fn request__approve() {
+		for answer in ["y\n", "yes\n", "YES\n", "\n"] {
+			let (consent, _output) = setup_terminal_consent(answer);
+			assert_eq!(consent.request(ConsentStep::Download, &Version::new(2, 3, 4)).await, ConsentDecision::Approve);
+		}
+	}
+	#[tokio::test]
+	async fn request__defer() {
+		for answer in ["l\n", "later\n"] {
+			let (consent, _output) = setup_terminal_consent(answer);
+			assert_eq!(
+				consent.request(ConsentStep::Install, &Version::new(2, 3, 4)).await,
+				ConsentDecision::Defer(Duration::from_secs(60)),
+			);
+		}
+	}
+	#[tokio::test]
+	async fn request__decline() {
+		for answer in ["n\n", "never\n"] {
+			let (consent, _output) = setup_terminal_consent(answer);
+			assert_eq!(consent.request(ConsentStep::Restart, &Version::new(2, 3, 4)).await, ConsentDecision::Decline);
+		}
+	}
+	#[tokio::test]
+	async fn request__invalid_answer() {
+		let (consent, output) = setup_terminal_consent("maybe\nn\n");
+		assert_eq!(consent.request(ConsentStep::Download, &Version::new(2, 3, 4)).await, ConsentDecision::Decline);
+		drop(consent);
+		assert_eq!(read_output(output).await, format!("{0}{0}",
+			"Version 2.3.4 is available. Download it now? [Y]es, [l]ater, or [n]ever: ",
+		));
+	}
+	#[tokio::test]
+	async fn request__closed_input() {
+		let (consent, _output) = setup_terminal_consent("");
+		assert_eq!(
+			consent.request(ConsentStep::Download, &Version::new(2, 3, 4)).await,
+			ConsentDecision::Defer(Duration::from_secs(60)),
+		);
+	}
+	
+	//		show_progress														
+	#[tokio::test]
+	async fn show_progress() {
+		let (consent, output) = setup_terminal_consent("");
+		let (tx, rx)          = broadcast::channel(4);
+		let handle            = consent.show_progress(rx);
+		assert_ok!(tx.send(Status::Checking));
+		assert_ok!(tx.send(Status::Downloading(Version::new(2, 3, 4), Progress::new(512, 1_024, None))));
+		assert_ok!(tx.send(Status::Downloaded(Version::new(2, 3, 4))));
+		drop(tx);
+		handle.await.unwrap();
+		drop(consent);
+		assert_eq!(read_output(output).await, format!("\r{}\n", progress_bar(&Version::new(2, 3, 4), &Progress::new(512, 1_024, None))));
+	}
+}
+
+#[cfg(test)]
+mod functions {
+	use super::*;
+	
+	//		progress_bar														
+	#[test]
+	fn progress_bar() {
+		let version = Version::new(2, 3, 4);
+		assert_eq!(
+			super::progress_bar(&version, &Progress::new(0, 1_024, None)),
+			"Downloading 2.3.4: [                              ]   0%                     ",
+		);
+		assert_eq!(
+			super::progress_bar(&version, &Progress::new(512, 1_024, Some(Duration::from_secs(3)))),
+			"Downloading 2.3.4: [###############               ]  50% 3s remaining        ",
+		);
+		assert_eq!(
+			super::progress_bar(&version, &Progress::new(1_024, 1_024, Some(Duration::ZERO))),
+			"Downloading 2.3.4: [##############################] 100% 0s remaining        ",
+		);
+	}
+}
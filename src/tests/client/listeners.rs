@@ -0,0 +1,81 @@
+//		Packages																										
+
+use super::*;
+use claims::{assert_none, assert_ok};
+use rubedo::sugar::s;
+use core::net::{Ipv4Addr, SocketAddr};
+use tempfile::tempdir;
+
+
+
+//		Common																											
+
+//		setup_listeners															
+/// This function sets up an `InheritedListeners` instance with no sockets.
+fn setup_listeners(names: &str) -> InheritedListeners {
+	InheritedListeners {
+		fds:   ListenFd::empty(),
+		names: parse_names(names),
+	}
+}
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod inherited_listeners {
+	use super::*;
+	
+	//		is_empty															
+	#[test]
+	fn is_empty() {
+		let listeners = setup_listeners("");
+		assert!(listeners.is_empty());
+		assert_eq!(listeners.len(), 0);
+	}
+	
+	//		take_tcp_listener													
+	#[test]
+	fn take_tcp_listener__not_inherited() {
+		//	The names are known, but the file descriptors were not passed on, so
+		//	nothing can be taken.
+		let mut listeners = setup_listeners("http:admin");
+		assert_eq!(listeners.names(), &[s!("http"), s!("admin")]);
+		assert_none!(listeners.take_tcp_listener("http").unwrap());
+		assert_none!(listeners.take_tcp_listener("other").unwrap());
+	}
+	
+	//		tcp_listener														
+	#[test]
+	fn tcp_listener__bind() {
+		let mut listeners = setup_listeners("");
+		let listener      = listeners.tcp_listener("http", SocketAddr::from((Ipv4Addr::LOCALHOST, 0))).unwrap();
+		assert_ok!(listener.local_addr());
+	}
+	
+	//		unix_listener														
+	#[test]
+	fn unix_listener__bind() {
+		let temp_dir      = tempdir().unwrap();
+		let socket_path   = temp_dir.path().join("app.sock");
+		let mut listeners = setup_listeners("");
+		let _listener     = listeners.unix_listener("app", &socket_path).unwrap();
+		assert!(socket_path.exists());
+	}
+}
+
+#[cfg(test)]
+mod functions {
+	use super::*;
+	
+	//		parse_names															
+	#[test]
+	fn parse_names() {
+		assert!(super::parse_names("").is_empty());
+		assert_eq!(super::parse_names("http"),       vec![s!("http")]);
+		assert_eq!(super::parse_names("http:admin"), vec![s!("http"), s!("admin")]);
+	}
+}
+
+
@@ -0,0 +1,209 @@
+//		Packages																										
+
+use super::*;
+use claims::{assert_none, assert_some_eq};
+
+
+
+//		Constants																										
+
+/// Midnight UTC on Monday 1st January 2024.
+const MONDAY: u64 = 1_704_067_200;
+
+/// The number of seconds in an hour.
+const HOUR: u64 = 3_600;
+
+
+
+//		Common																											
+
+//		at																		
+/// Gets the time that is the specified number of seconds after [`MONDAY`].
+fn at(seconds: u64) -> SystemTime {
+	UNIX_EPOCH + Duration::from_secs(MONDAY + seconds)
+}
+
+//		time																	
+/// Creates a time of day, for brevity.
+fn time(hour: u8, minute: u8) -> TimeOfDay {
+	TimeOfDay::new(hour, minute).unwrap()
+}
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod maintenance_schedule {
+	use super::*;
+	
+	//		is_open																
+	#[test]
+	fn is_open__daily() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 0);
+		assert!(!schedule.is_open(at(HOUR)));
+		assert!( schedule.is_open(at(2 * HOUR)));
+		assert!( schedule.is_open(at(4 * HOUR - 1)));
+		assert!(!schedule.is_open(at(4 * HOUR)));
+		assert!( schedule.is_open(at(5 * 24 * HOUR + 3 * HOUR)));
+	}
+	#[test]
+	fn is_open__weekly() {
+		let schedule = MaintenanceSchedule::new(vec![
+			MaintenanceWindow::weekly(&[Weekday::Saturday, Weekday::Sunday], time(9, 0), time(17, 0)),
+		], 0);
+		assert!(!schedule.is_open(at(12 * HOUR)));
+		assert!(!schedule.is_open(at(4 * 24 * HOUR + 12 * HOUR)));
+		assert!( schedule.is_open(at(5 * 24 * HOUR + 12 * HOUR)));
+		assert!( schedule.is_open(at(6 * 24 * HOUR + 12 * HOUR)));
+	}
+	#[test]
+	fn is_open__overnight() {
+		//	The window opens on Sunday evening and closes on Monday morning
+		let schedule = MaintenanceSchedule::new(vec![
+			MaintenanceWindow::weekly(&[Weekday::Sunday], time(22, 0), time(6, 0)),
+		], 0);
+		assert!( schedule.is_open(at(5 * HOUR)));
+		assert!(!schedule.is_open(at(6 * HOUR)));
+		assert!(!schedule.is_open(at(22 * HOUR)));
+		assert!( schedule.is_open(at(6 * 24 * HOUR + 23 * HOUR)));
+	}
+	#[test]
+	fn is_open__whole_day() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(0, 0), time(0, 0))], 0);
+		assert!(schedule.is_open(at(0)));
+		assert!(schedule.is_open(at(13 * HOUR)));
+	}
+	#[test]
+	fn is_open__utc_offset() {
+		//	02:00 to 04:00 at UTC+5 is 21:00 to 23:00 UTC on the previous day
+		let east = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 5 * 3_600);
+		assert!(!east.is_open(at(2 * HOUR)));
+		assert!( east.is_open(at(21 * HOUR)));
+		//	02:00 to 04:00 at UTC-5 is 07:00 to 09:00 UTC
+		let west = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], -5 * 3_600);
+		assert!(!west.is_open(at(2 * HOUR)));
+		assert!( west.is_open(at(8 * HOUR)));
+	}
+	#[test]
+	fn is_open__no_windows() {
+		let schedule = MaintenanceSchedule::new(vec![], 0);
+		assert!(!schedule.is_open(at(0)));
+	}
+	
+	//		next_opening														
+	#[test]
+	fn next_opening__later_today() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 30), time(4, 0))], 0);
+		assert_some_eq!(schedule.next_opening(at(HOUR)), at(2 * HOUR + 1_800));
+	}
+	#[test]
+	fn next_opening__tomorrow() {
+		//	The window is open, but the next opening is still reported
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 0);
+		assert_some_eq!(schedule.next_opening(at(3 * HOUR)), at(26 * HOUR));
+	}
+	#[test]
+	fn next_opening__next_week() {
+		let schedule = MaintenanceSchedule::new(vec![
+			MaintenanceWindow::weekly(&[Weekday::Monday], time(2, 0), time(4, 0)),
+		], 0);
+		assert_some_eq!(schedule.next_opening(at(3 * HOUR)), at(7 * 24 * HOUR + 2 * HOUR));
+	}
+	#[test]
+	fn next_opening__earliest_window() {
+		let schedule = MaintenanceSchedule::new(vec![
+			MaintenanceWindow::weekly(&[Weekday::Friday], time(1, 0), time(2, 0)),
+			MaintenanceWindow::weekly(&[Weekday::Wednesday], time(23, 0), time(1, 0)),
+		], 0);
+		assert_some_eq!(schedule.next_opening(at(0)), at(2 * 24 * HOUR + 23 * HOUR));
+	}
+	#[test]
+	fn next_opening__utc_offset() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 3_600);
+		assert_some_eq!(schedule.next_opening(at(0)), at(HOUR));
+	}
+	#[test]
+	fn next_opening__ignores_fractions() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 0);
+		assert_some_eq!(schedule.next_opening(at(HOUR) + Duration::from_millis(500)), at(2 * HOUR));
+	}
+	#[test]
+	fn next_opening__no_windows() {
+		let no_windows = MaintenanceSchedule::new(vec![], 0);
+		assert_none!(no_windows.next_opening(at(0)));
+		let no_days    = MaintenanceSchedule::new(vec![MaintenanceWindow::weekly(&[], time(2, 0), time(4, 0))], 0);
+		assert_none!(no_days.next_opening(at(0)));
+	}
+	
+	//		time_until_open														
+	#[test]
+	fn time_until_open() {
+		let schedule = MaintenanceSchedule::new(vec![MaintenanceWindow::daily(time(2, 0), time(4, 0))], 0);
+		assert_some_eq!(schedule.time_until_open(at(HOUR)),     Duration::from_secs(HOUR));
+		assert_some_eq!(schedule.time_until_open(at(3 * HOUR)), Duration::ZERO);
+		assert_none!(MaintenanceSchedule::new(vec![], 0).time_until_open(at(0)));
+	}
+}
+
+#[cfg(test)]
+mod maintenance_scope {
+	use super::*;
+	
+	//		covers_install														
+	#[test]
+	fn covers_install() {
+		assert!( MaintenanceScope::Install.covers_install());
+		assert!(!MaintenanceScope::Restart.covers_install());
+		assert!( MaintenanceScope::InstallAndRestart.covers_install());
+	}
+	
+	//		covers_restart														
+	#[test]
+	fn covers_restart() {
+		assert!(!MaintenanceScope::Install.covers_restart());
+		assert!( MaintenanceScope::Restart.covers_restart());
+		assert!( MaintenanceScope::InstallAndRestart.covers_restart());
+	}
+}
+
+#[cfg(test)]
+mod time_of_day {
+	use super::*;
+	
+	//		new																	
+	#[test]
+	fn new() {
+		let time = TimeOfDay::new(13, 45).unwrap();
+		assert_eq!(time.hour(),   13);
+		assert_eq!(time.minute(), 45);
+		assert_none!(TimeOfDay::new(24, 0));
+		assert_none!(TimeOfDay::new(0, 60));
+	}
+	
+	//		display																
+	#[test]
+	fn display() {
+		assert_eq!(time(9, 5).to_string(), "09:05");
+	}
+}
+
+#[cfg(test)]
+mod weekday {
+	use super::*;
+	
+	//		from_day_number														
+	#[test]
+	fn from_day_number() {
+		assert_eq!(Weekday::from_day_number(0),      Weekday::Thursday);
+		assert_eq!(Weekday::from_day_number(-1),     Weekday::Wednesday);
+		assert_eq!(Weekday::from_day_number(19_723), Weekday::Monday);
+		assert_eq!(Weekday::from_day_number(19_729), Weekday::Sunday);
+	}
+	
+	//		display																
+	#[test]
+	fn display() {
+		assert_eq!(Weekday::Wednesday.to_string(), "Wednesday");
+	}
+}
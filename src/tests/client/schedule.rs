@@ -0,0 +1,164 @@
+//		Packages																										
+
+use super::*;
+use claims::{assert_err_eq, assert_none, assert_ok, assert_some_eq};
+use rubedo::sugar::s;
+
+
+
+//		Constants																										
+
+/// Midnight UTC on Monday 1st January 2024.
+const MONDAY: u64 = 1_704_067_200;
+
+/// The number of seconds in an hour.
+const HOUR: u64 = 3_600;
+
+/// The number of seconds in a day.
+const DAY: u64 = 86_400;
+
+
+
+//		Common																											
+
+//		at																		
+/// Gets the time that is the specified number of seconds after [`MONDAY`].
+fn at(seconds: u64) -> SystemTime {
+	UNIX_EPOCH + Duration::from_secs(MONDAY + seconds)
+}
+
+//		schedule																
+/// Parses a schedule, for brevity.
+fn schedule(expression: &str) -> CheckSchedule {
+	expression.parse().unwrap()
+}
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod check_schedule {
+	use super::*;
+	
+	//		from_str															
+	#[test]
+	fn from_str() {
+		let parsed = schedule("*/15 9-17 * * 1-5");
+		assert_eq!(parsed.minutes,  1 | (1 << 15) | (1 << 30) | (1 << 45));
+		assert_eq!(parsed.hours,    0b11_1111_1110_0000_0000);
+		assert_eq!(parsed.weekdays, 0b011_1110);
+		assert!(!parsed.days_set);
+		assert!( parsed.weekdays_set);
+		assert_eq!(parsed.to_string(), "*/15 9-17 * * 1-5");
+	}
+	#[test]
+	fn from_str__lists_and_steps() {
+		let parsed = schedule("0,30 10/6 1,15 1-12/3 *");
+		assert_eq!(parsed.minutes, 1 | (1 << 30));
+		assert_eq!(parsed.hours,   (1 << 10) | (1 << 16) | (1 << 22));
+		assert_eq!(parsed.days,    (1 << 1) | (1 << 15));
+		assert_eq!(parsed.months,  (1 << 1) | (1 << 4) | (1 << 7) | (1 << 10));
+	}
+	#[test]
+	fn from_str__sunday() {
+		assert_eq!(schedule("0 0 * * 7").weekdays, 1);
+		assert_eq!(schedule("0 0 * * 0").weekdays, 1);
+	}
+	#[test]
+	fn from_str__shorthands() {
+		assert_eq!(schedule("@hourly").minutes,  1);
+		assert_eq!(schedule("@daily").hours,     1);
+		assert_eq!(schedule("@midnight").hours,  1);
+		assert_eq!(schedule("@weekly").weekdays, 1);
+		assert_eq!(schedule("@monthly").days,    1 << 1);
+		assert_eq!(schedule("@Daily").to_string(), "@Daily");
+	}
+	#[test]
+	fn from_str__daily_at() {
+		let parsed = schedule("daily at 03:45");
+		assert_eq!(parsed.minutes, 1 << 45);
+		assert_eq!(parsed.hours,   1 << 3);
+		assert_ok!("Daily at 23:59".parse::<CheckSchedule>());
+	}
+	#[test]
+	fn from_str__err_field_count() {
+		assert_err_eq!("* * * *".parse::<CheckSchedule>(), ScheduleError::InvalidFieldCount(s!("* * * *")));
+		assert_err_eq!("".parse::<CheckSchedule>(),        ScheduleError::InvalidFieldCount(s!("")));
+	}
+	#[test]
+	fn from_str__err_field() {
+		assert_err_eq!("60 * * * *".parse::<CheckSchedule>(),  ScheduleError::InvalidField(s!("minute"),       s!("60")));
+		assert_err_eq!("* 5-2 * * *".parse::<CheckSchedule>(), ScheduleError::InvalidField(s!("hour"),         s!("5-2")));
+		assert_err_eq!("* * 0 * *".parse::<CheckSchedule>(),   ScheduleError::InvalidField(s!("day of month"), s!("0")));
+		assert_err_eq!("* * * x *".parse::<CheckSchedule>(),   ScheduleError::InvalidField(s!("month"),        s!("x")));
+		assert_err_eq!("* * * * */0".parse::<CheckSchedule>(), ScheduleError::InvalidField(s!("day of week"),  s!("*/0")));
+	}
+	#[test]
+	fn from_str__err_time() {
+		assert_err_eq!("daily at 24:00".parse::<CheckSchedule>(), ScheduleError::InvalidTime(s!("24:00")));
+		assert_err_eq!("daily at noon".parse::<CheckSchedule>(),  ScheduleError::InvalidTime(s!("noon")));
+	}
+	
+	//		next_after															
+	#[test]
+	fn next_after__every_minute() {
+		assert_some_eq!(schedule("* * * * *").next_after(at(0)),  at(60));
+		assert_some_eq!(schedule("* * * * *").next_after(at(59)), at(60));
+	}
+	#[test]
+	fn next_after__daily_at() {
+		let daily = schedule("daily at 03:45");
+		assert_some_eq!(daily.next_after(at(0)),                 at(3 * HOUR + 45 * 60));
+		assert_some_eq!(daily.next_after(at(3 * HOUR + 45 * 60)), at(DAY + 3 * HOUR + 45 * 60));
+	}
+	#[test]
+	fn next_after__weekdays() {
+		//	Saturday morning is followed by Monday
+		let weekdays = schedule("0 9 * * 1-5");
+		assert_some_eq!(weekdays.next_after(at(5 * DAY)), at(7 * DAY + 9 * HOUR));
+	}
+	#[test]
+	fn next_after__day_of_month_or_week() {
+		//	The 15th of January 2024 was a Monday, but Friday the 5th comes first
+		let either = schedule("0 0 15 * 5");
+		assert_some_eq!(either.next_after(at(0)), at(4 * DAY));
+	}
+	#[test]
+	fn next_after__month() {
+		//	The 1st of March 2024 is 60 days after the 1st of January
+		assert_some_eq!(schedule("0 0 1 3 *").next_after(at(0)), at(60 * DAY));
+	}
+	#[test]
+	fn next_after__leap_day() {
+		//	The 29th of February 2024 is 59 days after the 1st of January
+		assert_some_eq!(schedule("0 0 29 2 *").next_after(at(0)), at(59 * DAY));
+	}
+	#[test]
+	fn next_after__utc_offset() {
+		let mut daily     = schedule("daily at 02:00");
+		daily.utc_offset  = 3_600;
+		assert_some_eq!(daily.next_after(at(0)), at(HOUR));
+		daily.utc_offset  = -3_600;
+		assert_some_eq!(daily.next_after(at(0)), at(3 * HOUR));
+	}
+	#[test]
+	fn next_after__never() {
+		assert_none!(schedule("0 0 31 2 *").next_after(at(0)));
+	}
+}
+
+#[cfg(test)]
+mod functions {
+	use super::*;
+	
+	//		civil_date															
+	#[test]
+	fn civil_date__dates() {
+		assert_eq!(civil_date(0),      (1, 1));
+		assert_eq!(civil_date(-1),     (12, 31));
+		assert_eq!(civil_date(19_723), (1, 1));
+		assert_eq!(civil_date(19_782), (2, 29));
+		assert_eq!(civil_date(19_783), (3, 1));
+	}
+}
@@ -0,0 +1,761 @@
+//		Packages																										
+
+use super::*;
+use crate::keys::generate_keypair;
+use crate::mocks::reqwest::{ResponseSignature, create_mock_binary_response, create_mock_response};
+use claims::{assert_err_eq, assert_none, assert_some};
+use ed25519_dalek::{Signature, Signer as _};
+use reqwest::StatusCode;
+use rubedo::crypto::SigningKey;
+use serde_json::json;
+use sha2::{Sha256, Digest as _};
+use sham::reqwest::{
+	MockClient,
+	MockError,
+	create_mock_client,
+	create_mock_response as create_sham_response,
+};
+use std::fs as std_fs;
+use tempfile::{TempDir, tempdir};
+
+
+
+//		Common																											
+
+//		setup_source															
+/// This function sets up an `HttpSource` instance for testing.
+/// 
+/// The `HttpSource` instance is created with a mock `Client` instance, so that
+/// no actual network requests will be made.
+/// 
+fn setup_source(api: &str, mirrors: &[&str], mock_client: MockClient) -> HttpSource {
+	HttpSource {
+		api:           api.parse().unwrap(),
+		client:        mock_client,
+		mirror:        Mutex::new((0, None)),
+		mirrors:       mirrors.iter().map(|mirror| mirror.parse().unwrap()).collect(),
+		primary_retry: Duration::from_secs(300),
+		token:         None,
+	}
+}
+
+//		setup_directory															
+/// This function sets up a directory of update files for testing.
+/// 
+/// The directory contains signed metadata for version 2.3.4 as the latest
+/// version, along with its release file. The directory is returned along with
+/// the private key used for signing.
+/// 
+fn setup_directory() -> (TempDir, SigningKey) {
+	let dir     = tempdir().unwrap();
+	let key     = generate_keypair().0;
+	let latest  = json!({ "version": "2.3.4" }).to_string();
+	let hash    = json!({ "version": "2.3.4", "hash": hex::encode(Sha256::digest(b"Test payload")) }).to_string();
+	std_fs::create_dir(dir.path().join("hashes")).unwrap();
+	std_fs::create_dir(dir.path().join("releases")).unwrap();
+	std_fs::write(dir.path().join("latest"),               &latest).unwrap();
+	std_fs::write(dir.path().join("latest.sig"),           format!("{}\n", key.sign(latest.as_bytes()))).unwrap();
+	std_fs::write(dir.path().join("hashes/2.3.4"),         &hash).unwrap();
+	std_fs::write(dir.path().join("hashes/2.3.4.sig"),     key.sign(hash.as_bytes()).to_string()).unwrap();
+	std_fs::write(dir.path().join("releases/2.3.4"),       b"Test payload").unwrap();
+	(dir, key)
+}
+
+//		file_url																
+/// This function gets the URL of a file in a directory.
+fn file_url(dir: &TempDir, name: &str) -> Url {
+	Url::from_file_path(std_fs::canonicalize(dir.path().join(name)).unwrap()).unwrap()
+}
+
+//		read_stream																
+/// This function reads the whole of a stream of bytes.
+async fn read_stream(mut stream: BoxStream<'static, Result<Bytes, String>>) -> Vec<u8> {
+	let mut data = vec![];
+	while let Some(chunk) = stream.next().await {
+		data.extend_from_slice(&chunk.unwrap());
+	}
+	data
+}
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod http_source {
+	use super::*;
+	
+	//		new																	
+	#[test]
+	fn new() {
+		let source = HttpSource::new(
+			"https://api.example.com/api/".parse().unwrap(),
+			vec!["https://mirror.example.com/api/".parse().unwrap()],
+			Duration::from_secs(60),
+		);
+		assert_eq!(source.api.as_str(),        "https://api.example.com/api/");
+		assert_eq!(source.mirrors.len(),       1);
+		assert_eq!(source.primary_retry,       Duration::from_secs(60));
+		assert_eq!(*source.mirror.lock(),      (0, None));
+		assert_none!(source.token);
+	}
+	
+	//		with_token															
+	#[test]
+	fn with_token() {
+		let source = setup_source("https://api.example.com/api/", &[], MockClient::new())
+			.with_token(TokenSource::Env(s!("API_TOKEN")))
+		;
+		assert_eq!(format!("{:?}", source.token), r#"Some(Env("API_TOKEN"))"#);
+	}
+	
+	//		latest																
+	#[tokio::test]
+	async fn latest() {
+		let url                         = "https://api.example.com/api/latest";
+		let json                        = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let (mock_response, public_key) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let payload = source.latest().await.unwrap();
+		assert_eq!(payload.url.as_str(), url);
+		assert_eq!(payload.body,         json);
+		assert!(public_key.verify_strict(json.as_bytes(), &Signature::from_slice(&hex::decode(payload.signature).unwrap()).unwrap()).is_ok());
+	}
+	
+	//		version_hash														
+	#[tokio::test]
+	async fn version_hash() {
+		let url                = "https://api.example.com/api/hashes/3.3.3";
+		let json               = json!({
+			"version": s!("3.3.3"),
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		}).to_string();
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Use(s!("signature")),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let payload = source.version_hash(&Version::new(3, 3, 3)).await.unwrap();
+		assert_eq!(payload, SignedPayload {
+			url:       url.parse().unwrap(),
+			body:      json,
+			signature: s!("signature"),
+		});
+	}
+	
+	//		release																
+	#[tokio::test]
+	async fn release() {
+		let url           = "https://api.example.com/api/releases/3.3.3";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(12),
+			Ok(b"Test payload"),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let release = source.release(&Version::new(3, 3, 3)).await.unwrap();
+		assert_eq!(release.url.as_str(), url);
+		assert_eq!(release.length,       Some(12));
+		assert_eq!(read_stream(release.stream).await, b"Test payload");
+	}
+	#[tokio::test]
+	async fn release__unknown_length() {
+		let url           = "https://api.example.com/api/releases/3.3.3";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			None,
+			Ok(b"Test payload"),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let release = source.release(&Version::new(3, 3, 3)).await.unwrap();
+		assert_none!(release.length);
+		assert_eq!(read_stream(release.stream).await, b"Test payload");
+	}
+	#[tokio::test]
+	async fn release__err_unexpected_content_type() {
+		let url           = "https://api.example.com/api/releases/3.3.3";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("text/plain"),
+			Some(12),
+			Ok(b"Test payload"),
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let err    = source.release(&Version::new(3, 3, 3)).await;
+		assert_err_eq!(err, UpdaterError::UnexpectedContentType(
+			url.parse().unwrap(),
+			s!("text/plain"),
+			s!("application/octet-stream"),
+		));
+	}
+	
+	//		events																
+	#[tokio::test]
+	async fn events() {
+		let url           = "https://api.example.com/api/events";
+		let mock_response = create_mock_binary_response(
+			url,
+			StatusCode::OK,
+			Some("text/event-stream"),
+			Some(3),
+			Ok(b":\n\n"),
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let events = source.events().await.unwrap().unwrap();
+		assert_eq!(events.url.as_str(), url);
+		assert_eq!(read_stream(events.stream).await, b":\n\n");
+	}
+	
+	//		get_signed															
+	#[tokio::test]
+	async fn get_signed__missing_signature() {
+		let url                = "https://api.example.com/api/latest";
+		let json               = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Omit,
+		);
+		let sig_url            = "https://api.example.com/api/latest.sig";
+		let mock_sig_response  = create_mock_binary_response(
+			sig_url,
+			StatusCode::NOT_FOUND,
+			Some("text/plain"),
+			Some(0),
+			Ok(b""),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url,     Ok(mock_response)),
+			(sig_url, Ok(mock_sig_response)),
+		]));
+		let payload = source.get_signed("latest").await.unwrap();
+		assert_eq!(payload.signature, "");
+	}
+	#[tokio::test]
+	async fn get_signed__detached_signature() {
+		let url                = "https://api.example.com/api/hashes/3.3.3";
+		let sig_url            = "https://api.example.com/api/hashes/3.3.3.sig";
+		let json               = json!({
+			"version": s!("3.3.3"),
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		}).to_string();
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Omit,
+		);
+		let mock_sig_response  = create_mock_binary_response(
+			sig_url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			Some(10),
+			Ok(b"signature\n"),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url,     Ok(mock_response)),
+			(sig_url, Ok(mock_sig_response)),
+		]));
+		let payload = source.get_signed("hashes/3.3.3").await.unwrap();
+		assert_eq!(payload, SignedPayload {
+			url:       url.parse().unwrap(),
+			body:      json,
+			signature: s!("signature"),
+		});
+	}
+	#[tokio::test]
+	async fn get_signed__unknown_length() {
+		let url                = "https://api.example.com/api/hashes/3.3.3";
+		let sig_url            = "https://api.example.com/api/hashes/3.3.3.sig";
+		let json               = json!({
+			"version": s!("3.3.3"),
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		}).to_string();
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			None,
+			Ok(&json),
+			&ResponseSignature::Omit,
+		);
+		let mock_sig_response  = create_mock_binary_response(
+			sig_url,
+			StatusCode::OK,
+			Some("application/octet-stream"),
+			None,
+			Ok(b"signature\n"),
+		);
+		let source  = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url,     Ok(mock_response)),
+			(sig_url, Ok(mock_sig_response)),
+		]));
+		let payload = source.get_signed("hashes/3.3.3").await.unwrap();
+		assert_eq!(payload.body,      json);
+		assert_eq!(payload.signature, "signature");
+	}
+	#[tokio::test]
+	async fn get_signed__err_detached_signature_unavailable() {
+		let url                = "https://api.example.com/api/latest";
+		let sig_url            = "https://api.example.com/api/latest.sig";
+		let json               = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Omit,
+		);
+		let mock_sig_response  = create_mock_binary_response(
+			sig_url,
+			StatusCode::SERVICE_UNAVAILABLE,
+			Some("text/plain"),
+			Some(0),
+			Ok(b""),
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url,     Ok(mock_response)),
+			(sig_url, Ok(mock_sig_response)),
+		]));
+		let err    = source.get_signed("latest").await;
+		assert_err_eq!(err, UpdaterError::HttpError(sig_url.parse().unwrap(), StatusCode::SERVICE_UNAVAILABLE));
+	}
+	#[tokio::test]
+	async fn get_signed__err_invalid_body() {
+		let url                = "https://api.example.com/api/latest";
+		let (mock_response, _) = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			None,
+			Err(MockError::default()),
+			&ResponseSignature::Use(s!("dummy signature")),
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let err    = source.get_signed("latest").await;
+		assert_err_eq!(err.clone(), UpdaterError::InvalidBody(url.parse().unwrap()));
+		assert_eq!(err.unwrap_err().to_string(), format!("Invalid HTTP body received from {url}"));
+	}
+	#[tokio::test]
+	async fn get_signed__err_unexpected_content_type() {
+		let url                   = "https://api.example.com/api/latest";
+		let content_type          = "text/plain";
+		let expected_content_type = s!("application/json");
+		let json                  = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let (mock_response, _)    = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some(content_type),
+			Some(json.len()),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let err    = source.get_signed("latest").await;
+		assert_err_eq!(err.clone(), UpdaterError::UnexpectedContentType(url.parse().unwrap(), content_type.to_owned(), expected_content_type.clone()));
+		assert_eq!(err.unwrap_err().to_string(), format!(r#"HTTP response from {url} had unexpected content type: "{content_type}", expected: "{expected_content_type}""#));
+	}
+	#[tokio::test]
+	async fn get_signed__err_missing_data() {
+		let url                  = "https://api.example.com/api/latest";
+		let json                 = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let content_len          = json.len();
+		let expected_content_len = json.len() + 1;
+		let (mock_response, _)   = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(expected_content_len),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let err    = source.get_signed("latest").await;
+		assert_err_eq!(err.clone(), UpdaterError::MissingData(url.parse().unwrap(), content_len, expected_content_len));
+		assert_eq!(err.unwrap_err().to_string(), format!("HTTP response body from {url} is shorter than expected: {content_len} < {expected_content_len}"));
+	}
+	#[tokio::test]
+	async fn get_signed__err_too_much_data() {
+		let url                  = "https://api.example.com/api/latest";
+		let json                 = json!({
+			"version": s!("3.3.3"),
+		}).to_string();
+		let content_len          = json.len();
+		let expected_content_len = json.len() - 1;
+		let (mock_response, _)   = create_mock_response(
+			url,
+			StatusCode::OK,
+			Some("application/json"),
+			Some(expected_content_len),
+			Ok(&json),
+			&ResponseSignature::Generate,
+		);
+		let source = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		]));
+		let err    = source.get_signed("latest").await;
+		assert_err_eq!(err.clone(), UpdaterError::TooMuchData(url.parse().unwrap(), content_len, expected_content_len));
+		assert_eq!(err.unwrap_err().to_string(), format!("HTTP response body from {url} is longer than expected: {content_len} > {expected_content_len}"));
+	}
+	
+	//		failover_request													
+	#[tokio::test]
+	async fn failover_request() {
+		let url1              = "https://api.example.com/api/latest";
+		let url2              = "https://mirror1.example.com/api/latest";
+		let url3              = "https://mirror2.example.com/api/latest";
+		let mock_failure      = create_mock_binary_response(url2, StatusCode::BAD_GATEWAY, Some("text/plain"), Some(0), Ok(b""));
+		let mock_response     = create_mock_binary_response(url3, StatusCode::OK,          Some("text/plain"), Some(0), Ok(b""));
+		let source            = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror1.example.com/api/", "https://mirror2.example.com/api/"],
+			create_mock_client(vec![
+				(url1, Err(MockError::default())),
+				(url2, Ok(mock_failure)),
+				(url3, Ok(mock_response)),
+			]),
+		);
+		let (url, _response)  = source.failover_request("latest").await.unwrap();
+		assert_eq!(url.as_str(), url3);
+		let (index, failed)   = *source.mirror.lock();
+		assert_eq!(index, 2);
+		assert_some!(failed);
+	}
+	#[tokio::test]
+	async fn failover_request__remembers_mirror() {
+		let url               = "https://mirror.example.com/api/latest";
+		let mock_response     = create_mock_binary_response(url, StatusCode::OK, Some("text/plain"), Some(0), Ok(b""));
+		let source            = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror.example.com/api/"],
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		let failed_at         = Instant::now();
+		*source.mirror.lock() = (1, Some(failed_at));
+		let (url2, _response) = source.failover_request("latest").await.unwrap();
+		assert_eq!(url2.as_str(), url);
+		assert_eq!(*source.mirror.lock(), (1, Some(failed_at)));
+	}
+	#[tokio::test]
+	async fn failover_request__retries_primary() {
+		let url               = "https://api.example.com/api/latest";
+		let mock_response     = create_mock_binary_response(url, StatusCode::OK, Some("text/plain"), Some(0), Ok(b""));
+		let mut source        = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror.example.com/api/"],
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		source.primary_retry  = Duration::ZERO;
+		*source.mirror.lock() = (1, Some(Instant::now()));
+		let (url2, _response) = source.failover_request("latest").await.unwrap();
+		assert_eq!(url2.as_str(), url);
+		assert_eq!(*source.mirror.lock(), (0, None));
+	}
+	#[tokio::test]
+	async fn failover_request__err_all_failed() {
+		let url1            = "https://api.example.com/api/latest";
+		let url2            = "https://mirror.example.com/api/latest";
+		let err_msg         = "Mocked Reqwest error";
+		let source          = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror.example.com/api/"],
+			create_mock_client(vec![
+				(url1, Err(MockError::default())),
+				(url2, Err(MockError::default())),
+			]),
+		);
+		let err             = source.failover_request("latest").await;
+		assert_err_eq!(err, UpdaterError::HttpRequestFailed(url2.parse().unwrap(), err_msg.to_owned()));
+		let (index, failed) = *source.mirror.lock();
+		assert_eq!(index, 0);
+		assert_some!(failed);
+	}
+	#[tokio::test]
+	async fn failover_request__err_not_failed_over() {
+		let url           = "https://api.example.com/api/latest";
+		let mock_response = create_mock_binary_response(url, StatusCode::NOT_FOUND, Some("text/plain"), Some(0), Ok(b""));
+		let source        = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror.example.com/api/"],
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		);
+		let err           = source.failover_request("latest").await;
+		assert_err_eq!(err, UpdaterError::HttpError(url.parse().unwrap(), StatusCode::NOT_FOUND));
+		assert_eq!(*source.mirror.lock(), (0, None));
+	}
+	
+	//		api_url																
+	#[test]
+	fn api_url() {
+		let source = setup_source("https://api.example.com/api/", &["https://mirror.example.com/api/"], MockClient::new());
+		assert_eq!(source.api_url(0).as_str(), "https://api.example.com/api/");
+		assert_eq!(source.api_url(1).as_str(), "https://mirror.example.com/api/");
+		assert_eq!(source.api_url(2).as_str(), "https://api.example.com/api/");
+	}
+	
+	//		send_request														
+	#[tokio::test]
+	async fn send_request__err_invalid_url() {
+		let base     = "https://api.example.com/api";
+		let endpoint = "https://[invalid]/../../../endpoint";
+		let source   = setup_source(base, &[], MockClient::new());
+		let err      = source.send_request(&base.parse().unwrap(), endpoint).await;
+		assert_err_eq!(err.clone(), UpdaterError::InvalidUrl(base.parse().unwrap(), endpoint.to_owned()));
+		assert_eq!(err.unwrap_err().to_string(), format!("Invalid URL specified: {base} plus {endpoint}"));
+	}
+	
+	//		get																	
+	#[tokio::test]
+	async fn get__err_unauthorized() {
+		let url           = "https://api.example.com/api/latest";
+		let mock_response = create_mock_binary_response(url, StatusCode::UNAUTHORIZED, Some("text/plain"), Some(0), Ok(b""));
+		let source        = setup_source(
+			"https://api.example.com/api/",
+			&["https://mirror.example.com/api/"],
+			create_mock_client(vec![
+				(url, Ok(mock_response)),
+			]),
+		).with_token(TokenSource::Static(s!("secret")));
+		let err           = source.failover_request("latest").await;
+		assert_err_eq!(err, UpdaterError::Unauthorized(url.parse().unwrap()));
+		assert_eq!(*source.mirror.lock(), (0, None));
+	}
+	#[tokio::test]
+	async fn get__err_forbidden() {
+		let url           = "https://api.example.com/api/releases/2.3.4";
+		let mock_response = create_mock_binary_response(url, StatusCode::FORBIDDEN, Some("text/plain"), Some(0), Ok(b""));
+		let source        = setup_source("https://api.example.com/api/", &[], create_mock_client(vec![
+			(url, Ok(mock_response)),
+		])).with_token(TokenSource::Static(s!("secret")));
+		let err           = source.release(&Version::new(2, 3, 4)).await;
+		assert_err_eq!(err.map(|release| release.url), UpdaterError::Forbidden(url.parse().unwrap()));
+	}
+	#[tokio::test]
+	async fn get__err_invalid_token() {
+		let source = setup_source("https://api.example.com/api/", &[], MockClient::new())
+			.with_token(TokenSource::Static(s!("secret\n")))
+		;
+		let err    = source.get(&"https://api.example.com/api/latest".parse().unwrap()).await;
+		assert_err_eq!(err.map(|response| response.status()), UpdaterError::InvalidToken(s!("The token contains invalid characters")));
+	}
+}
+
+#[cfg(test)]
+mod directory_source {
+	use super::*;
+	
+	//		latest																
+	#[tokio::test]
+	async fn latest() {
+		let (dir, key) = setup_directory();
+		let source     = DirectorySource::new(dir.path());
+		let payload    = source.latest().await.unwrap();
+		let latest     = json!({ "version": "2.3.4" }).to_string();
+		assert_eq!(payload, SignedPayload {
+			url:       file_url(&dir, "latest"),
+			signature: key.sign(latest.as_bytes()).to_string(),
+			body:      latest,
+		});
+	}
+	#[tokio::test]
+	async fn latest__missing_signature() {
+		let (dir, _key) = setup_directory();
+		std_fs::remove_file(dir.path().join("latest.sig")).unwrap();
+		let source      = DirectorySource::new(dir.path());
+		assert_eq!(source.latest().await.unwrap().signature, "");
+	}
+	#[tokio::test]
+	async fn latest__err_missing() {
+		let dir    = tempdir().unwrap();
+		let source = DirectorySource::new(dir.path());
+		let err    = source.latest().await;
+		let path   = dir.path().join("latest");
+		assert_err_eq!(err.clone(), UpdaterError::UnableToReadFile(path.clone(), s!("No such file or directory (os error 2)")));
+		assert_eq!(err.unwrap_err().to_string(), format!("Unable to read file {path:?}: No such file or directory (os error 2)"));
+	}
+	
+	//		version_hash														
+	#[tokio::test]
+	async fn version_hash() {
+		let (dir, key) = setup_directory();
+		let source     = DirectorySource::new(dir.path());
+		let payload    = source.version_hash(&Version::new(2, 3, 4)).await.unwrap();
+		assert_eq!(payload.url,       file_url(&dir, "hashes/2.3.4"));
+		assert_eq!(payload.signature, key.sign(payload.body.as_bytes()).to_string());
+	}
+	
+	//		release																
+	#[tokio::test]
+	async fn release() {
+		let (dir, _key) = setup_directory();
+		let source      = DirectorySource::new(dir.path());
+		let release     = source.release(&Version::new(2, 3, 4)).await.unwrap();
+		assert_eq!(release.url,    file_url(&dir, "releases/2.3.4"));
+		assert_eq!(release.length, Some(12));
+		assert_eq!(read_stream(release.stream).await, b"Test payload");
+	}
+	#[tokio::test]
+	async fn release__err_missing() {
+		let (dir, _key) = setup_directory();
+		let source      = DirectorySource::new(dir.path());
+		let err         = source.release(&Version::new(3, 0, 0)).await;
+		assert_err_eq!(err, UpdaterError::UnableToReadFile(
+			dir.path().join("releases/3.0.0"),
+			s!("No such file or directory (os error 2)"),
+		));
+	}
+	
+	//		events																
+	#[tokio::test]
+	async fn events() {
+		let (dir, _key) = setup_directory();
+		let source      = DirectorySource::new(dir.path());
+		assert_none!(source.events().await.unwrap());
+	}
+}
+
+#[cfg(test)]
+mod functions {
+	use std::collections::HashMap;
+	use super::*;
+	
+	//		get_header															
+	#[test]
+	fn get_header__string() {
+		let mock_response = create_sham_response(
+			"http://127.0.0.1",
+			StatusCode::OK,
+			Some("text/plain"),
+			Some("Test body".len()),
+			HashMap::<String, String>::new(),
+			Ok("Test body".as_ref()),
+		);
+		let content_type: String = get_header(&mock_response, CONTENT_TYPE);
+		assert_eq!(content_type, s!("text/plain"));
+	}
+	#[test]
+	fn get_header__integer() {
+		let mock_response = create_sham_response(
+			"http://127.0.0.1",
+			StatusCode::OK,
+			Some("text/plain"),
+			Some(1234),
+			HashMap::<String, String>::new(),
+			Ok("Test body".as_ref()),
+		);
+		let content_length: usize = get_header(&mock_response, CONTENT_LENGTH);
+		assert_eq!(content_length, 1234);
+	}
+	
+	//		get_content_length													
+	#[test]
+	fn get_content_length() {
+		let mock_response = create_sham_response(
+			"http://127.0.0.1",
+			StatusCode::OK,
+			Some("text/plain"),
+			Some(1234),
+			HashMap::<String, String>::new(),
+			Ok("Test body".as_ref()),
+		);
+		assert_eq!(super::get_content_length(&mock_response), Some(1234));
+	}
+	#[test]
+	fn get_content_length__missing() {
+		let mock_response = create_sham_response(
+			"http://127.0.0.1",
+			StatusCode::OK,
+			Some("text/plain"),
+			None,
+			HashMap::<String, String>::new(),
+			Ok("Test body".as_ref()),
+		);
+		assert_none!(super::get_content_length(&mock_response));
+	}
+	
+	//		is_under															
+	#[test]
+	fn is_under__same_path() {
+		let base = Url::parse("https://api.example.com/api/").unwrap();
+		assert!(is_under(&Url::parse("https://api.example.com/api/latest").unwrap(),             &base));
+		assert!(is_under(&Url::parse("https://api.example.com/api/releases/1.2.3").unwrap(),     &base));
+		assert!(is_under(&Url::parse("https://api.example.com:443/api/latest?x=1").unwrap(),     &base));
+	}
+	#[test]
+	fn is_under__no_trailing_slash() {
+		let base = Url::parse("https://api.example.com/api").unwrap();
+		assert!( is_under(&Url::parse("https://api.example.com/api/latest").unwrap(),            &base));
+		assert!(!is_under(&Url::parse("https://api.example.com/apis/latest").unwrap(),           &base));
+	}
+	#[test]
+	fn is_under__different_path() {
+		let base = Url::parse("https://api.example.com/api/").unwrap();
+		assert!(!is_under(&Url::parse("https://api.example.com/apis/latest").unwrap(),           &base));
+		assert!(!is_under(&Url::parse("https://api.example.com/other/api/latest").unwrap(),      &base));
+		assert!(!is_under(&Url::parse("https://api.example.com/").unwrap(),                      &base));
+	}
+	#[test]
+	fn is_under__different_origin() {
+		let base = Url::parse("https://api.example.com/api/").unwrap();
+		assert!(!is_under(&Url::parse("http://api.example.com/api/latest").unwrap(),             &base));
+		assert!(!is_under(&Url::parse("https://api.example.com:8443/api/latest").unwrap(),       &base));
+		assert!(!is_under(&Url::parse("https://api.example.com.evil.com/api/latest").unwrap(),   &base));
+		assert!(!is_under(&Url::parse("https://cdn.example.com/api/latest").unwrap(),            &base));
+	}
+}
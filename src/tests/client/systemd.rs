@@ -0,0 +1,49 @@
+//		Packages																										
+
+use super::*;
+use claims::{assert_err, assert_ok};
+use std::process::id as process_id;
+use tempfile::tempdir;
+
+
+
+//		Tests																											
+
+#[cfg(test)]
+mod functions {
+	use super::*;
+	
+	//		send_to																
+	#[test]
+	fn send_to__path() {
+		let temp_dir    = tempdir().unwrap();
+		let socket_path = temp_dir.path().join("notify.sock");
+		let receiver    = UnixDatagram::bind(&socket_path).unwrap();
+		assert_ok!(send_to(socket_path.as_os_str(), "STOPPING=1\nSTATUS=Stopping"));
+		let mut buffer  = [0_u8; 64];
+		let size        = receiver.recv(&mut buffer).unwrap();
+		assert_eq!(&buffer[..size], b"STOPPING=1\nSTATUS=Stopping");
+	}
+	#[cfg(target_os = "linux")]
+	#[test]
+	fn send_to__abstract() {
+		let name       = format!("patchify-test-{}", process_id());
+		let receiver   = UnixDatagram::bind_addr(&SocketAddr::from_abstract_name(name.as_bytes()).unwrap()).unwrap();
+		assert_ok!(send_to(OsStr::new(&format!("@{name}")), "RELOADING=1"));
+		let mut buffer = [0_u8; 64];
+		let size       = receiver.recv(&mut buffer).unwrap();
+		assert_eq!(&buffer[..size], b"RELOADING=1");
+	}
+	#[cfg(not(target_os = "linux"))]
+	#[test]
+	fn send_to__err_abstract() {
+		assert_err!(send_to(OsStr::new("@patchify-test"), "READY=1"));
+	}
+	#[test]
+	fn send_to__err_missing_socket() {
+		let temp_dir = tempdir().unwrap();
+		assert_err!(send_to(temp_dir.path().join("missing.sock").as_os_str(), "READY=1"));
+	}
+}
+
+
@@ -1,7 +0,0 @@
-//		Modules																											
-
-#[cfg(test)]
-#[path = "common/utils.rs"]
-pub mod utils;
-
-
@@ -8,12 +8,64 @@ pub mod reqwest;
 #[path = "mocks/std_env.rs"]
 pub mod std_env;
 
+#[cfg(test)]
+#[path = "mocks/std_process.rs"]
+pub mod std_process;
+
 
 
 //		Packages																										
 
-use crate::client::Status;
+use crate::client::{
+	Status,
+	consent::{Consent, ConsentDecision, ConsentStep},
+};
+use futures_util::future::BoxFuture;
 use mockall::automock;
+use parking_lot::Mutex;
+use semver::Version;
+use std::collections::VecDeque;
+
+
+
+//		Structs																											
+
+//		MockConsent																
+/// A consent implementation that gives pre-arranged decisions.
+/// 
+/// Each request is recorded, and answered with the next decision in the queue.
+/// Once the queue is empty, all further requests are approved.
+/// 
+#[derive(Debug, Default)]
+pub struct MockConsent {
+	//		Public properties													
+	/// The decisions to give, in order.
+	pub decisions: Mutex<VecDeque<ConsentDecision>>,
+	
+	/// The requests that have been received, in order.
+	pub requests:  Mutex<Vec<(ConsentStep, Version)>>,
+}
+
+//󰭅		MockConsent																
+impl MockConsent {
+	//		new																	
+	pub fn new(decisions: impl IntoIterator<Item = ConsentDecision>) -> Self {
+		Self {
+			decisions: Mutex::new(decisions.into_iter().collect()),
+			requests:  Mutex::new(vec![]),
+		}
+	}
+}
+
+//		Consent																	
+impl Consent for MockConsent {
+	//		request																
+	fn request<'a>(&'a self, step: ConsentStep, version: &'a Version) -> BoxFuture<'a, ConsentDecision> {
+		self.requests.lock().push((step, version.clone()));
+		let decision = self.decisions.lock().pop_front().unwrap_or(ConsentDecision::Approve);
+		Box::pin(async move { decision })
+	}
+}
 
 
 
@@ -19,7 +19,7 @@
 //		Packages																										
 
 use std::collections::HashMap;
-use crate::common::utils::*;
+use crate::keys::generate_keypair;
 use ed25519_dalek::Signer as _;
 use reqwest::{StatusCode, IntoUrl};
 use rubedo::{
@@ -58,7 +58,7 @@ pub fn create_mock_response<U: IntoUrl, S: Into<String>>(
 		ResponseSignature::GenerateUsing(ref key) => key.clone(),
 		ResponseSignature::Generate |
 		ResponseSignature::Omit     |
-		ResponseSignature::Use(_)   => generate_new_private_key(),
+		ResponseSignature::Use(_)   => generate_keypair().0,
 	};
 	let signature = match *sign {
 		ResponseSignature::GenerateUsing(_) |
@@ -0,0 +1,186 @@
+//! This module mocks `std::process` in order to test the `Updater` struct.
+//! 
+//! The `Updater` struct is responsible for restarting the application once it
+//! has been upgraded, which it does in a number of ways according to the
+//! configured restart strategy. This module provides fake versions of the parts
+//! of `std::process` that are used, which record the calls made to them instead
+//! of replacing, spawning, or exiting the process. This is important because
+//! unit tests run via the Cargo test runner, which should not be restarted.
+//! 
+//! The approach taken is the same as for the other mocks: the "real" code
+//! imports functionality from `std::process` when running in non-test mode, but
+//! imports the fakes when running in test mode. The calls are recorded per
+//! thread, so that tests running in parallel do not see each other's calls.
+//! 
+
+
+
+//		Packages																										
+
+use command_fds::{FdMapping, FdMappingCollision};
+use core::cell::RefCell;
+use sham::std_process::MockStdio;
+use std::{
+	ffi::OsStr,
+	io::{Error as IoError, Result as IoResult},
+	os::fd::RawFd,
+	path::PathBuf,
+};
+
+
+
+//		Enums																											
+
+//		ProcessCall																
+/// A call made to one of the fake process functions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ProcessCall {
+	/// The process was replaced with the specified program and arguments.
+	Exec(PathBuf, Vec<String>),
+	
+	/// The specified program was started with the specified arguments.
+	Spawn(PathBuf, Vec<String>),
+	
+	/// An environment variable was set, or removed if there is no value.
+	Env(String, Option<String>),
+	
+	/// File descriptors were mapped to the specified numbers in the child.
+	MapFds(Vec<RawFd>),
+	
+	/// The process exited with the specified code.
+	Exit(i32),
+}
+
+
+
+//		Structs																											
+
+//		FakeChild																
+/// A stand-in for a spawned child process.
+#[derive(Debug)]
+pub struct FakeChild;
+
+//󰭅		FakeChild																
+impl FakeChild {
+	//		id																	
+	#[expect(clippy::unused_self, reason = "Needed for mock")]
+	pub const fn id(&self) -> u32 {
+		0
+	}
+}
+
+//		FakeCommand																
+/// A stand-in for [`std::process::Command`], which records what is run.
+#[derive(Debug)]
+pub struct FakeCommand {
+	/// The program to run.
+	program: PathBuf,
+	
+	/// The arguments to pass to the program.
+	args:    Vec<String>,
+}
+
+//󰭅		FakeCommand																
+impl FakeCommand {
+	//		new																	
+	pub fn new<S: AsRef<OsStr>>(program: S) -> Self {
+		Self {
+			program: PathBuf::from(program.as_ref()),
+			args:    vec![],
+		}
+	}
+	
+	//		args																
+	pub fn args<I, S>(&mut self, args: I) -> &mut Self
+	where
+		I: IntoIterator<Item = S>,
+		S: AsRef<OsStr>,
+	{
+		self.args = args.into_iter().map(|arg| arg.as_ref().to_string_lossy().into_owned()).collect();
+		self
+	}
+	
+	//		env																	
+	pub fn env<K: AsRef<OsStr>, V: AsRef<OsStr>>(&mut self, key: K, value: V) -> &mut Self {
+		record(ProcessCall::Env(
+			key.as_ref().to_string_lossy().into_owned(),
+			Some(value.as_ref().to_string_lossy().into_owned()),
+		));
+		self
+	}
+	
+	//		env_remove															
+	pub fn env_remove<K: AsRef<OsStr>>(&mut self, key: K) -> &mut Self {
+		record(ProcessCall::Env(key.as_ref().to_string_lossy().into_owned(), None));
+		self
+	}
+	
+	//		exec																
+	#[expect(clippy::needless_pass_by_ref_mut, reason = "Needed for mock")]
+	pub fn exec(&mut self) -> IoError {
+		record(ProcessCall::Exec(self.program.clone(), self.args.clone()));
+		IoError::from_raw_os_error(0)
+	}
+	
+	//		fd_mappings															
+	#[expect(clippy::needless_pass_by_value, reason = "Needed for mock")]
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for mock")]
+	pub fn fd_mappings(&mut self, mappings: Vec<FdMapping>) -> Result<&mut Self, FdMappingCollision> {
+		record(ProcessCall::MapFds(mappings.iter().map(|mapping| mapping.child_fd).collect()));
+		Ok(self)
+	}
+	
+	//		spawn																
+	#[expect(clippy::needless_pass_by_ref_mut, reason = "Needed for mock")]
+	#[expect(clippy::unnecessary_wraps, reason = "Needed for mock")]
+	pub fn spawn(&mut self) -> IoResult<FakeChild> {
+		record(ProcessCall::Spawn(self.program.clone(), self.args.clone()));
+		Ok(FakeChild)
+	}
+	
+	//		stdin																
+	pub fn stdin(&mut self, _cfg: MockStdio) -> &mut Self {
+		self
+	}
+	
+	//		stdout																
+	pub fn stdout(&mut self, _cfg: MockStdio) -> &mut Self {
+		self
+	}
+	
+	//		stderr																
+	pub fn stderr(&mut self, _cfg: MockStdio) -> &mut Self {
+		self
+	}
+}
+
+
+
+//		Statics																											
+
+thread_local! {
+	/// The calls made to the fake process functions on the current thread.
+	static CALLS: RefCell<Vec<ProcessCall>> = const { RefCell::new(vec![]) };
+}
+
+
+
+//		Functions																										
+
+//		mock_exit																
+pub fn mock_exit(code: i32) {
+	record(ProcessCall::Exit(code));
+}
+
+//		record																	
+fn record(call: ProcessCall) {
+	CALLS.with_borrow_mut(|calls| calls.push(call));
+}
+
+//		take_calls																
+/// Takes the calls made to the fake process functions on the current thread.
+pub fn take_calls() -> Vec<ProcessCall> {
+	CALLS.take()
+}
+
+
@@ -1,9 +1,10 @@
 //		Packages																										
 
 use super::*;
-use crate::common::utils::*;
+use crate::keys::generate_keypair;
 use assert_json_diff::assert_json_eq;
-use claims::{assert_err_eq, assert_none};
+use ::axum::http::HeaderValue;
+use claims::{assert_err_eq, assert_none, assert_ok};
 use rubedo::{
 	http::{ResponseExt as _, UnpackedResponse},
 	sugar::s,
@@ -34,12 +35,25 @@ const VERSION_DATA: [(Version, usize, &[u8]); 5] = [
 
 //		Common																											
 
+//		add_release_file														
+fn add_release_file(releases_dir: &TempDir, version: &str, data: &[u8]) -> Sha256Hash {
+	fs::write(releases_dir.path().join(format!("test-{version}")), data).unwrap();
+	Sha256::digest(data).into()
+}
+
+//		release_event															
+fn release_event(key: &SigningKey, version: &str) -> Bytes {
+	let payload   = json!({ "version": version }).to_string();
+	let signature = key.sign(payload.as_bytes()).to_string();
+	Bytes::from(format!("event: release\ndata: {}\n\n", json!({ "payload": payload, "signature": signature })))
+}
+
 //		setup_core																
 fn setup_core(releases_dir: &TempDir) -> Result<Core, ReleaseError> {
 	#[expect(clippy::pattern_type_mismatch, reason = "Not resolvable")]
 	Core::new(Config {
 		appname:  s!("test"),
-		key:      generate_new_private_key(),
+		key:      generate_keypair().0,
 		releases: releases_dir.path().to_path_buf(),
 		versions: VERSION_DATA.iter()
 			.map(|(version, repetitions, data)| (version.clone(), Sha256::digest(data.repeat(*repetitions)).into()))
@@ -48,9 +62,46 @@ fn setup_core(releases_dir: &TempDir) -> Result<Core, ReleaseError> {
 		stream_threshold: 1000,
 		stream_buffer:    256,
 		read_buffer:      128,
+		tokens:           None,
+		signed_urls:      None,
+		release_redirect: None,
 	})
 }
 
+//		setup_auth_core															
+fn setup_auth_core(releases_dir: &TempDir) -> Core {
+	let mut core = setup_core(releases_dir).unwrap();
+	core.config.tokens = Some(hash_map!{
+		s!("full"):    Entitlement::default(),
+		s!("limited"): Entitlement { max_version: Some(Version::new(1, 0, 0)), ..Entitlement::default() },
+		s!("other"):   Entitlement { apps: Some(HashSet::from([s!("other")])), ..Entitlement::default() },
+		s!("stable"):  Entitlement { channels: Some(HashSet::from([s!("stable")])), ..Entitlement::default() },
+	});
+	core
+}
+
+//		setup_signed_core														
+fn setup_signed_core(releases_dir: &TempDir, signing: UrlSigning, verify: bool) -> Core {
+	let mut core = setup_core(releases_dir).unwrap();
+	core.config.signed_urls = Some(SignedUrls {
+		base:     Url::parse("https://cdn.example.com/releases/").unwrap(),
+		lifetime: Duration::from_secs(60),
+		signing,
+		verify,
+	});
+	core
+}
+
+//		url_query																
+fn url_query(url: &Url) -> HashMap<String, String> {
+	url.query_pairs().into_owned().collect()
+}
+
+//		bearer																	
+fn bearer(token: &str) -> HeaderMap {
+	HeaderMap::from_iter([(AUTHORIZATION, HeaderValue::from_str(&format!("Bearer {token}")).unwrap())])
+}
+
 //		setup_files																
 fn setup_files() -> TempDir {
 	let releases_dir = tempdir().unwrap();
@@ -66,6 +117,48 @@ fn setup_files() -> TempDir {
 
 //		Tests																											
 
+//		Entitlement																
+#[cfg(test)]
+mod entitlement {
+	use super::*;
+	
+	//		allows_app															
+	#[test]
+	fn allows_app() {
+		let entitlement = Entitlement { apps: Some(HashSet::from([s!("test")])), ..Entitlement::default() };
+		assert!( entitlement.allows_app("test"));
+		assert!(!entitlement.allows_app("other"));
+		assert!( Entitlement::default().allows_app("other"));
+	}
+	
+	//		allows_version														
+	#[test]
+	fn allows_version__max_version() {
+		let entitlement = Entitlement { max_version: Some(Version::new(1, 0, 0)), ..Entitlement::default() };
+		assert!( entitlement.allows_version(&Version::new(0, 9, 0)));
+		assert!( entitlement.allows_version(&Version::new(1, 0, 0)));
+		assert!( entitlement.allows_version(&Version::parse("1.0.0-beta.1").unwrap()));
+		assert!(!entitlement.allows_version(&Version::new(1, 0, 1)));
+	}
+	#[test]
+	fn allows_version__channels() {
+		let entitlement = Entitlement { channels: Some(HashSet::from([s!("stable"), s!("beta")])), ..Entitlement::default() };
+		assert!( entitlement.allows_version(&Version::new(2, 0, 0)));
+		assert!( entitlement.allows_version(&Version::parse("2.0.0-beta.2").unwrap()));
+		assert!(!entitlement.allows_version(&Version::parse("2.0.0-alpha").unwrap()));
+		assert!( Entitlement::default().allows_version(&Version::parse("2.0.0-alpha").unwrap()));
+	}
+	
+	//		channel																
+	#[test]
+	fn channel() {
+		assert_eq!(Entitlement::channel(&Version::new(1, 2, 3)),                    "stable");
+		assert_eq!(Entitlement::channel(&Version::parse("1.2.3-beta").unwrap()),     "beta");
+		assert_eq!(Entitlement::channel(&Version::parse("1.2.3-rc.1").unwrap()),     "rc");
+		assert_eq!(Entitlement::channel(&Version::parse("1.2.3+build.5").unwrap()),  "stable");
+	}
+}
+
 //		Core																	
 #[cfg(test)]
 mod core {
@@ -76,7 +169,7 @@ mod core {
 	fn new() {
 		let core = setup_core(&setup_files()).unwrap();
 		assert_eq!(core.config.appname, "test");
-		assert_eq!(core.latest,         Version::new(1, 1, 0));
+		assert_eq!(*core.latest.borrow(), Version::new(1, 1, 0));
 	}
 	#[test]
 	fn new__err_missing() {
@@ -88,6 +181,14 @@ mod core {
 		assert_eq!(err.unwrap_err().to_string(), format!("The release file for version 1.0.0 is missing: {path:?}"));
 	}
 	#[test]
+	fn new__redirect_without_files() {
+		let releases_dir = tempdir().unwrap();
+		let mut core     = setup_core(&setup_files()).unwrap();
+		core.config.releases         = releases_dir.path().to_path_buf();
+		core.config.release_redirect = Some(Url::parse("https://cdn.example.com/releases/").unwrap());
+		assert_ok!(Core::new(core.config));
+	}
+	#[test]
 	fn new__err_invalid() {
 		let dir      = setup_files();
 		let path     = dir.path().join("test-1.0.0");
@@ -98,27 +199,224 @@ mod core {
 		assert_eq!(err.unwrap_err().to_string(), format!("The release file for version 1.0.0 failed hash verification: {path:?}"));
 	}
 	
-	//		latest_version														
+	//		authenticate														
+	#[test]
+	fn authenticate() {
+		let core = setup_auth_core(&setup_files());
+		assert_eq!(core.authenticate(Some("full")).unwrap(),    Entitlement::default());
+		assert_eq!(core.authenticate(Some("limited")).unwrap(), Entitlement { max_version: Some(Version::new(1, 0, 0)), ..Entitlement::default() });
+	}
+	#[test]
+	fn authenticate__not_required() {
+		let core = setup_core(&setup_files()).unwrap();
+		assert_eq!(core.authenticate(None).unwrap(),            Entitlement::default());
+		assert_eq!(core.authenticate(Some("unknown")).unwrap(), Entitlement::default());
+	}
+	#[test]
+	fn authenticate__err_unauthorized() {
+		let core = setup_auth_core(&setup_files());
+		assert_err_eq!(core.authenticate(None),            AuthError::Unauthorized);
+		assert_err_eq!(core.authenticate(Some("unknown")), AuthError::Unauthorized);
+	}
+	#[test]
+	fn authenticate__err_forbidden() {
+		let core = setup_auth_core(&setup_files());
+		assert_err_eq!(core.authenticate(Some("other")), AuthError::Forbidden);
+	}
+	
+	//		add_version															
+	#[test]
+	fn add_version() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let hash = add_release_file(&dir, "2.0.0", b"new");
+		core.add_version(Version::new(2, 0, 0), hash).unwrap();
+		assert_eq!(core.latest_version(&Entitlement::default()),                      Version::new(2, 0, 0));
+		assert_eq!(core.versions().get(&Version::new(2, 0, 0)), Some(&hash));
+	}
+	#[test]
+	fn add_version__older() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let hash = add_release_file(&dir, "0.5.0", b"old");
+		core.add_version(Version::new(0, 5, 0), hash).unwrap();
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(1, 1, 0));
+		assert_eq!(core.versions().len(), 6);
+	}
+	#[test]
+	fn add_version__shared() {
+		let dir   = setup_files();
+		let core  = setup_core(&dir).unwrap();
+		let clone = core.clone();
+		let hash  = add_release_file(&dir, "2.0.0", b"new");
+		core.add_version(Version::new(2, 0, 0), hash).unwrap();
+		assert_eq!(clone.latest_version(&Entitlement::default()), Version::new(2, 0, 0));
+	}
+	#[test]
+	fn add_version__err_missing() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let path = dir.path().join("test-2.0.0");
+		let err  = core.add_version(Version::new(2, 0, 0), Sha256::digest(b"new").into());
+		assert_err_eq!(err, ReleaseError::Missing(Version::new(2, 0, 0), path));
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(1, 1, 0));
+	}
+	#[test]
+	fn add_version__err_invalid() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let _ignored = add_release_file(&dir, "2.0.0", b"new");
+		let err  = core.add_version(Version::new(2, 0, 0), Sha256::digest(b"other").into());
+		assert_err_eq!(err, ReleaseError::Invalid(Version::new(2, 0, 0), dir.path().join("test-2.0.0")));
+		assert_none!(core.versions().get(&Version::new(2, 0, 0)));
+	}
+	#[test]
+	fn add_version__below_latest_for_channel() {
+		let dir         = setup_files();
+		let core        = setup_core(&dir).unwrap();
+		let stable      = Entitlement { channels: Some(HashSet::from([s!("stable")])), ..Entitlement::default() };
+		let beta_hash   = add_release_file(&dir, "2.0.0-beta.1", b"beta");
+		core.add_version(Version::parse("2.0.0-beta.1").unwrap(), beta_hash).unwrap();
+		let mut changes = core.changes.subscribe();
+		let latest      = core.subscribe();
+		let new_hash    = add_release_file(&dir, "1.2.1", b"new");
+		core.add_version(Version::new(1, 2, 1), new_hash).unwrap();
+		assert!(!latest.has_changed().unwrap());
+		assert!( changes.has_changed().unwrap());
+		assert_eq!(core.latest_version(&stable), Version::new(1, 2, 1));
+		changes.mark_unchanged();
+		let _removed    = core.remove_version(&Version::new(1, 2, 1));
+		assert!( changes.has_changed().unwrap());
+	}
+	
+	//		remove_version														
+	#[test]
+	fn remove_version() {
+		let core = setup_core(&setup_files()).unwrap();
+		assert_eq!(core.remove_version(&Version::new(1, 1, 0)).unwrap().to_hex(), "71b9dacf6c68a207b01c2b05f6362e62c267cc86123a596821366f6753bf10fa");
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(1, 0, 0));
+		assert_eq!(core.versions().len(), 4);
+	}
+	#[test]
+	fn remove_version__not_found() {
+		let core = setup_core(&setup_files()).unwrap();
+		assert_none!(core.remove_version(&Version::new(8, 7, 6)));
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(1, 1, 0));
+	}
+	
+	//		create_bundle														
+	#[test]
+	fn create_bundle() {
+		let dir    = setup_files();
+		let core   = setup_core(&dir).unwrap();
+		let path   = dir.path().join("test.bundle");
+		core.create_bundle(&Version::new(1, 0, 0), &path).unwrap();
+		let bundle = fs::read(&path).unwrap();
+		let header = format!("PATCHIFY-BUNDLE 1\n{}\n", json!({
+			"version": "1.0.0",
+			"hash":    hex::encode(Sha256::digest(b"foo")),
+		}));
+		assert!(bundle.starts_with(header.as_bytes()));
+		assert!(bundle.ends_with(b"\nfoo"));
+	}
+	#[test]
+	fn create_bundle__err_unknown_version() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let path = dir.path().join("test.bundle");
+		let err  = core.create_bundle(&Version::new(3, 0, 0), &path);
+		assert_err_eq!(err.clone(), BundleError::UnknownVersion(Version::new(3, 0, 0)));
+		assert_eq!(err.unwrap_err().to_string(), "Version 3.0.0 not found");
+		assert!(!path.exists());
+	}
+	
+	//		export																
+	#[tokio::test]
+	async fn export() {
+		let dir      = setup_files();
+		let core     = Arc::new(setup_core(&dir).unwrap());
+		let export   = tempdir().unwrap();
+		core.export(&export.path().join("static")).unwrap();
+		let root     = export.path().join("static");
+		let latest   = Axum::get_latest_version(Extension(Arc::clone(&core)), HeaderMap::new()).await.into_response().unpack().unwrap();
+		assert_eq!(fs::read(root.join("latest")).unwrap(),                latest.body.as_bytes());
+		assert_eq!(fs::read_to_string(root.join("latest.sig")).unwrap(), latest.headers.iter().find(|header| header.name == "x-signature").unwrap().value);
+		for (version, repetitions, data) in VERSION_DATA {
+			let hash = Axum::get_hash_for_version(
+				Extension(Arc::clone(&core)),
+				HeaderMap::new(),
+				Path(version.clone()),
+			).await.into_response().unpack().unwrap();
+			assert_eq!(fs::read(root.join(format!("hashes/{version}"))).unwrap(),                hash.body.as_bytes());
+			assert_eq!(fs::read_to_string(root.join(format!("hashes/{version}.sig"))).unwrap(), hash.headers.iter().find(|header| header.name == "x-signature").unwrap().value);
+			assert_eq!(fs::read(root.join(format!("releases/{version}"))).unwrap(),              data.repeat(repetitions));
+		}
+	}
+	#[test]
+	fn export__err_missing_release() {
+		let dir     = setup_files();
+		let core    = setup_core(&dir).unwrap();
+		let export  = tempdir().unwrap();
+		let release = dir.path().join("test-1.0.0");
+		fs::remove_file(&release).unwrap();
+		let err     = core.export(export.path());
+		assert_err_eq!(err.clone(), ExportError::UnableToCopyRelease(release.clone(), s!("No such file or directory (os error 2)")));
+		assert_eq!(err.unwrap_err().to_string(), format!("Unable to copy release file {release:?}: No such file or directory (os error 2)"));
+		assert!(!export.path().join("latest").exists());
+	}
+	
+	//		latest_version														
 	#[test]
 	fn latest_version() {
 		let core = setup_core(&setup_files()).unwrap();
-		assert_eq!(core.latest_version(), Version::new(1, 1, 0));
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(1, 1, 0));
+	}
+	#[test]
+	fn latest_version__entitlement() {
+		let dir  = setup_files();
+		let core = setup_core(&dir).unwrap();
+		let hash = add_release_file(&dir, "2.0.0-beta.1", b"beta");
+		core.add_version(Version::parse("2.0.0-beta.1").unwrap(), hash).unwrap();
+		let stable = Entitlement { channels: Some(HashSet::from([s!("stable")])), ..Entitlement::default() };
+		assert_eq!(core.latest_version(&Entitlement::default()),                                                         Version::parse("2.0.0-beta.1").unwrap());
+		assert_eq!(core.latest_version(&stable),                                                                         Version::new(1, 1, 0));
+		assert_eq!(core.latest_version(&Entitlement { max_version: Some(Version::new(1, 0, 5)), ..Entitlement::default() }), Version::new(1, 0, 0));
+		assert_eq!(core.latest_version(&Entitlement { max_version: Some(Version::new(0, 0, 0)), ..Entitlement::default() }), Version::new(0, 0, 0));
 	}
 	#[test]
 	fn latest_version__empty() {
 		let core = Core::new(Config {
 			appname:  s!("test"),
-			key:      generate_new_private_key(),
+			key:      generate_keypair().0,
 			releases: tempdir().unwrap().path().to_path_buf(),
 			versions: hash_map!{},
 			stream_threshold: 1000,
 			stream_buffer:    256,
 			read_buffer:      128,
+			tokens:           None,
+			signed_urls:      None,
+			release_redirect: None,
 		}).unwrap();
-		assert_eq!(core.latest_version(), Version::new(0, 0, 0));
+		assert_eq!(core.latest_version(&Entitlement::default()), Version::new(0, 0, 0));
 	}
 	
-	//		versions															
+	//		subscribe															
+	#[test]
+	fn subscribe() {
+		let dir         = setup_files();
+		let core        = setup_core(&dir).unwrap();
+		let mut watcher = core.subscribe();
+		assert_eq!(*watcher.borrow_and_update(), Version::new(1, 1, 0));
+		let old_hash    = add_release_file(&dir, "0.5.0", b"old");
+		core.add_version(Version::new(0, 5, 0), old_hash).unwrap();
+		assert!(!watcher.has_changed().unwrap());
+		let new_hash    = add_release_file(&dir, "2.0.0", b"new");
+		core.add_version(Version::new(2, 0, 0), new_hash).unwrap();
+		assert!( watcher.has_changed().unwrap());
+		assert_eq!(*watcher.borrow_and_update(), Version::new(2, 0, 0));
+	}
+	
+	//		versions															
 	#[test]
 	fn versions() {
 		let core = setup_core(&setup_files()).unwrap();
@@ -137,12 +435,15 @@ mod core {
 	fn versions__empty() {
 		let core = Core::new(Config {
 			appname:  s!("test"),
-			key:      generate_new_private_key(),
+			key:      generate_keypair().0,
 			releases: tempdir().unwrap().path().to_path_buf(),
 			versions: hash_map!{},
 			stream_threshold: 1000,
 			stream_buffer:    256,
 			read_buffer:      128,
+			tokens:           None,
+			signed_urls:      None,
+			release_redirect: None,
 		}).unwrap();
 		assert_eq!(core.versions(), hash_map!{});
 	}
@@ -158,6 +459,80 @@ mod core {
 		let core = setup_core(&setup_files()).unwrap();
 		assert_none!(core.release_file(&Version::new(8, 7, 6)));
 	}
+	
+	//		release_location													
+	#[test]
+	fn release_location() {
+		let mut core = setup_core(&setup_files()).unwrap();
+		assert_none!(core.release_location(&Version::new(1, 1, 0)));
+		core.config.release_redirect = Some(Url::parse("https://cdn.example.com/releases/").unwrap());
+		assert_eq!(core.release_location(&Version::new(1, 1, 0)).unwrap().as_str(), "https://cdn.example.com/releases/test-1.1.0");
+		assert_none!(core.release_location(&Version::new(8, 7, 6)));
+	}
+	
+	//		download_url														
+	#[test]
+	fn download_url() {
+		let core    = setup_signed_core(&setup_files(), UrlSigning::Hmac(b"secret".to_vec()), false);
+		let url     = core.download_url(&Version::new(1, 0, 0)).unwrap();
+		let query   = url_query(&url);
+		let expires = query["expires"].parse::<u64>().unwrap();
+		let now     = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs();
+		assert!(url.as_str().starts_with("https://cdn.example.com/releases/1.0.0?expires="));
+		assert!(expires >= now + 59 && expires <= now + 60);
+		assert_eq!(query["signature"].len(), 64);
+		assert_ok!(core.verify_download(&Version::new(1, 0, 0), expires, &query["signature"]));
+	}
+	#[test]
+	fn download_url__ed25519() {
+		let core  = setup_signed_core(&setup_files(), UrlSigning::Ed25519, false);
+		let url   = core.download_url(&Version::new(1, 0, 0)).unwrap();
+		let query = url_query(&url);
+		let bytes = hex::decode(&query["signature"]).unwrap();
+		let sig   = Signature::from_bytes(&bytes.try_into().unwrap());
+		let data  = format!("test/1.0.0/{}", query["expires"]);
+		assert_ok!(core.config.key.verifying_key().verify_strict(data.as_bytes(), &sig));
+		assert_ok!(core.verify_download(&Version::new(1, 0, 0), query["expires"].parse().unwrap(), &query["signature"]));
+	}
+	#[test]
+	fn download_url__not_configured() {
+		let core = setup_core(&setup_files()).unwrap();
+		assert_none!(core.download_url(&Version::new(1, 0, 0)));
+	}
+	
+	//		verify_download														
+	#[test]
+	fn verify_download__err_expired() {
+		let core      = setup_signed_core(&setup_files(), UrlSigning::Hmac(b"secret".to_vec()), false);
+		let settings  = core.config.signed_urls.clone().unwrap();
+		let signature = core.sign_download(&settings, &Version::new(1, 0, 0), 1_000);
+		assert_err_eq!(core.verify_download(&Version::new(1, 0, 0), 1_000, &signature), AuthError::ExpiredUrl);
+	}
+	#[test]
+	fn verify_download__err_tampered() {
+		let core    = setup_signed_core(&setup_files(), UrlSigning::Hmac(b"secret".to_vec()), false);
+		let query   = url_query(&core.download_url(&Version::new(1, 0, 0)).unwrap());
+		let expires = query["expires"].parse::<u64>().unwrap();
+		assert_err_eq!(core.verify_download(&Version::new(1, 1, 0), expires,     &query["signature"]), AuthError::InvalidUrlSignature);
+		assert_err_eq!(core.verify_download(&Version::new(1, 0, 0), expires + 1, &query["signature"]), AuthError::InvalidUrlSignature);
+		assert_err_eq!(core.verify_download(&Version::new(1, 0, 0), expires,     "not-hex"),           AuthError::InvalidUrlSignature);
+		let other   = setup_signed_core(&setup_files(), UrlSigning::Hmac(b"other".to_vec()), false);
+		assert_err_eq!(other.verify_download(&Version::new(1, 0, 0), expires,   &query["signature"]), AuthError::InvalidUrlSignature);
+	}
+	#[test]
+	fn verify_download__err_tampered_ed25519() {
+		let core    = setup_signed_core(&setup_files(), UrlSigning::Ed25519, false);
+		let query   = url_query(&core.download_url(&Version::new(1, 0, 0)).unwrap());
+		let expires = query["expires"].parse::<u64>().unwrap();
+		let short   = query["signature"].get(..64).unwrap();
+		assert_err_eq!(core.verify_download(&Version::new(1, 1, 0), expires, &query["signature"]), AuthError::InvalidUrlSignature);
+		assert_err_eq!(core.verify_download(&Version::new(1, 0, 0), expires, short),               AuthError::InvalidUrlSignature);
+	}
+	#[test]
+	fn verify_download__err_not_configured() {
+		let core = setup_core(&setup_files()).unwrap();
+		assert_err_eq!(core.verify_download(&Version::new(1, 0, 0), u64::MAX, "abcd"), AuthError::InvalidUrlSignature);
+	}
 }
 
 //		Axum																	
@@ -169,7 +544,7 @@ mod axum {
 	#[tokio::test]
 	async fn get_latest_version() {
 		let core     = Arc::new(setup_core(&setup_files()).unwrap());
-		let unpacked = Axum::get_latest_version(Extension(Arc::clone(&core))).await.into_response().unpack().unwrap();
+		let unpacked = Axum::get_latest_version(Extension(Arc::clone(&core)), HeaderMap::new()).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::OK,
 			vec![
@@ -183,13 +558,102 @@ mod axum {
 		);
 		assert_json_eq!(unpacked, crafted);
 	}
+	#[tokio::test]
+	async fn get_latest_version__entitled() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let unpacked = Axum::get_latest_version(Extension(Arc::clone(&core)), bearer("limited")).await.into_response().unpack().unwrap();
+		assert_eq!(unpacked.status, StatusCode::OK);
+		assert_json_eq!(serde_json::from_slice::<serde_json::Value>(unpacked.body.as_bytes()).unwrap(), json!({
+			"version": s!("1.0.0"),
+		}));
+	}
+	#[tokio::test]
+	async fn get_latest_version__unauthorized() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let unpacked = Axum::get_latest_version(Extension(Arc::clone(&core)), HeaderMap::new()).await.into_response().unpack().unwrap();
+		let crafted  = UnpackedResponse::new(
+			StatusCode::UNAUTHORIZED,
+			vec![
+				//	Axum automatically adds a content-type header.
+				(s!("content-type"),     s!("text/plain; charset=utf-8")),
+				(s!("www-authenticate"), s!("Bearer")),
+			],
+			"A valid API token is required",
+		);
+		assert_json_eq!(unpacked, crafted);
+	}
+	#[tokio::test]
+	async fn get_latest_version__forbidden() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let unpacked = Axum::get_latest_version(Extension(Arc::clone(&core)), bearer("other")).await.into_response().unpack().unwrap();
+		let crafted  = UnpackedResponse::new(
+			StatusCode::FORBIDDEN,
+			vec![
+				//	Axum automatically adds a content-type header.
+				(s!("content-type"), s!("text/plain; charset=utf-8")),
+			],
+			"The API token is not entitled to this release",
+		);
+		assert_json_eq!(unpacked, crafted);
+	}
 	
-	//		get_hash_for_version												
+	//		get_events															
+	#[tokio::test]
+	async fn get_events() {
+		let dir      = setup_files();
+		let core     = Arc::new(setup_core(&dir).unwrap());
+		let response = Axum::get_events(Extension(Arc::clone(&core)), HeaderMap::new()).await.into_response();
+		assert_eq!(response.status(),              StatusCode::OK);
+		assert_eq!(response.headers()[CONTENT_TYPE], "text/event-stream");
+		let mut body = response.into_body().into_data_stream();
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "1.1.0"));
+		let hash     = add_release_file(&dir, "2.0.0", b"new");
+		core.add_version(Version::new(2, 0, 0), hash).unwrap();
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "2.0.0"));
+		let _removed = core.remove_version(&Version::new(2, 0, 0));
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "1.1.0"));
+	}
+	#[tokio::test]
+	async fn get_events__entitled() {
+		let dir      = setup_files();
+		let core     = Arc::new(setup_auth_core(&dir));
+		let response = Axum::get_events(Extension(Arc::clone(&core)), bearer("limited")).await.into_response();
+		assert_eq!(response.status(), StatusCode::OK);
+		let mut body = response.into_body().into_data_stream();
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "1.0.0"));
+		let hash     = add_release_file(&dir, "2.0.0", b"new");
+		core.add_version(Version::new(2, 0, 0), hash).unwrap();
+		let _removed = core.remove_version(&Version::new(1, 0, 0));
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "0.2.0"));
+	}
+	#[tokio::test]
+	async fn get_events__channel() {
+		let dir       = setup_files();
+		let core      = Arc::new(setup_auth_core(&dir));
+		let beta_hash = add_release_file(&dir, "2.0.0-beta.1", b"beta");
+		core.add_version(Version::parse("2.0.0-beta.1").unwrap(), beta_hash).unwrap();
+		let response  = Axum::get_events(Extension(Arc::clone(&core)), bearer("stable")).await.into_response();
+		assert_eq!(response.status(), StatusCode::OK);
+		let mut body  = response.into_body().into_data_stream();
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "1.1.0"));
+		let new_hash  = add_release_file(&dir, "1.2.1", b"new");
+		core.add_version(Version::new(1, 2, 1), new_hash).unwrap();
+		assert_eq!(body.next().await.unwrap().unwrap(), release_event(&core.config.key, "1.2.1"));
+	}
+	#[tokio::test]
+	async fn get_events__unauthorized() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let response = Axum::get_events(Extension(Arc::clone(&core)), bearer("unknown")).await.into_response();
+		assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+	}
+	
+	//		get_hash_for_version												
 	#[tokio::test]
 	async fn get_hash_for_version() {
 		let core     = Arc::new(setup_core(&setup_files()).unwrap());
 		let unpacked = Axum::get_hash_for_version(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(0, 2, 0)),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
@@ -211,6 +675,7 @@ mod axum {
 		let core     = Arc::new(setup_core(&setup_files()).unwrap());
 		let unpacked = Axum::get_hash_for_version(
 			Extension(core),
+			HeaderMap::new(),
 			Path(Version::new(3, 2, 1)),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
@@ -223,15 +688,48 @@ mod axum {
 		);
 		assert_json_eq!(unpacked, crafted);
 	}
+	#[tokio::test]
+	async fn get_hash_for_version__forbidden() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let allowed  = Axum::get_hash_for_version(
+			Extension(Arc::clone(&core)),
+			bearer("limited"),
+			Path(Version::new(1, 0, 0)),
+		).await.into_response();
+		let denied   = Axum::get_hash_for_version(
+			Extension(Arc::clone(&core)),
+			bearer("limited"),
+			Path(Version::new(1, 1, 0)),
+		).await.into_response();
+		assert_eq!(allowed.status(), StatusCode::OK);
+		assert_eq!(denied.status(),  StatusCode::FORBIDDEN);
+	}
+	#[tokio::test]
+	async fn get_hash_for_version__signed_url() {
+		let core     = Arc::new(setup_signed_core(&setup_files(), UrlSigning::Hmac(b"secret".to_vec()), false));
+		let unpacked = Axum::get_hash_for_version(
+			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
+			Path(Version::new(0, 2, 0)),
+		).await.into_response().unpack().unwrap();
+		let json     = serde_json::from_str::<VersionHashResponse>(&unpacked.body.to_string()).unwrap();
+		let url      = json.url.unwrap();
+		let query    = url_query(&url);
+		assert_eq!(unpacked.status, StatusCode::OK);
+		assert!(url.as_str().starts_with("https://cdn.example.com/releases/0.2.0?"));
+		assert_ok!(core.verify_download(&Version::new(0, 2, 0), query["expires"].parse().unwrap(), &query["signature"]));
+	}
 	
-	//		get_release_file													
+	//		get_release_file													
 	#[tokio::test]
 	async fn get_release_file() {
 		let dir      = setup_files();
 		let core     = Arc::new(setup_core(&dir).unwrap());
 		let unpacked = Axum::get_release_file(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(0, 0, 1)),
+			Query(HashMap::new()),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::OK,
@@ -249,7 +747,9 @@ mod axum {
 		let core     = Arc::new(setup_core(&dir).unwrap());
 		let unpacked = Axum::get_release_file(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(1, 1, 0)),
+			Query(HashMap::new()),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::OK,
@@ -267,7 +767,9 @@ mod axum {
 		let core     = Arc::new(setup_core(&dir).unwrap());
 		let unpacked = Axum::get_release_file(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(0, 2, 0)),
+			Query(HashMap::new()),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::OK,
@@ -285,7 +787,9 @@ mod axum {
 		let core     = Arc::new(setup_core(&dir).unwrap());
 		let unpacked = Axum::get_release_file(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(7, 8, 9)),
+			Query(HashMap::new()),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::NOT_FOUND,
@@ -298,13 +802,100 @@ mod axum {
 		assert_json_eq!(unpacked, crafted);
 	}
 	#[tokio::test]
+	async fn get_release_file__forbidden() {
+		let core     = Arc::new(setup_auth_core(&setup_files()));
+		let denied   = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			bearer("limited"),
+			Path(Version::new(1, 1, 0)),
+			Query(HashMap::new()),
+		).await.into_response();
+		let missing  = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
+			Path(Version::new(1, 0, 0)),
+			Query(HashMap::new()),
+		).await.into_response();
+		assert_eq!(denied.status(),  StatusCode::FORBIDDEN);
+		assert_eq!(missing.status(), StatusCode::UNAUTHORIZED);
+	}
+	#[tokio::test]
+	async fn get_release_file__signed_url() {
+		let dir      = setup_files();
+		let core     = Arc::new(setup_signed_core(&dir, UrlSigning::Ed25519, true));
+		let url      = core.download_url(&Version::new(0, 0, 1)).unwrap();
+		let unpacked = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
+			Path(Version::new(0, 0, 1)),
+			Query(url_query(&url)),
+		).await.into_response().unpack().unwrap();
+		assert_eq!(unpacked.status, StatusCode::OK);
+		assert_eq!(unpacked.body.to_string(), "foobarbaz");
+	}
+	#[tokio::test]
+	async fn get_release_file__signed_url_invalid() {
+		let core     = Arc::new(setup_signed_core(&setup_files(), UrlSigning::Ed25519, true));
+		let url      = core.download_url(&Version::new(0, 0, 1)).unwrap();
+		let other    = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
+			Path(Version::new(1, 0, 0)),
+			Query(url_query(&url)),
+		).await.into_response();
+		let unsigned = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
+			Path(Version::new(0, 0, 1)),
+			Query(HashMap::new()),
+		).await.into_response();
+		assert_eq!(other.status(),    StatusCode::FORBIDDEN);
+		assert_eq!(unsigned.status(), StatusCode::FORBIDDEN);
+	}
+	#[tokio::test]
+	async fn get_release_file__redirect() {
+		let mut auth_core = setup_auth_core(&setup_files());
+		auth_core.config.release_redirect = Some(Url::parse("https://cdn.example.com/releases/").unwrap());
+		let core     = Arc::new(auth_core);
+		let unpacked = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			bearer("full"),
+			Path(Version::new(1, 1, 0)),
+			Query(HashMap::new()),
+		).await.into_response().unpack().unwrap();
+		let crafted  = UnpackedResponse::new(
+			StatusCode::TEMPORARY_REDIRECT,
+			vec![
+				(s!("location"), s!("https://cdn.example.com/releases/test-1.1.0")),
+			],
+			"",
+		);
+		assert_json_eq!(unpacked, crafted);
+		let denied   = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			bearer("limited"),
+			Path(Version::new(1, 1, 0)),
+			Query(HashMap::new()),
+		).await.into_response();
+		let missing  = Axum::get_release_file(
+			Extension(Arc::clone(&core)),
+			bearer("full"),
+			Path(Version::new(8, 7, 6)),
+			Query(HashMap::new()),
+		).await.into_response();
+		assert_eq!(denied.status(),  StatusCode::FORBIDDEN);
+		assert_eq!(missing.status(), StatusCode::NOT_FOUND);
+	}
+	#[tokio::test]
 	async fn get_release_file__missing() {
 		let dir      = setup_files();
 		let core     = Arc::new(setup_core(&dir).unwrap());
 		fs::remove_file(dir.path().join("test-0.0.1")).unwrap();
 		let unpacked = Axum::get_release_file(
 			Extension(Arc::clone(&core)),
+			HeaderMap::new(),
 			Path(Version::new(0, 0, 1)),
+			Query(HashMap::new()),
 		).await.into_response().unpack().unwrap();
 		let crafted  = UnpackedResponse::new(
 			StatusCode::INTERNAL_SERVER_ERROR,
@@ -338,7 +929,7 @@ mod axum {
 	}
 	#[test]
 	fn sign_response__specific_key() {
-		let other_key  = generate_new_private_key();
+		let other_key  = generate_keypair().0;
 		let core       = Arc::new(setup_core(&setup_files()).unwrap());
 		let unpacked   = Axum::sign_response(&core.config.key, Response::builder()
 			.status(StatusCode::OK)
@@ -352,6 +943,13 @@ mod axum {
 		assert_ne!(unpacked.headers[0].value, other_key      .sign(b"This is a test").to_string());
 		assert_eq!(unpacked.body.as_bytes(),  b"This is a test");
 	}
+	
+	//		sign_event															
+	#[tokio::test]
+	async fn sign_event() {
+		let key      = generate_keypair().0;
+		let response = Sse::new(stream::iter([Ok::<_, Infallible>(Axum::sign_event(&key, Version::new(1, 2, 3)))])).into_response();
+		let body     = response.into_body().into_data_stream().next().await.unwrap().unwrap();
+		assert_eq!(body, release_event(&key, "1.2.3"));
+	}
 }
-
-
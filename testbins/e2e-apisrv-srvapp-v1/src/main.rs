@@ -41,11 +41,12 @@ mod common;
 
 use common::server::{initialize, create_basic_server, get_ping};
 use axum::{Router, routing::get};
+use core::time::Duration;
 use figment::{
 	Figment,
 	providers::Env,
 };
-use patchify::client::{Config as UpdaterConfig, Updater};
+use patchify::client::{Config as UpdaterConfig, DrainPolicy, HttpSettings, ProgressPolicy, RestartStrategy, RetryPolicy, UpdatePolicy, Updater};
 use rubedo::crypto::VerifyingKey;
 use semver::Version;
 use serde::Deserialize;
@@ -82,9 +83,27 @@ async fn main() {
 	let _updater = Updater::new(UpdaterConfig {
 		version:          Version::new(1, 0, 0),
 		api:              format!("http://127.0.0.1:{}/api/", config.api_port).parse().unwrap(),
+		mirrors:          vec![],
+		source:           None,
+		http:             HttpSettings::default(),
+		token:            None,
 		key:              config.public_key,
 		check_on_startup: true,
 		check_interval:   None,
+		check_schedule:   None,
+		check_jitter:     Duration::ZERO,
+		push_updates:     false,
+		bundle_dir:       None,
+		update_policy:    UpdatePolicy::Automatic,
+		maintenance:      None,
+		retry_policy:     RetryPolicy::default(),
+		failure_cooldown: Duration::from_secs(60),
+		shutdown_timeout: Duration::from_secs(30),
+		drain_policy:     DrainPolicy::default(),
+		progress_policy:  ProgressPolicy::default(),
+		restart_strategy: RestartStrategy::default(),
+		systemd_notify:   false,
+		consent:          None,
 	}).unwrap();
 	println!("Listening on: {address}");
 	signal::ctrl_c().await.unwrap();
@@ -41,13 +41,28 @@ mod common;
 
 //		Packages																										
 
-use crate::common::{client::request, utils::*};
+use crate::common::client::request;
 use core::{
 	net::SocketAddr,
 	time::Duration,
 };
 use ed25519_dalek::Signer as _;
-use patchify::client::{Config, Status, Updater};
+use patchify::client::{
+	CheckOutcome,
+	Config,
+	DrainPolicy,
+	HttpSettings,
+	ProgressPolicy,
+	RestartStrategy,
+	RetryPolicy,
+	Status,
+	TokenSource,
+	UpdatePolicy,
+	Updater,
+	UpdaterError,
+	source::{HttpSource, UpdateSource as _},
+};
+use patchify::keys::generate_keypair;
 use reqwest::StatusCode;
 use rubedo::{
 	crypto::{Sha256Hash, VerifyingKey},
@@ -66,12 +81,17 @@ use std::{
 };
 use tempfile::tempdir;
 use test_binary::build_test_binary;
-use tokio::time::sleep;
+use tokio::{
+	io::{AsyncReadExt as _, AsyncWriteExt as _},
+	net::TcpListener,
+	spawn,
+	time::sleep,
+};
 use wiremock::{
 	Mock,
 	MockServer,
 	ResponseTemplate,
-	matchers::{method, path},
+	matchers::{header, header_exists, method, path, query_param},
 };
 
 
@@ -110,9 +130,9 @@ mod foundation {
 	async fn ping_test_server() {
 		let testbin_path = build_test_binary("standard-api-server", "testbins").unwrap();
 		let mut subproc  = Command::new(testbin_path).stdout(Stdio::piped()).spawn().unwrap();
-		let reader       = BufReader::new(subproc.stdout.take().unwrap());
+		let mut reader   = BufReader::new(subproc.stdout.take().unwrap());
 		let mut address  = String::new();
-		for l in reader.lines() {
+		for l in (&mut reader).lines() {
 			let line     = l.unwrap();
 			if line.contains("Listening on") {
 				line.split_whitespace().last().unwrap().clone_into(&mut address);
@@ -149,9 +169,27 @@ mod mock_actions {
 		let _updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              *EMPTY_PUBLIC_KEY,
 			check_on_startup: true,
 			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		sleep(Duration::from_millis(50)).await;
 	}
@@ -167,9 +205,27 @@ mod mock_actions {
 		let _updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              *EMPTY_PUBLIC_KEY,
 			check_on_startup: false,
 			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		sleep(Duration::from_millis(100)).await;
 	}
@@ -185,9 +241,27 @@ mod mock_actions {
 		let _updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              *EMPTY_PUBLIC_KEY,
 			check_on_startup: true,
 			check_interval:   Some(Duration::from_millis(50)),
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		sleep(Duration::from_millis(125)).await;
 	}
@@ -203,9 +277,27 @@ mod mock_actions {
 		let _updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              *EMPTY_PUBLIC_KEY,
 			check_on_startup: false,
 			check_interval:   Some(Duration::from_millis(50)),
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		sleep(Duration::from_millis(125)).await;
 	}
@@ -221,7 +313,7 @@ mod mock_actions {
 	#[tokio::test]
 	async fn check_for_updates__no_update_available() {
 		let mock_server = MockServer::start().await;
-		let private_key = generate_new_private_key();
+		let private_key = generate_keypair().0;
 		let json_data   = json!({
 			"version": "1.0.0",
 		});
@@ -239,9 +331,27 @@ mod mock_actions {
 		let updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              private_key.verifying_key(),
 			check_on_startup: true,
 			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		sleep(Duration::from_millis(50)).await;
 		//	TODO: Should gain more insight into the outcome at some point, through
@@ -249,10 +359,548 @@ mod mock_actions {
 		assert_eq!(updater.status(), Status::Idle);
 	}
 	#[tokio::test]
+	async fn check_for_updates__static_hosting() {
+		let mock_server = MockServer::start().await;
+		let private_key = generate_keypair().0;
+		let json_data   = json!({
+			"version": "1.0.0",
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/octet-stream")
+					.set_body_json(&json_data)
+			)
+			.expect(1)
+			.mount(&mock_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path("/api/latest.sig"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/octet-stream")
+					.set_body_string(private_key.sign(json_data.to_string().as_ref()).to_string())
+			)
+			.expect(1)
+			.mount(&mock_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpToDate(Version::new(1, 0, 0)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__http_settings() {
+		let mock_server = MockServer::start().await;
+		let private_key = generate_keypair().0;
+		let json_data   = json!({
+			"version": "1.0.0",
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.and(header("User-Agent", "test-app/1.0.0"))
+			.and(header("X-Api-Key",  "secret"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data.to_string().as_ref()).to_string())
+					.set_body_json(json_data)
+			)
+			.expect(1)
+			.mount(&mock_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings {
+				connect_timeout: Some(Duration::from_secs(5)),
+				read_timeout:    Some(Duration::from_secs(5)),
+				user_agent:      Some("test-app/1.0.0".to_owned()),
+				headers:         vec![("X-Api-Key".to_owned(), "secret".to_owned())],
+				..HttpSettings::default()
+			},
+			token:            None,
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::UpToDate(Version::new(1, 0, 0)));
+	}
+	#[tokio::test]
+	async fn check_for_updates__api_token() {
+		let mock_server = MockServer::start().await;
+		let private_key = generate_keypair().0;
+		let json_data   = json!({
+			"version": "1.0.0",
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.and(header("Authorization", "Bearer secret"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data.to_string().as_ref()).to_string())
+					.set_body_json(json_data)
+			)
+			.expect(1)
+			.mount(&mock_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(ResponseTemplate::new(401))
+			.expect(1)
+			.mount(&mock_server).await
+		;
+		let api    = format!("{}/api/", mock_server.uri());
+		let config = Config {
+			version:          Version::new(1, 0, 0),
+			api:              api.parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            Some(TokenSource::Static("secret".to_owned())),
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::ZERO,
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		};
+		let allowed = Updater::new(config.clone()).unwrap();
+		assert_eq!(allowed.check_for_updates().await, CheckOutcome::UpToDate(Version::new(1, 0, 0)));
+		let denied  = Updater::new(Config { token: Some(TokenSource::Static("wrong".to_owned())), ..config }).unwrap();
+		assert_eq!(denied.check_for_updates().await, CheckOutcome::Failed(UpdaterError::Unauthorized(format!("{api}latest").parse().unwrap())));
+	}
+	#[tokio::test]
+	async fn check_for_updates__download_url() {
+		let api_server  = MockServer::start().await;
+		let cdn_server  = MockServer::start().await;
+		let version     = Version::new(2, 3, 4);
+		let private_key = generate_keypair().0;
+		let payload     = b"Test payload";
+		let json_data1  = json!({
+			"version": version,
+		});
+		let json_data2  = json!({
+			"version": version,
+			"hash":    hex::encode(Sha256::digest(payload)),
+			"url":     format!("{}/releases/{version}?expires=4102444800&signature=abcd", cdn_server.uri()),
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data1.to_string().as_ref()).to_string())
+					.set_body_json(json_data1)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/hashes/{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data2.to_string().as_ref()).to_string())
+					.set_body_json(json_data2)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/releases/{version}")))
+			.respond_with(ResponseTemplate::new(500))
+			.expect(0)
+			.mount(&api_server).await
+		;
+		//	The API token must not be sent to the download host
+		Mock::given(method("GET"))
+			.and(header_exists("Authorization"))
+			.respond_with(ResponseTemplate::new(500))
+			.expect(0)
+			.mount(&cdn_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/releases/{version}")))
+			.and(query_param("expires",   "4102444800"))
+			.and(query_param("signature", "abcd"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/octet-stream")
+					.set_body_bytes(payload.to_vec())
+			)
+			.expect(1)
+			.mount(&cdn_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", api_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            Some(TokenSource::Static("secret".to_owned())),
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::DownloadOnly,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::ZERO,
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(version));
+	}
+	#[tokio::test]
+	async fn check_for_updates__redirect() {
+		let api_server  = MockServer::start().await;
+		let cdn_server  = MockServer::start().await;
+		let version     = Version::new(2, 3, 4);
+		let private_key = generate_keypair().0;
+		let payload     = b"Test payload";
+		let json_data1  = json!({
+			"version": version,
+		});
+		let json_data2  = json!({
+			"version": version,
+			"hash":    hex::encode(Sha256::digest(payload)),
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data1.to_string().as_ref()).to_string())
+					.set_body_json(json_data1)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/hashes/{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data2.to_string().as_ref()).to_string())
+					.set_body_json(json_data2)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/releases/{version}")))
+			.and(header("Authorization", "Bearer secret"))
+			.respond_with(
+				ResponseTemplate::new(307)
+					.append_header("Location", format!("{}/releases/test-{version}", cdn_server.uri()))
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		//	The API token must not be passed on to the external location
+		Mock::given(method("GET"))
+			.and(header_exists("Authorization"))
+			.respond_with(ResponseTemplate::new(500))
+			.expect(0)
+			.mount(&cdn_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/releases/test-{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/octet-stream")
+					.set_body_bytes(payload.to_vec())
+			)
+			.expect(1)
+			.mount(&cdn_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", api_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            Some(TokenSource::Static("secret".to_owned())),
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::DownloadOnly,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::ZERO,
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(version));
+	}
+	#[tokio::test]
+	async fn check_for_updates__redirect_without_content_length() {
+		let api_server  = MockServer::start().await;
+		let version     = Version::new(2, 3, 4);
+		let private_key = generate_keypair().0;
+		let json_data1  = json!({
+			"version": version,
+		});
+		let json_data2  = json!({
+			"version": version,
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		});
+		//	External storage often sends files in chunks, without a length
+		let cdn_server  = TcpListener::bind("127.0.0.1:0").await.unwrap();
+		let cdn_address = cdn_server.local_addr().unwrap();
+		drop(spawn(async move {
+			let (mut stream, _) = cdn_server.accept().await.unwrap();
+			let mut request     = vec![];
+			while !request.ends_with(b"\r\n\r\n") {
+				let mut byte = [0_u8; 1];
+				_ = stream.read_exact(&mut byte).await.unwrap();
+				request.push(byte[0]);
+			}
+			stream.write_all(concat!(
+				"HTTP/1.1 200 OK\r\n",
+				"Content-Type: application/octet-stream\r\n",
+				"Transfer-Encoding: chunked\r\n",
+				"Connection: close\r\n",
+				"\r\n",
+				"5\r\nTest \r\n",
+				"7\r\npayload\r\n",
+				"0\r\n\r\n",
+			).as_bytes()).await.unwrap();
+		}));
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data1.to_string().as_ref()).to_string())
+					.set_body_json(json_data1)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/hashes/{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data2.to_string().as_ref()).to_string())
+					.set_body_json(json_data2)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/releases/{version}")))
+			.respond_with(
+				ResponseTemplate::new(307)
+					.append_header("Location", format!("http://{cdn_address}/releases/test-{version}"))
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", api_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::DownloadOnly,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::ZERO,
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Downloaded(version));
+	}
+	#[tokio::test]
+	async fn http_source__redirect_policy() {
+		let api_server = MockServer::start().await;
+		let url        = format!("{}/api/latest", api_server.uri());
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(307)
+					.append_header("Location", url.as_str())
+			)
+			.mount(&api_server).await
+		;
+		//	The default source follows redirects with the same policy as configured
+		//	settings, and so gives the reason for refusing this one
+		let source = HttpSource::new(format!("{}/api/", api_server.uri()).parse().unwrap(), vec![], Duration::from_secs(60));
+		let err    = source.latest().await.unwrap_err();
+		assert_eq!(err, UpdaterError::HttpRequestFailed(
+			url.parse().unwrap(),
+			format!("error following redirect for url ({url}): Too many redirects, stopped at {url}"),
+		));
+	}
+	#[tokio::test]
+	async fn check_for_updates__redirect_unexpected_content() {
+		let api_server  = MockServer::start().await;
+		let cdn_server  = MockServer::start().await;
+		let version     = Version::new(2, 3, 4);
+		let private_key = generate_keypair().0;
+		let json_data1  = json!({
+			"version": version,
+		});
+		let json_data2  = json!({
+			"version": version,
+			"hash":    hex::encode(Sha256::digest(b"Test payload")),
+		});
+		Mock::given(method("GET"))
+			.and(path("/api/latest"))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data1.to_string().as_ref()).to_string())
+					.set_body_json(json_data1)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/hashes/{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.append_header("Content-Type", "application/json")
+					.append_header("X-Signature",  private_key.sign(json_data2.to_string().as_ref()).to_string())
+					.set_body_json(json_data2)
+			)
+			.expect(1)
+			.mount(&api_server).await
+		;
+		Mock::given(method("GET"))
+			.and(path(format!("/api/releases/{version}")))
+			.respond_with(
+				ResponseTemplate::new(307)
+					.append_header("Location", format!("{}/releases/test-{version}", cdn_server.uri()))
+			)
+			.mount(&api_server).await
+		;
+		//	An error page from the external location must not be taken as the release
+		Mock::given(method("GET"))
+			.and(path(format!("/releases/test-{version}")))
+			.respond_with(
+				ResponseTemplate::new(200)
+					.set_body_raw("<html>Access denied</html>", "text/html")
+			)
+			.mount(&cdn_server).await
+		;
+		let updater = Updater::new(Config {
+			version:          Version::new(1, 0, 0),
+			api:              format!("{}/api/", api_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
+			key:              private_key.verifying_key(),
+			check_on_startup: false,
+			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::DownloadOnly,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::ZERO,
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
+		}).unwrap();
+		assert_eq!(updater.check_for_updates().await, CheckOutcome::Failed(UpdaterError::UnexpectedContentType(
+			format!("{}/releases/test-{version}", cdn_server.uri()).parse().unwrap(),
+			"text/html".to_owned(),
+			"application/octet-stream".to_owned(),
+		)));
+	}
+	#[tokio::test]
 	async fn check_for_updates__restart_blocked() {
 		let mock_server = MockServer::start().await;
 		let version     = Version::new(2, 3, 4);
-		let private_key = generate_new_private_key();
+		let private_key = generate_keypair().0;
 		let payload     = b"Test payload";
 		let json_data1  = json!({
 			"version": version,
@@ -298,15 +946,33 @@ mod mock_actions {
 		let updater = Updater::new(Config {
 			version:          Version::new(1, 0, 0),
 			api:              format!("{}/api/", mock_server.uri()).parse().unwrap(),
+			mirrors:          vec![],
+			source:           None,
+			http:             HttpSettings::default(),
+			token:            None,
 			key:              private_key.verifying_key(),
 			check_on_startup: true,
 			check_interval:   None,
+			check_schedule:   None,
+			check_jitter:     Duration::ZERO,
+			push_updates:     false,
+			bundle_dir:       None,
+			update_policy:    UpdatePolicy::Automatic,
+			maintenance:      None,
+			retry_policy:     RetryPolicy::default(),
+			failure_cooldown: Duration::from_secs(60),
+			shutdown_timeout: Duration::from_secs(30),
+			drain_policy:     DrainPolicy::default(),
+			progress_policy:  ProgressPolicy::default(),
+			restart_strategy: RestartStrategy::default(),
+			systemd_notify:   false,
+			consent:          None,
 		}).unwrap();
 		let _ = updater.register_action();
 		sleep(Duration::from_millis(100)).await;
 		//	We've registered a critical action, so the installation will be blocked,
 		//	which is what we want here, so that we can check the status is correct
-		assert_eq!(updater.status(), Status::PendingRestart(version.clone()));
+		assert_eq!(updater.status(), Status::PendingRestart(version.clone(), 1, None));
 		//	Assuming the update process ran correctly, we now need to rename the
 		//	test binary back to its original name, so that the next test can run
 		let path = current_exe().unwrap();
@@ -348,11 +1014,11 @@ mod test_actions {
 			.stdout(Stdio::piped())
 			.spawn().unwrap()
 		;
+		let mut srv_reader  = BufReader::new(subproc_srv.stdout.take().unwrap());
 		let (srv_address, public_key) = {
-			let reader          = BufReader::new(subproc_srv.stdout.take().unwrap());
 			let mut address     = None;
 			let mut public_key  = None;
-			for l in reader.lines() {
+			for l in (&mut srv_reader).lines() {
 				let line        = l.unwrap();
 				if line.contains("Listening on") {
 					address     = Some(line.split_whitespace().last().unwrap().to_owned());
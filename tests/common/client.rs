@@ -30,12 +30,13 @@ pub async fn request(url: String, public_key: Option<VerifyingKey>) -> (StatusCo
 	let content_len  = response.headers().get(CONTENT_LENGTH).and_then(|h| h.to_str().ok()).and_then(|s| s.parse::<usize>().ok());
 	let signature    = response.headers().get("x-signature") .and_then(|h| h.to_str().ok()).unwrap_or("").to_owned();
 	let body         = response.bytes().await.unwrap();
-	let verified     = if public_key.is_none() || signature.is_empty() {
-		None
-	} else {
-		let signature_bytes            = hex::decode(signature).unwrap();
-		let signature_array: &[u8; 64] = signature_bytes.as_slice().try_into().unwrap();
-		Some(public_key.unwrap().verify_strict(&body, &Signature::from_bytes(signature_array)).is_ok())
+	let verified     = match public_key {
+		Some(key) if !signature.is_empty() => {
+			let signature_bytes            = hex::decode(signature).unwrap();
+			let signature_array: &[u8; 64] = signature_bytes.as_slice().try_into().unwrap();
+			Some(key.verify_strict(&body, &Signature::from_bytes(signature_array)).is_ok())
+		},
+		_ => None,
 	};
 	(status, content_type, content_len, verified, body)
 }
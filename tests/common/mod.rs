@@ -5,7 +5,4 @@
 pub mod client;
 pub mod server;
 
-#[path = "../../src/tests/common/utils.rs"]
-pub mod utils;
-
 
@@ -2,7 +2,6 @@
 
 //		Packages																										
 
-use crate::common::utils::generate_new_private_key;
 use axum::{
 	Extension,
 	Router,
@@ -19,6 +18,7 @@ use patchify::server::{
 	Config as PatchifyConfig,
 	Core as PatchifyCore,
 };
+use patchify::keys::generate_keypair;
 use rubedo::{
 	crypto::{Sha256Hash, SigningKey},
 	std::ByteSized as _,
@@ -92,7 +92,7 @@ pub fn initialize() {
 			)
 			.init()
 		;
-		KEY.set(generate_new_private_key()).unwrap();
+		KEY.set(generate_keypair().0).unwrap();
 	});
 }
 
@@ -163,6 +163,9 @@ pub async fn create_patchify_api_server(
 		stream_buffer:    256,
 		read_buffer:      128,
 		versions,
+		tokens:           None,
+		signed_urls:      None,
+		release_redirect: None,
 	}).unwrap();
 	let allocated_address = create_basic_server(
 		address,
@@ -203,6 +206,7 @@ pub fn patchify_api_routes() -> Router {
 	Router::new()
 		.route("/api/ping",              get(get_ping))
 		.route("/api/latest",            get(Patchify::get_latest_version))
+		.route("/api/events",            get(Patchify::get_events))
 		.route("/api/hashes/{version}",   get(Patchify::get_hash_for_version))
 		.route("/api/releases/{version}", get(Patchify::get_release_file))
 }
@@ -40,8 +40,9 @@ mod common;
 
 //		Packages																										
 
-use crate::common::{client::*, server::*, utils::*};
+use crate::common::{client::*, server::*};
 use assert_json_diff::assert_json_eq;
+use patchify::keys::generate_keypair;
 use reqwest::StatusCode;
 use rubedo::{
 	crypto::Sha256Hash,
@@ -98,7 +99,7 @@ mod endpoints {
 	#[tokio::test]
 	async fn get_latest__fail_signature_verification() {
 		initialize();
-		let other_public_key   = generate_new_private_key().verifying_key();
+		let other_public_key   = generate_keypair().0.verifying_key();
 		let (address, _releases_dir) = create_test_server().await;
 		let (status, content_type, content_len, verified, body) = request(
 			format!("http://{address}/api/latest"),